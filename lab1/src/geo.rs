@@ -14,18 +14,10 @@ pub enum LineType {
 use LineType::*;
 
 #[derive(Debug)]
-struct Line {
+pub struct Line {
     a: Point,
     b: Point,
 }
-impl Line {
-    pub fn new(a: &Point, b: &Point) -> Self {
-        Self {
-            a: a.clone(),
-            b: b.clone(),
-        }
-    }
-}
 
 impl Point {
     pub fn new(x: impl Into<f64>, y: impl Into<f64>) -> Self {
@@ -38,8 +30,7 @@ impl Point {
     pub fn distance(first: &Point, second: &Point) -> f64 {
         let dx = second.x - first.x;
         let dy = second.y - first.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-        distance
+        (dx * dx + dy * dy).sqrt()
     }
 
     fn calculate_bisect(first: &Point, second: &Point, third: &Point) -> Line {
@@ -116,16 +107,7 @@ fn line_fmt(line: &Line) -> LineType {
     let b = line.a.y - k * line.a.x;
     Tan(k, b)
 }
-#[derive(Debug)]
-pub struct Res {
-    tria1: (Line, Line, Line),
-    bis1: (Line, Line, Line),
-    tria2: (Line, Line, Line),
-    bis2: (Line, Line, Line),
-    con: Line,
-}
-
-pub fn calc(a: &Vec<Point>, b: &Vec<Point>) -> Option<Res> {
+pub fn calc(a: &[Point], b: &[Point]) -> Option<Line> {
     let mut max_angle = (2.0 * PI, None);
     for (x, y) in iproduct!(a.iter().combinations(3), b.iter().combinations(3)) {
         let (x0, x1, x2) = (x[0], x[1], x[2]);
@@ -134,12 +116,10 @@ pub fn calc(a: &Vec<Point>, b: &Vec<Point>) -> Option<Res> {
         if point_form_triangle(x0, x1, x2) && point_form_triangle(y0, y1, y2) {
             let bis_a = Point::calculate_bisect(x0, x1, x2);
             let bis_b = Point::calculate_bisect(x1, x2, x0);
-            let bis_c = Point::calculate_bisect(x2, x0, x1);
             let c1 = Point::calculate_intersection(&bis_a.a, &bis_a.b, &bis_b.a, &bis_b.b);
 
             let bis_a2 = Point::calculate_bisect(y0, y1, y2);
             let bis_b2 = Point::calculate_bisect(y1, y2, y0);
-            let bis_c2 = Point::calculate_bisect(y2, y0, y1);
             let c2 = Point::calculate_intersection(&bis_a2.a, &bis_a2.b, &bis_b2.a, &bis_b2.b);
 
             println!("{:?} {:?}", c1, c2);
@@ -155,13 +135,7 @@ pub fn calc(a: &Vec<Point>, b: &Vec<Point>) -> Option<Res> {
             if let Some(line) = new_line {
                 let new_angle = ord_angle(line_fmt(&line));
                 if new_angle <= max_angle.0 {
-                    max_angle = (new_angle, Some(Res {
-                        tria1: (Line::new(x0, x1), Line::new(x1, x2), Line::new(x2, x0)),
-                        tria2: (Line::new(y0, y1), Line::new(y1, y2), Line::new(y2, y0)),
-                        bis1: (bis_a, bis_b, bis_c),
-                        bis2: (bis_a2, bis_b2, bis_c2),
-                        con: line,
-                    }));
+                    max_angle = (new_angle, Some(line));
                 }
             }
         }
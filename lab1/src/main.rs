@@ -3,7 +3,9 @@ use eframe::epaint::Color32;
 use eframe::{egui::Ui, Theme};
 use egui::Align2;
 use egui_extras::{Column, TableBuilder};
-use egui_plot::{Plot, Points};
+use egui_plot::{Line, Plot, Points};
+use raster::bezier::Flattening;
+use raster::spline::{Kind, Spline};
 
 pub mod geo;
 use geo::{Point};
@@ -27,6 +29,8 @@ struct MyApp {
     edit_buf_y: String,
     show_edit: bool,
     to_edit: usize,
+    spline: Option<Kind>,
+    closed: bool,
 }
 
 impl eframe::App for MyApp {
@@ -54,6 +58,13 @@ impl eframe::App for MyApp {
                             self.dots2.clear();
                         }
                     });
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("Сплайн");
+                        ui.radio_value(&mut self.spline, None, "Нет");
+                        ui.radio_value(&mut self.spline, Some(Kind::BSpline), "B-сплайн");
+                        ui.radio_value(&mut self.spline, Some(Kind::CatmullRom), "Катмулл — Ром");
+                        ui.checkbox(&mut self.closed, "Замкнутый");
+                    });
                     self.input_coords(ui);
                     self.update_table(ui);
                     self.calculate_triangle(ui);
@@ -110,7 +121,6 @@ impl MyApp {
                             self.show_error = true;
                         }
                         self.show_edit = false;
-                        self.show_error = false;
                         self.edit_buf_x.clear();
                         self.edit_buf_y.clear();
                     }
@@ -172,11 +182,10 @@ impl MyApp {
                 });
             })
             .body(|mut body| {
-                let data;
-                match self.set {
-                    Set::First => data = self.dots1.clone(),
-                    Set::Second => data = self.dots2.clone(),
-                }
+                let data = match self.set {
+                    Set::First => self.dots1.clone(),
+                    Set::Second => self.dots2.clone(),
+                };
                 for (c, i) in data.iter().enumerate() {
                     body.row(30.0, |mut row| {
                         row.col(|ui| {
@@ -209,6 +218,22 @@ impl MyApp {
             });
     }
 
+    /// Сплайн через точки множества; `None`, если сплайн не выбран или
+    /// точек мало.
+    fn spline_line(&self, dots: &[Point], color: Color32) -> Option<Line> {
+        let points = dots
+            .iter()
+            .map(|p| raster::point(p.x as f32, p.y as f32))
+            .collect::<Vec<_>>();
+        let spline = Spline::new(self.spline?, &points, self.closed).ok()?;
+        let vertices = spline
+            .flatten(Flattening::Subdivision, 0.01)
+            .into_iter()
+            .map(|p| [p.x as f64, p.y as f64])
+            .collect::<Vec<_>>();
+        Some(Line::new(vertices).color(color))
+    }
+
     fn update_plot(&mut self, ui: &mut Ui) {
         let plot = Plot::new("plot")
             .data_aspect(1.0)
@@ -230,7 +255,14 @@ impl MyApp {
             .show_y(false)
             .auto_bounds(Vec2b { x: false, y: false });
 
+        let splines = [
+            self.spline_line(&self.dots1, Color32::RED),
+            self.spline_line(&self.dots2, Color32::GREEN),
+        ];
         plot.show(ui, |plot_ui| {
+            for line in splines.into_iter().flatten() {
+                plot_ui.line(line);
+            }
             let points1 = Points::new(
                 self.dots1
                     .iter()
@@ -1,18 +1,15 @@
 use std::collections::HashMap;
-use nalgebra::Scale;
 use std::f32::consts::PI;
 
 pub struct Spacing {
     b: f32,
     e: f32,
     d: f32,
-    amount: usize,
 }
 
 impl Spacing {
     pub fn new(min: f32, max: f32, step: f32) -> Self {
-        let amount = ((max - min) / step).ceil() as usize;
-        Self { b: min, e: max, d: step, amount }
+        Self { b: min, e: max, d: step }
     }
 }
 
@@ -26,7 +23,6 @@ pub struct Horizont {
 
 impl Horizont {
     pub fn new(screen_size: (usize, usize), f: fn(f32, f32) -> f32, scale: f32) -> Self {
-        let (width, _) = screen_size;
         let down = HashMap::new();
         let top = HashMap::new();
         Self { screen_size, down, top, f, scale }
@@ -151,7 +147,7 @@ impl Horizont {
         *y = tetaz.sin() * buf + tetaz.cos() * *y;
     }
 
-    fn transform(&self, x: &mut f32, y: &mut f32, z: &mut f32, tetax: f32, tetay: f32, tetaz: f32, res_x: &mut isize, res_y: &mut isize, coef: f32) {
+    fn transform(&self, x: &mut f32, y: &mut f32, z: &mut f32, tetax: f32, tetay: f32, tetaz: f32, res_x: &mut isize, res_y: &mut isize) {
         let (width, height) = self.screen_size;
         let x_center = width as f32 / 2.0;
         let y_center = height as f32 / 2.0;
@@ -165,7 +161,7 @@ impl Horizont {
         *res_y = (y_tmp * self.scale + y_center).round() as isize;
     }
 
-    pub fn horizon_algo(&mut self, mut par_x: Spacing, par_z: Spacing, painter: &mut Vec<(isize, isize, isize, isize)>, tetax: f32, tetay: f32, tetaz: f32, coef: f32) {
+    pub fn horizon_algo(&mut self, mut par_x: Spacing, par_z: Spacing, painter: &mut Vec<(isize, isize, isize, isize)>, tetax: f32, tetay: f32, tetaz: f32) {
         self.prepare_arrays();
 
         let mut x_left = -1;
@@ -176,8 +172,8 @@ impl Horizont {
         let mut y_prev = 0;
         let mut z = par_z.e;
         while z >= par_z.b {
-            let mut y_p = (self.f)(par_x.b, z as f32);
-            self.transform(&mut par_x.b, &mut y_p, &mut (z as f32), tetax, tetay, tetaz, &mut x_prev, &mut y_prev, self.scale);
+            let mut y_p = (self.f)(par_x.b, z);
+            self.transform(&mut par_x.b, &mut y_p, &mut { z }, tetax, tetay, tetaz, &mut x_prev, &mut y_prev);
             self.process_edge(&mut x_prev, &mut y_prev, &mut x_left, &mut y_left, painter);
             let mut p_flag = self.visible(x_prev, y_prev);
             let mut x = par_x.b;
@@ -186,9 +182,9 @@ impl Horizont {
                 let mut y_curr = 0;
                 let mut xi = 0;
                 let mut yi = 0;
-                y_p = (self.f)(x as f32, z as f32);
-                self.transform(&mut (x as f32), &mut y_p, &mut (z as f32), tetax, tetay, tetaz, &mut x_curr, &mut y_curr, self.scale);
-                if self.top.get(&x_curr).is_none() || self.down.get(&x_curr).is_none() {
+                y_p = (self.f)(x, z);
+                self.transform(&mut { x }, &mut y_p, &mut { z }, tetax, tetay, tetaz, &mut x_curr, &mut y_curr);
+                if !self.top.contains_key(&x_curr) || !self.down.contains_key(&x_curr) {
                     x += par_x.d;
                     continue;
                 }
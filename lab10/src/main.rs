@@ -1,10 +1,8 @@
 mod logic;
 
 
-use std::fmt::Display;
 use eframe::egui;
 use eframe::egui::{Color32, Pos2};
-use epaint::{pos2, Stroke};
 use logic::utils::*;
 use logic::windows::ErrorWindow;
 
@@ -48,19 +46,16 @@ impl FUNCS {
             FUNCS::C => |x: f32, z: f32| {
                 x.cos() * z.sin()
             },
-            FUNCS::D => |x: f32, z: f32| {
+            FUNCS::D => |x: f32, _z: f32| {
                 x.sin()
             },
-            FUNCS::E => |x: f32, z: f32| {
+            FUNCS::E => |_x: f32, z: f32| {
                 z.cos()
             },
             FUNCS::F => |x: f32, z: f32| {
                 let r  = (x * x +z * z).sqrt();
                 8. * (1.2 * r).cos() / (r + 1.)
             },
-            _ => |x: f32, z: f32| {
-                    x
-                }
         }
     }
 }
@@ -202,7 +197,7 @@ impl MyApp {
 
 impl MyApp {
     fn painter(&mut self, ui: &mut egui::Ui) {
-        let (response, painter) =
+        let (_, painter) =
             ui.allocate_painter(ui.available_size(), egui::Sense::click_and_drag());
         painter.rect(
             painter.clip_rect().shrink(0.0),
@@ -210,12 +205,6 @@ impl MyApp {
             self.background,
             egui::Stroke::new(0.5, egui::Color32::BLACK),
         );
-        let p_rect = response.rect;
-
-        let to_screen = egui::emath::RectTransform::from_to(
-            egui::Rect::from_min_size(egui::Pos2::ZERO, p_rect.size()),
-            response.rect.translate(egui::Pos2::ZERO.to_vec2()),
-        );
         let ppp = ui.ctx().pixels_per_point();
         let unit = ppp.recip();
         painter.extend(self.lines.iter().map(|&(a, b)| {
@@ -257,7 +246,7 @@ impl MyApp {
             let mut hor = Horizont::new((950, 650), f, s);
             let mut res = vec![];
             self.lines.clear();
-            hor.horizon_algo(Spacing::new(xs, xe, xh), Spacing::new(zs, ze, zh),&mut res,  ax, ay, az, s);
+            hor.horizon_algo(Spacing::new(xs, xe, xh), Spacing::new(zs, ze, zh),&mut res,  ax, ay, az);
             for i in res {
                 self.lines.push(((i.0 as f32, i.1 as f32).into(), (i.2 as f32, i.3 as f32).into()))
             }
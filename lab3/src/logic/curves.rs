@@ -0,0 +1,27 @@
+use raster::bezier::{Bezier, Flattening};
+use raster::point;
+
+/// Опорные точки кривой по умолчанию.
+pub(crate) fn default_curve(cubic: bool) -> Bezier {
+    if cubic {
+        Bezier::Cubic([
+            point(150.0, 500.0),
+            point(300.0, 100.0),
+            point(650.0, 100.0),
+            point(800.0, 500.0),
+        ])
+    } else {
+        Bezier::Quadratic([
+            point(150.0, 500.0),
+            point(475.0, 100.0),
+            point(800.0, 500.0),
+        ])
+    }
+}
+
+pub(crate) fn flattening_title(flattening: Flattening) -> &'static str {
+    match flattening {
+        Flattening::Subdivision => "Адаптивное деление",
+        Flattening::ForwardDifferencing => "Прямые разности",
+    }
+}
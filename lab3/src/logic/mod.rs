@@ -1,3 +1,3 @@
 pub mod algo;
-pub mod utils;
+pub mod curves;
 pub mod windows;
@@ -1,5 +1,6 @@
 use eframe::egui;
 use eframe::egui::{Align2, Color32};
+use itertools::Itertools;
 
 #[derive(Default, Debug)]
 pub struct ErrorWindow {
@@ -54,11 +55,13 @@ impl ErrorWindow {
 pub struct HistoWindow {
     enabled: bool,
     data: Vec<(String, u128)>,
+    len: u32,
 }
 impl HistoWindow {
-    pub fn set_values(&mut self, data: &[(String, u128)]) {
+    pub fn set_values(&mut self, data: &[(String, u128)], len: u32) {
         self.enabled = true;
         self.data = data.into();
+        self.len = len;
     }
 
     pub fn enabled(&self) -> bool {
@@ -70,10 +73,6 @@ impl HistoWindow {
         self.data.clear();
     }
 
-    pub fn enable(&mut self) {
-        self.enabled = true;
-    }
-
     pub fn update(&mut self, ctx: &egui::Context) {
         egui::Window::new("Гистограмма".to_owned())
             .anchor(Align2::CENTER_CENTER, [0.0; 2])
@@ -101,20 +100,23 @@ impl HistoWindow {
                                 Color32::KHAKI,
                                 Color32::DARK_GREEN,
                                 Color32::GRAY,
+                                Color32::BROWN,
                             ];
                             for (i, x) in self.data.iter().enumerate() {
+                                let color = colors[i % colors.len()];
                                 plot_ui.bar_chart({
                                     egui_plot::BarChart::new(
                                         [egui_plot::Bar::new(i as f64 * 20.0, x.1 as f64)
                                             .width(10.0)
-                                            .fill(colors[i])]
+                                            .fill(color)]
                                         .to_vec(),
                                     )
                                     .name(x.0.clone())
-                                    .color(colors[i])
+                                    .color(color)
                                 })
                             }
                         });
+                    ui.label(format!("Спектр отрезков длины {} с шагом 15°", self.len));
                     ui.separator();
                     ui.add_space(20.0);
                     if ui.button("Ок").clicked() {
@@ -146,14 +148,10 @@ impl GraphWindow {
         self.enabled = false;
     }
 
-    pub fn enable(&mut self) {
-        self.enabled = true;
-    }
-
-    pub fn graph(points: &Vec<(i32, i32)>, color: Color32) -> egui_plot::Line {
+    pub fn graph(points: &[(i32, i32)], color: Color32) -> egui_plot::Line {
         egui_plot::Line::new(egui_plot::PlotPoints::new({
             points
-                .into_iter()
+                .iter()
                 .map(|(x, y)| [x.to_owned() as f64, y.to_owned() as f64])
                 .collect::<Vec<[f64; 2]>>()
         }))
@@ -186,18 +184,24 @@ impl GraphWindow {
             .max_width(100.0)
             .show(ctx, |ui| {
                 ui.vertical_centered_justified(|ui| {
-                    ui.horizontal(|ui| {
-                        self.plot_graph(ui, &self.data[0], Color32::RED);
-                        self.plot_graph(ui, &self.data[1], Color32::GREEN);
-                        // self.plot_graph(ui, &self.data[1], Color32::GREEN);
-                    });
-                    ui.end_row();
-                    ui.horizontal(|ui| {
-                        self.plot_graph(ui, &self.data[2], Color32::BLUE);
-                        self.plot_graph(ui, &self.data[3], Color32::KHAKI);
-                        self.plot_graph(ui, &self.data[4], Color32::BROWN);
-                    });
-                    ui.end_row();
+                    let colors = [
+                        Color32::RED,
+                        Color32::GREEN,
+                        Color32::BLUE,
+                        Color32::KHAKI,
+                        Color32::BROWN,
+                        Color32::DARK_GREEN,
+                        Color32::GRAY,
+                    ];
+                    let graphs = self.data.iter().zip(colors.into_iter().cycle());
+                    for row in &graphs.chunks(4) {
+                        ui.horizontal(|ui| {
+                            for (graph, color) in row {
+                                self.plot_graph(ui, graph, color);
+                            }
+                        });
+                        ui.end_row();
+                    }
                     ui.label("Y - количество ступенек; X - Угол наклона (от 0 до 90)");
                     ui.label(format!("Длина {:?}", self.len));
                     if ui.button("Ок").clicked() {
@@ -207,3 +211,74 @@ impl GraphWindow {
             });
     }
 }
+
+#[derive(Default, Debug)]
+pub struct TimeWindow {
+    enabled: bool,
+    data: Vec<(String, Vec<(i32, f64)>)>,
+    title: String,
+}
+impl TimeWindow {
+    pub fn set_values(&mut self, data: &[(String, Vec<(i32, f64)>)], title: String) {
+        self.enabled = true;
+        self.data = data.into();
+        self.title = title;
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn disable(&mut self) {
+        self.enabled = false;
+    }
+
+    pub fn graph(points: &(String, Vec<(i32, f64)>), color: Color32) -> egui_plot::Line {
+        egui_plot::Line::new(egui_plot::PlotPoints::new({
+            points
+                .1
+                .iter()
+                .map(|(x, y)| [*x as f64, *y])
+                .collect::<Vec<[f64; 2]>>()
+        }))
+        .color(color)
+        .name(points.0.clone())
+    }
+
+    pub fn plot_graph(&self, ui: &mut egui::Ui) {
+        egui_plot::Plot::new("Время кривых")
+            .legend(egui_plot::Legend::default().position(egui_plot::Corner::LeftTop))
+            .width(1000.0)
+            .height(500.0)
+            .allow_scroll(false)
+            .allow_boxed_zoom(false)
+            .set_margin_fraction([0.2, 0.2].into())
+            .allow_double_click_reset(false)
+            .allow_drag(false)
+            .x_axis_label("Размер кривой")
+            .y_axis_label("Время (мкс)")
+            .show(ui, |plot_ui| {
+                let colors = [Color32::RED, Color32::BLUE];
+                for (data, color) in self.data.iter().zip(colors.into_iter().cycle()) {
+                    plot_ui.line(Self::graph(data, color));
+                }
+            });
+    }
+
+    pub fn update(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Сравнение времени кривых".to_owned())
+            .anchor(Align2::CENTER_CENTER, [0.0; 2])
+            .resizable(false)
+            .movable(true)
+            .max_width(100.0)
+            .show(ctx, |ui| {
+                ui.vertical_centered_justified(|ui| {
+                    self.plot_graph(ui);
+                    ui.label(self.title.clone());
+                    if ui.button("Ок").clicked() {
+                        self.disable();
+                    }
+                });
+            });
+    }
+}
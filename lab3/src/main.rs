@@ -1,19 +1,26 @@
 mod logic;
 
 use logic::algo::*;
-use logic::utils::*;
+use logic::curves::*;
 use logic::windows::*;
 
 use eframe::egui::color_picker::color_edit_button_srgba;
 use eframe::egui::widgets::color_picker::Alpha::Opaque;
 use eframe::egui::{pos2, Color32, Pos2, Stroke};
 use eframe::{egui, emath};
+use raster::bezier::{Bezier, Flattening};
+use raster::integer::Overflow;
+use raster::jaggies;
+use raster::line::Rasterizer;
+use raster::pattern::Patterned;
+use raster::stroke::{Cap, Join};
+use raster::{abate_color, line, point, timing, Point};
 
 #[derive(Debug, Clone)]
 enum Line {
     Path([Pos2; 2], Color32),
-    Line(Vec<Pos2>, Color32),
-    LinePix(Vec<(Pos2, f32)>, Color32),
+    Line(Vec<Point>, Color32),
+    LinePix(Vec<(Point, f32)>, Color32),
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -21,6 +28,8 @@ enum DrawType {
     #[default]
     Segment,
     Spectre,
+    Polyline,
+    Bezier,
 }
 
 #[derive(Debug)]
@@ -28,6 +37,7 @@ struct MyApp {
     error: ErrorWindow,
     histo: HistoWindow,
     graph: GraphWindow,
+    time: TimeWindow,
     draw_type: DrawType,
     lines: Vec<Line>,
     algo: Algo,
@@ -41,7 +51,18 @@ struct MyApp {
     ppp: i32,
     buf_len: String,
     buf_len2: String,
+    buf_export: String,
+    export_status: String,
     painter_pos: Pos2,
+    width: u32,
+    cap: Cap,
+    join: Join,
+    polyline: Vec<Point>,
+    style: Style,
+    bezier: Bezier,
+    flattening: Flattening,
+    tolerance: f32,
+    dragged: Option<usize>,
 }
 
 impl Default for MyApp {
@@ -50,6 +71,7 @@ impl Default for MyApp {
             error: Default::default(),
             histo: Default::default(),
             graph: Default::default(),
+            time: Default::default(),
             draw_type: Default::default(),
             lines: vec![],
             algo: Default::default(),
@@ -64,6 +86,17 @@ impl Default for MyApp {
             ppp: 1,
             painter_pos: Pos2::ZERO,
             buf_len2: "1000".to_string(),
+            buf_export: "jaggies.md".to_string(),
+            export_status: String::new(),
+            width: 1,
+            cap: Default::default(),
+            join: Default::default(),
+            polyline: vec![],
+            style: Style::default(),
+            bezier: default_curve(true),
+            flattening: Default::default(),
+            tolerance: 0.5,
+            dragged: None,
         }
     }
 }
@@ -79,10 +112,14 @@ impl eframe::App for MyApp {
         if self.graph().enabled() {
             self.graph().update(ctx);
         }
+        if self.time.enabled() {
+            self.time.update(ctx);
+        }
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.set_enabled(!self.error().enabled());
             ui.set_enabled(!self.histo().enabled());
             ui.set_enabled(!self.graph().enabled());
+            ui.set_enabled(!self.time.enabled());
             ui.horizontal(|ui| {
                 ui.vertical(|ui| {
                     ui.set_width(950.0);
@@ -137,9 +174,83 @@ impl MyApp {
                 .iter()
                 .map(|line| self.convert_line_to_shape(line, &to_screen, unit));
             painter.extend(shapes);
+            if self.draw_type == DrawType::Bezier {
+                self.edit_bezier(&response, &to_screen, unit);
+                // о переполнении сообщает рисование кривой, не каждый кадр предпросмотра
+                let preview = self
+                    .polyline_lines(&self.bezier_polyline())
+                    .unwrap_or_default();
+                let shapes = preview
+                    .iter()
+                    .map(|line| self.convert_line_to_shape(line, &to_screen, unit));
+                painter.extend(shapes);
+                painter.extend(self.control_polygon(&to_screen, unit));
+            }
         });
     }
 
+    /// Центр пикселя опорной точки на экране.
+    fn handle_to_screen(&self, p: Point, to_screen: &emath::RectTransform, unit: f32) -> Pos2 {
+        let unit = unit * self.ppp as f32;
+        to_screen.transform_pos(pos2((p.x.round() + 0.5) * unit, (p.y.round() + 0.5) * unit))
+    }
+
+    /// Перетаскивание опорных точек: захватывается ближайшая к курсору в
+    /// пределах `HANDLE_RADIUS`.
+    fn edit_bezier(
+        &mut self,
+        response: &egui::Response,
+        to_screen: &emath::RectTransform,
+        unit: f32,
+    ) {
+        const HANDLE_RADIUS: f32 = 8.0;
+        let Some(pos) = response.interact_pointer_pos() else {
+            return;
+        };
+        if response.drag_started() {
+            self.dragged = self
+                .bezier
+                .points()
+                .iter()
+                .map(|&p| self.handle_to_screen(p, to_screen, unit).distance(pos))
+                .enumerate()
+                .filter(|&(_, d)| d <= HANDLE_RADIUS)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(i, _)| i);
+        }
+        if let Some(i) = self.dragged {
+            let unit = unit * self.ppp as f32;
+            let p = to_screen.inverse().transform_pos(pos);
+            self.bezier.points_mut()[i] = point(p.x / unit - 0.5, p.y / unit - 0.5).round();
+        }
+        if response.drag_stopped() {
+            self.dragged = None;
+        }
+    }
+
+    /// Ломаная через опорные точки и сами точки.
+    fn control_polygon(&self, to_screen: &emath::RectTransform, unit: f32) -> Vec<egui::Shape> {
+        let handles = self
+            .bezier
+            .points()
+            .iter()
+            .map(|&p| self.handle_to_screen(p, to_screen, unit))
+            .collect::<Vec<_>>();
+        let mut shapes = vec![egui::Shape::line(
+            handles.clone(),
+            Stroke::new(1.0, Color32::GRAY),
+        )];
+        shapes.extend(handles.into_iter().enumerate().map(|(i, p)| {
+            let color = if self.dragged == Some(i) {
+                Color32::RED
+            } else {
+                Color32::DARK_GRAY
+            };
+            egui::Shape::circle_stroke(p, 5.0, Stroke::new(2.0, color))
+        }));
+        shapes
+    }
+
     fn convert_line_to_shape(
         &self,
         line: &Line,
@@ -152,7 +263,7 @@ impl MyApp {
                     to_screen.transform_pos(v[0] * unit * self.ppp as f32),
                     to_screen.transform_pos(v[1] * unit * self.ppp as f32),
                 ],
-                Stroke::new(unit * self.ppp as f32, c.clone()),
+                Stroke::new(unit * self.ppp as f32, *c),
             ),
 
             Line::Line(v, c) => egui::Shape::Vec(
@@ -167,7 +278,7 @@ impl MyApp {
                             ]
                             .into(),
                             0.0,
-                            c.clone(),
+                            *c,
                         )
                     })
                     .collect(),
@@ -185,7 +296,7 @@ impl MyApp {
                             ]
                             .into(),
                             0.0,
-                            abate_color(c.to_owned(), self.background, a.to_owned()),
+                            Color32::from(abate_color((*c).into(), self.background.into(), *a)),
                         )
                     })
                     .collect(),
@@ -215,13 +326,51 @@ impl MyApp {
                     Algo::BresenhamReal,
                     "Алгоритм Брезенхема целочисленный",
                 );
+                ui.radio_value(
+                    &mut self.algo,
+                    Algo::BresenhamInteger,
+                    "Алгоритм Брезенхема только на целых числах",
+                );
                 ui.radio_value(
                     &mut self.algo,
                     Algo::BresenhamJaggiesLess,
                     "Алгоритм Брезенхема с устранением ступенчатости",
                 );
                 ui.radio_value(&mut self.algo, Algo::WU, "Алгоритм Ву");
+                ui.radio_value(
+                    &mut self.algo,
+                    Algo::XiaolinWu,
+                    "Алгоритм Ву с дробными концами",
+                );
+                ui.radio_value(&mut self.algo, Algo::GuptaSproull, "Алгоритм Гупты — Спрулла");
                 ui.radio_value(&mut self.algo, Algo::BuiltIn, "Встроенная функция");
+                ui.label("Тип линии");
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.style, Style::Solid, "Сплошная");
+                    ui.radio_value(&mut self.style, Style::Dashed, "Штриховая");
+                });
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.style, Style::Dotted, "Пунктирная");
+                    ui.radio_value(&mut self.style, Style::DashDot, "Штрихпунктирная");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Толщина");
+                    ui.add(egui::Slider::new(&mut self.width, 1..=50));
+                });
+                if self.width > 1 {
+                    ui.label("Концы");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut self.cap, Cap::Butt, "Плоские");
+                        ui.radio_value(&mut self.cap, Cap::Round, "Круглые");
+                        ui.radio_value(&mut self.cap, Cap::Square, "Квадратные");
+                    });
+                    ui.label("Стыки");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut self.join, Join::Miter, "Острые");
+                        ui.radio_value(&mut self.join, Join::Round, "Круглые");
+                        ui.radio_value(&mut self.join, Join::Bevel, "Срезанные");
+                    });
+                }
 
                 ui.separator();
                 ui.horizontal(|ui| {
@@ -231,15 +380,20 @@ impl MyApp {
                 ui.horizontal(|ui| {
                     ui.radio_value(&mut self.draw_type, DrawType::Segment, "Отрезок");
                     ui.radio_value(&mut self.draw_type, DrawType::Spectre, "Спектр");
+                    ui.radio_value(&mut self.draw_type, DrawType::Polyline, "Ломаная");
+                    ui.radio_value(&mut self.draw_type, DrawType::Bezier, "Кривая Безье");
                 });
-                ui.vertical_centered_justified(|ui| {
-                    ui.label("Первая точка");
+                if self.draw_type != DrawType::Bezier {
                     ui.vertical_centered_justified(|ui| {
-                        ui.add(egui::TextEdit::singleline(&mut self.buf_x1).hint_text("X1"));
-                        ui.add(egui::TextEdit::singleline(&mut self.buf_y1).hint_text("Y1"));
+                        ui.label("Первая точка");
+                        ui.vertical_centered_justified(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut self.buf_x1).hint_text("X1"));
+                            ui.add(egui::TextEdit::singleline(&mut self.buf_y1).hint_text("Y1"));
+                        });
                     });
-                });
+                }
                 match self.draw_type {
+                    DrawType::Bezier => self.bezier_control(ui),
                     DrawType::Spectre => {
                         ui.vertical_centered_justified(|ui| {
                             ui.add(
@@ -253,6 +407,17 @@ impl MyApp {
                             };
                         });
                     }
+                    DrawType::Polyline => {
+                        ui.vertical_centered_justified(|ui| {
+                            ui.label(format!("Вершин: {}", self.polyline.len()));
+                            if ui.button("Добавить вершину").clicked() {
+                                self.parse_to_add_vertex();
+                            };
+                            if ui.button("Построить ломаную").clicked() {
+                                self.draw_polyline();
+                            };
+                        });
+                    }
                     DrawType::Segment => {
                         ui.vertical_centered_justified(|ui| {
                             ui.label("Вторая точка");
@@ -282,6 +447,16 @@ impl MyApp {
                     if ui.button("Сравнение ступенчатости").clicked() {
                         self.parse_jaggies();
                     };
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.buf_export)
+                            .hint_text("Файл таблицы (.csv или .md)"),
+                    );
+                    if ui.button("Экспорт ступенчатости").clicked() {
+                        self.parse_to_export_jaggies();
+                    };
+                    if !self.export_status.is_empty() {
+                        ui.label(&self.export_status);
+                    }
                     if ui.button("Очистка экрана").clicked() {
                         self.clear();
                     };
@@ -291,6 +466,41 @@ impl MyApp {
         });
     }
 
+    fn bezier_control(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let cubic = matches!(self.bezier, Bezier::Cubic(_));
+            if ui.radio(!cubic, "Квадратичная").clicked() && cubic {
+                self.bezier = default_curve(false);
+            }
+            if ui.radio(cubic, "Кубическая").clicked() && !cubic {
+                self.bezier = default_curve(true);
+            }
+        });
+        for flattening in Flattening::ALL {
+            ui.radio_value(
+                &mut self.flattening,
+                flattening,
+                flattening_title(flattening),
+            );
+        }
+        ui.horizontal(|ui| {
+            ui.label("Допуск");
+            ui.add(egui::Slider::new(&mut self.tolerance, 0.1..=5.0).suffix(" пикс."));
+        });
+        ui.label("Опорные точки перетаскиваются мышью");
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Построить кривую").clicked() {
+                self.draw_bezier();
+            };
+            if ui.button("Сбросить опорные точки").clicked() {
+                self.bezier = default_curve(matches!(self.bezier, Bezier::Cubic(_)));
+            };
+            if ui.button("Сравнение времени кривых").clicked() {
+                self.measure_curves();
+            };
+        });
+    }
+
     fn parse_field_x1(&mut self) -> Result<f32, ()> {
         self.buf_x1.parse::<f32>().map_err(|_| {
             self.error().set_error(
@@ -360,7 +570,7 @@ impl MyApp {
         let x2 = self.parse_field_x2();
         let y2 = self.parse_field_y2();
         if let (Ok(x1), Ok(y1), Ok(x2), Ok(y2)) = (x1, y1, x2, y2) {
-            self.draw_line([pos2(x1 as f32, y1 as f32), pos2(x2 as f32, y2 as f32)]);
+            self.draw_line([point(x1, y1), point(x2, y2)]);
         } else {
             self.error.enable();
         }
@@ -378,116 +588,270 @@ impl MyApp {
                     "Некорректное значение в поле угла: 0".to_string(),
                 )
             } else {
-                self.draw_spectre(
-                    [
-                        pos2(x1 as f32, y1 as f32),
-                        pos2(x1 as f32 + len as f32, y1 as f32),
-                    ],
-                    angle,
-                );
+                self.draw_spectre(point(x1, y1), len, angle);
             }
         } else {
             self.error.enable();
         }
     }
 
+    /// Результаты `cargo bench -p raster` для длины из сводки, ближайшей к введённой.
     fn parse_to_measure(&mut self) {
         let len = self.parse_field_len2();
-        if let Ok(len) = len {
-            self.histo.enable();
-            let dda = measure_time(dda, len);
-            let bf = measure_time(bresenham_float, len);
-            let bi = measure_time(bresenham_int, len);
-            let bj = measure_time(bresenham_jaggiesless, len);
-            let wu = measure_time(wu, len);
-            let eg = measure_time(
-                |points| {
-                    egui::Painter::line_segment(
-                        &mut egui::Painter::new(
-                            egui::Context::default(),
-                            egui::LayerId::debug(),
-                            egui::Rect::NAN,
-                        ),
-                        points.clone(),
-                        Stroke::new(1.0, egui::Color32::RED),
-                    )
-                },
-                len,
-            );
-            let mut dm = vec![dda, bf, bi, bj, wu, eg];
-            dm.sort();
-            let values = vec![
-                ("Брезенхем целочисленный".to_owned(), dm[1]),
-                ("Брезенхем вещественный".to_owned(), dm[2]),
-                ("Брезенхем с устранением ступенчатости".to_owned(), dm[3]),
-                ("ЦДА".to_owned(), dm[4]),
-                ("ВУ".to_owned(), dm[5]),
-                ("Встроенная функция".to_owned(), dm[0]),
-            ];
-            self.histo.set_values(&values);
-        } else {
+        let Ok(len) = len else {
             self.error.enable();
+            return;
+        };
+        let Some(samples) = self.load_summary() else {
+            return;
+        };
+        let lines = samples.iter().filter(|s| s.group == "line");
+        let Some(size) = lines
+            .clone()
+            .map(|s| s.size)
+            .min_by(|a, b| (*a as f32 - len).abs().total_cmp(&(*b as f32 - len).abs()))
+        else {
+            self.error()
+                .set_error("Ошибка".to_string(), "В замерах нет отрезков".to_string());
+            return;
+        };
+        let values = lines
+            .filter(|s| s.size == size)
+            .filter_map(|s| {
+                let algo = s.algorithm.parse().ok()?;
+                Some((title(algo).to_owned(), s.mean_ns.round() as u128))
+            })
+            .collect::<Vec<_>>();
+        self.histo.set_values(&values, size);
+    }
+
+    /// Ступенчатость всех алгоритмов на углах от 0 до 90°.
+    fn jaggies(len: f32) -> Result<Vec<jaggies::Record>, Overflow> {
+        let mut records = vec![];
+        for algo in line::Algo::ALL {
+            let rows = jaggies::sweep(&algo, len, 0..=90)?;
+            records.extend(rows.into_iter().map(|(angle, steps)| jaggies::Record {
+                algorithm: algo.name().to_owned(),
+                angle,
+                steps,
+            }));
         }
+        Ok(records)
     }
 
     fn parse_jaggies(&mut self) {
         let len = self.parse_field_len2();
         if let Ok(len) = len {
-            self.graph().enable();
-            let dda = measure_jaggies(dda, len);
-            let bf = measure_jaggies(bresenham_float, len);
-            let bi = measure_jaggies(bresenham_int, len);
-            let bj = measure_jaggies_bj(bresenham_jaggiesless, len);
-            let wu = measure_jaggies_wu(wu, len);
-            let values = vec![
-                ("ЦДА".to_owned(), dda),
-                ("Брезенхем целочисленный".to_owned(), bf),
-                ("Брезенхем вещественный".to_owned(), bi),
-                ("Брезенхем с устранением ступенчатости".to_owned(), bj),
-                ("ВУ".to_owned(), wu),
-            ];
+            let Ok(records) = Self::jaggies(len) else {
+                self.overflow_error();
+                return;
+            };
+            let values = line::Algo::ALL
+                .into_iter()
+                .map(|algo| {
+                    let steps = records
+                        .iter()
+                        .filter(|r| r.algorithm == algo.name())
+                        .map(|r| (r.angle as i32, r.steps.steps as i32))
+                        .collect();
+                    (title(algo).to_owned(), steps)
+                })
+                .collect::<Vec<_>>();
             self.graph().set_values(&values, len);
         } else {
             self.error.enable();
         }
     }
 
-    fn draw_line(&mut self, points: [Pos2; 2]) {
-        match self.algo {
-            Algo::DDA => {
-                let points = dda(&points);
-                self.lines.push(Line::Line(points, self.buf_linecolor));
-            }
-            Algo::BuiltIn => {
-                self.lines.push(Line::Path(points, self.buf_linecolor));
-            }
-            Algo::BresenhamFloat => {
-                let points = bresenham_float(&points);
-                self.lines.push(Line::Line(points, self.buf_linecolor));
+    /// Таблица ступенчатости в файл из поля ввода; формат — по расширению,
+    /// CSV или Markdown.
+    fn parse_to_export_jaggies(&mut self) {
+        self.export_status.clear();
+        let Ok(len) = self.parse_field_len2() else {
+            self.error.enable();
+            return;
+        };
+        let path = std::path::PathBuf::from(self.buf_export.trim());
+        let Ok(records) = Self::jaggies(len) else {
+            self.overflow_error();
+            return;
+        };
+        let res = match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => jaggies::save_csv(&path, &records),
+            Some("md") => jaggies::save_markdown(&path, &records),
+            _ => {
+                self.error().set_error(
+                    "Ошибка".to_string(),
+                    format!("{}: ожидается файл .csv или .md", path.display()),
+                );
+                self.error.enable();
+                return;
             }
-            Algo::BresenhamReal => {
-                let points = bresenham_int(&points);
-                self.lines.push(Line::Line(points, self.buf_linecolor));
+        };
+        match res {
+            Ok(()) => self.export_status = format!("Таблица сохранена в {}", path.display()),
+            Err(e) => {
+                self.error().set_error(
+                    "Ошибка".to_string(),
+                    format!("Не удалось сохранить таблицу в {}: {e}", path.display()),
+                );
+                self.error.enable();
             }
-            Algo::BresenhamJaggiesLess => {
-                let points = bresenham_jaggiesless(&points);
-                self.lines.push(Line::LinePix(points, self.buf_linecolor));
+        }
+    }
+
+    fn stroke(&self) -> raster::stroke::Stroke {
+        let mut stroke = raster::stroke::Stroke::new(self.width as f32);
+        stroke.set_cap(self.cap);
+        stroke.set_join(self.join);
+        stroke
+    }
+
+    fn parse_to_add_vertex(&mut self) {
+        let x = self.parse_field_x1();
+        let y = self.parse_field_y1();
+        if let (Ok(x), Ok(y)) = (x, y) {
+            self.polyline.push(point(x, y));
+        } else {
+            self.error.enable();
+        }
+    }
+
+    fn draw_polyline(&mut self) {
+        let vertices = std::mem::take(&mut self.polyline);
+        self.add_lines(self.polyline_lines(&vertices));
+    }
+
+    fn draw_bezier(&mut self) {
+        let vertices = self.bezier_polyline();
+        self.add_lines(self.polyline_lines(&vertices));
+    }
+
+    /// Брезенхем только на целых не рисует отрезки, концы которых не
+    /// помещаются в `i32`; вместо пустого холста показывается ошибка.
+    fn add_lines(&mut self, lines: Result<Vec<Line>, Overflow>) {
+        match lines {
+            Ok(lines) => self.lines.extend(lines),
+            Err(Overflow) => self.overflow_error(),
+        }
+    }
+
+    fn overflow_error(&mut self) {
+        self.error().set_error(
+            "Ошибка".to_string(),
+            "Отрезок слишком велик для целочисленного алгоритма".to_string(),
+        );
+    }
+
+    /// Сплошная толстая ломаная строится целиком, чтобы были стыки; иначе —
+    /// по отрезкам.
+    fn polyline_lines(&self, vertices: &[Point]) -> Result<Vec<Line>, Overflow> {
+        self.check_integer(vertices)?;
+        if self.width > 1 && self.style == Style::Solid {
+            let pixels = self.stroke().polyline(vertices);
+            Ok(vec![Line::Line(pixels, self.buf_linecolor)])
+        } else {
+            self.segment_lines(vertices)
+        }
+    }
+
+    fn draw_line(&mut self, points: [Point; 2]) {
+        self.add_lines(self.polyline_lines(&points));
+    }
+
+    /// Толстую линию строит не Брезенхем, но с целочисленным алгоритмом её
+    /// отрезки тоже должны помещаться в `i32`, как у тонкой.
+    fn check_integer(&self, vertices: &[Point]) -> Result<(), Overflow> {
+        if self.algo.raster() != Some(line::Algo::BresenhamInteger) {
+            return Ok(());
+        }
+        vertices
+            .windows(2)
+            .try_for_each(|w| line::bresenham_integer(&[w[0], w[1]]).map(drop))
+    }
+
+    /// Отрезки между соседними вершинами; узор не прерывается на вершинах.
+    fn segment_lines(&self, vertices: &[Point]) -> Result<Vec<Line>, Overflow> {
+        let rasterizer: Box<dyn Rasterizer> = match self.algo.raster() {
+            _ if self.width > 1 => Box::new(self.stroke()),
+            Some(algo) => Box::new(algo),
+            None => {
+                return Ok(vertices
+                    .windows(2)
+                    .map(|w| Line::Path([Pos2::from(w[0]), Pos2::from(w[1])], self.buf_linecolor))
+                    .collect());
             }
-            Algo::WU => {
-                let points = wu(&points);
-                self.lines.push(Line::LinePix(points, self.buf_linecolor));
+        };
+        let mut patterned = Patterned::new(self.style.pattern());
+        let lines = patterned
+            .polyline(rasterizer.as_ref(), vertices)?
+            .into_iter()
+            .map(|pixels| match pixels {
+                LinePixels::Plain(points) => Line::Line(points, self.buf_linecolor),
+                LinePixels::Shaded(points) => Line::LinePix(points, self.buf_linecolor),
+            })
+            .collect();
+        Ok(lines)
+    }
+
+    /// Кривая Безье, заменённая ломаной выбранным способом; её звенья рисует
+    /// выбранный алгоритм отрезков.
+    fn bezier_polyline(&self) -> Vec<Point> {
+        self.bezier.polyline(self.flattening, self.tolerance)
+    }
+
+    /// Сводка `cargo bench -p raster`; если её нет, показывается ошибка.
+    fn load_summary(&mut self) -> Option<Vec<timing::Sample>> {
+        let path = timing::summary_csv(&timing::criterion_dir());
+        match timing::load_csv(&path) {
+            Ok(samples) => Some(samples),
+            Err(e) => {
+                self.error().set_error(
+                    "Ошибка".to_string(),
+                    format!(
+                        "Нет результатов замеров {}: {e}\nЗапустите cargo bench -p raster",
+                        path.display()
+                    ),
+                );
+                None
             }
+        }
+    }
+
+    /// Время кривой той же степени, что и в редакторе, из сводки бенчмарка:
+    /// S-образная кривая в квадрате заданного размера, звенья рисует ЦДА.
+    fn measure_curves(&mut self) {
+        let cubic = matches!(self.bezier, Bezier::Cubic(_));
+        let (group, kind) = if cubic {
+            ("bezier-cubic", "Кубическая")
+        } else {
+            ("bezier-quadratic", "Квадратичная")
+        };
+        let Some(samples) = self.load_summary() else {
+            return;
         };
+        let data = timing::series(&samples, group)
+            .into_iter()
+            .filter_map(|(name, points)| {
+                let flattening = Flattening::ALL.into_iter().find(|f| f.name() == name)?;
+                let points = points
+                    .into_iter()
+                    .map(|(size, ns)| (size as i32, ns / 1000.0))
+                    .collect();
+                Some((flattening_title(flattening).to_owned(), points))
+            })
+            .collect::<Vec<_>>();
+        if data.is_empty() {
+            self.error()
+                .set_error("Ошибка".to_string(), "В замерах нет кривых Безье".to_string());
+            return;
+        }
+        self.time.set_values(&data, format!("{kind} кривая, ЦДА, допуск 0.5"));
     }
 
-    fn draw_spectre(&mut self, mut points: [Pos2; 2], angle: f32) {
-        let mut i = 0.0;
-        self.draw_line(points);
-        while i + angle <= 360.0 {
-            let center = points[0];
-            rotate(&mut points[1], angle, &center);
+    fn draw_spectre(&mut self, center: Point, len: f32, angle: f32) {
+        for points in raster::line::spectrum(center, len, angle) {
             self.draw_line(points);
-            i += angle;
         }
     }
 
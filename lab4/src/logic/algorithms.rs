@@ -1,10 +1,11 @@
-#![allow(arithmetic_overflow)]
-
-
-use std::fs::OpenOptions;
 use eframe::egui;
 use eframe::egui::{Pos2, Vec2};
-use std::io::{Write};
+use raster::arc::ArcRange;
+use raster::circle::Circle;
+use raster::conic::Conic;
+use raster::ellipse::{rotated_ellipse_path, Ellipse, RotatedEllipse};
+use raster::sink::Counter;
+use raster::superellipse::Superellipse;
 
 #[derive(Default, Debug, PartialEq, Clone, Copy)]
 pub enum DrawType {
@@ -13,382 +14,249 @@ pub enum DrawType {
     PARAMETRIC,
     MIDPOINT,
     BRESENHAM,
+    /// Брезенхем только на целых числах; только для окружности и эллипса.
+    BresenhamInteger,
+    /// Сглаживание по Ву; только для окружности и эллипса.
+    WU,
+    /// Перебор клеток; только для суперэллипса и коники.
+    MARCHING,
     BuiltIn,
 }
 
-pub trait Measurable {
-    fn measure_time(&mut self, draw_type: DrawType, rad: f32, file: &std::fs::File);
-}
-
-pub struct Circle {
-    center: Pos2,
-    radius: f32,
-    profile: bool
-}
-
-impl Circle {
-    pub fn new(center: Pos2, radius: f32) -> Self {
-        Circle { center, radius, profile: false }
-    }
-
-    fn plot_circle_pixels(&self, x: f32, y: f32, x_c: f32, y_c: f32, pixels: &mut Vec<Pos2>) {
-        let (mut sx, mut sy) = (1.0, 1.0);
-        for _ in 0..4 {
-            let xsx = x * sx;
-            let ysy = y * sy;
-            pixels.push(Pos2::new(x_c + xsx, y_c + ysy));
-            pixels.push(Pos2::new(x_c + ysy, y_c + xsx));
-            sx *= -1.0;
-            sy *= -1.0 * sx;
-        }
-    }
-
-    pub fn draw_canonic(&self) -> Vec<Pos2> {
-        let mut pixels = vec![];
-        let (x_c, y_c) = (self.center.x, self.center.y);
-        let sqr_radius = (self.radius * self.radius) as u32;
-        let x_range = self.radius / std::f32::consts::SQRT_2 + 1.0;
-        let mut x = 0;
-        while x as f32 <= x_range {
-            let y: f32 = ((sqr_radius - x * x) as f32).sqrt();
-            if !self.profile {
-                self.plot_circle_pixels(x as f32, y.round(), x_c, y_c, &mut pixels);
-            }
-            x += 1;
-        }
-        pixels
-    }
-
-    pub fn draw_parametric(&self) -> Vec<Pos2> {
-        let mut pixels = vec![];
-        let (x_c, y_c) = (self.center.x, self.center.y);
-        let t_range = std::f32::consts::FRAC_PI_4;
-        let t_step = self.radius.recip();
-        let mut t = 0.0;
-        while t <= t_range {
-            let x = self.radius * t.cos();
-            let y = self.radius * t.sin();
-            if !self.profile {
-                self.plot_circle_pixels(x.round(), y.round(), x_c, y_c, &mut pixels);
-            }
-            t += t_step;
-        }
-        pixels
-    }
-
-    pub fn draw_bresenham(&self) -> Vec<Pos2> {
-        let mut pixels = vec![];
-        let (x_c, y_c) = (self.center.x, self.center.y);
-        let mut x = 0i32;
-        let mut y = self.radius as i32;
-        let mut cap_delta: i32 = 2 * (1 - self.radius as i32);
-        while y >= x {
-            if !self.profile {
-                self.plot_circle_pixels(x as f32, y as f32, x_c, y_c, &mut pixels);
-            }
-            if cap_delta < 0 {
-                let delta = 2 * (cap_delta + y) - 1;
-                if delta <= 0 {
-                    x += 1;
-                    cap_delta += 2 * x + 1;
-                } else {
-                    x += 1;
-                    y -= 1;
-                    cap_delta += 2 * (x - y + 1);
-                }
-            } else if cap_delta > 0 {
-                let delta = 2 * (cap_delta - x) - 1;
-                if delta <= 0 {
-                    x += 1;
-                    y -= 1;
-                    cap_delta += 2 * (x - y + 1);
-                } else {
-                    y -= 1;
-                    cap_delta -= 2 * y + 1;
-                }
-            } else {
-                x += 1;
-                y -= 1;
-                cap_delta += 2 * (x - y + 1);
-            }
+impl DrawType {
+    pub const ALL: [DrawType; 8] = [
+        DrawType::CANONICAL,
+        DrawType::PARAMETRIC,
+        DrawType::BRESENHAM,
+        DrawType::BresenhamInteger,
+        DrawType::MIDPOINT,
+        DrawType::WU,
+        DrawType::MARCHING,
+        DrawType::BuiltIn,
+    ];
+
+    /// Имя алгоритма в хранилище замеров, как в бенчмарке.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DrawType::CANONICAL => "canonic",
+            DrawType::PARAMETRIC => "parametric",
+            DrawType::MIDPOINT => "midpoint",
+            DrawType::BRESENHAM => "bresenham",
+            DrawType::BresenhamInteger => "bresenham-integer",
+            DrawType::WU => "wu",
+            DrawType::MARCHING => "marching",
+            DrawType::BuiltIn => "builtin",
         }
-
-        pixels
     }
+}
 
-    pub fn draw_midpoint(&self) -> Vec<Pos2> {
-        let mut pixels = vec![];
-        let (x_c, y_c) = (self.center.x, self.center.y);
-        let mut x = 0;
-        let mut y = self.radius as i32;
-
-        let mut trial: i32 = 4 * (5 - self.radius as i32);
-        while x <= y {
-            if !self.profile {
-                self.plot_circle_pixels(x as f32, y as f32, x_c, y_c, &mut pixels);
-            }
-            x += 1;
-            if trial > 0 {
-                y -= 1;
-                trial -= 8 * y;
-            }
-            trial += 8 * x + 4;
-        }
-        pixels
-    }
+pub trait Measurable {
+    /// Имя фигуры в хранилище замеров.
+    const SHAPE: &'static str;
 
-    pub fn draw_builtin(&self) -> Vec<Pos2> {
-        let _x = egui::Painter::circle_stroke(
-            &mut egui::Painter::new(
-                egui::Context::default(),
-                egui::LayerId::debug(),
-                egui::Rect::NAN,
-            ),
-            self.center.clone(),
-            self.radius,
-            egui::Stroke::new(1.0, egui::Color32::RED),
-        );
-        vec![]
-    }
+    /// Среднее время одного построения с радиусом `rad` в микросекундах;
+    /// `None`, если у фигуры нет такого алгоритма или он переполняется.
+    fn measure_time(&mut self, draw_type: DrawType, rad: f32) -> Option<f64>;
 }
 
-impl Measurable for Circle {
-    fn measure_time(&mut self, draw_type: DrawType, rad: f32, _file: &std::fs::File) {
-        let mut file = OpenOptions::new()
-            .write(true)
-            .open("/dev/null").unwrap();
-        self.radius = rad;
-        self.profile = true;
-        let f = match draw_type {
-            DrawType::CANONICAL => Circle::draw_canonic,
-            DrawType::BRESENHAM => Circle::draw_bresenham,
-            DrawType::PARAMETRIC => Circle::draw_parametric,
-            DrawType::MIDPOINT => Circle::draw_midpoint,
-            DrawType::BuiltIn => Circle::draw_builtin,
-        };
-        let start = std::time::Instant::now();
-        let runs = 5000;
-        for _ in 0..runs {
-            f(self);
-        }
-        let n = start.elapsed().as_micros() / runs;
-        file.write_all(format!("{} {}\n", rad, n).as_bytes()).unwrap();
+/// Пиксели только считаются, так что в замер не попадает выделение памяти.
+fn average_us<T>(obj: &T, f: fn(&T, &mut Counter), runs: u32) -> f64 {
+    let mut counter = Counter::default();
+    let start = std::time::Instant::now();
+    for _ in 0..runs {
+        f(obj, &mut counter);
     }
+    let elapsed = start.elapsed();
+    std::hint::black_box(counter);
+    elapsed.as_secs_f64() * 1e6 / runs as f64
 }
 
-pub struct Ellipse {
-    center: Pos2,
-    radius: Vec2,
-    profile: bool
+pub fn ellipse_path(center: Pos2, radius: Vec2) -> Vec<Pos2> {
+    let n = (radius.x.max(radius.y) as usize).clamp(16, 1024);
+    (0..n)
+        .map(|i| {
+            let t = std::f32::consts::TAU * i as f32 / n as f32;
+            Pos2::new(center.x + radius.x * t.cos(), center.y + radius.y * t.sin())
+        })
+        .collect()
 }
 
-impl Ellipse {
-    pub fn new(center: Pos2, radius: Vec2) -> Self {
-        Ellipse { center, radius, profile: false }
-    }
-
-    fn plot_ellipse_pixels(&self, x: f32, y: f32, x_c: f32, y_c: f32, pixels: &mut Vec<Pos2>) {
-        let (mut sx, mut sy) = (1.0, 1.0);
-        for _ in 0..4 {
-            let xsx = x * sx;
-            let ysy = y * sy;
-            pixels.push(Pos2::new(x_c + xsx, y_c + ysy));
-            sx *= -1.0;
-            sy *= -1.0 * sx;
-        }
-    }
-
-    pub fn draw_canonic(&self) -> Vec<Pos2> {
-        let mut pixels = vec![];
-        let (x_c, y_c) = (self.center.x, self.center.y);
-        let sqr_a = self.radius.x * self.radius.x;
-        let sqr_b = self.radius.y * self.radius.y;
-        let sqrt_coeff = self.radius.y / self.radius.x;
-        let hyp = (sqr_a + sqr_b).sqrt();
-        let x_range = sqr_a / hyp + 1.0;
-        let mut x = 0.0;
-        while x <= x_range {
-            let y = sqrt_coeff * (sqr_a - x * x).sqrt();
-            if !self.profile {
-                self.plot_ellipse_pixels(x, y.round(), x_c, y_c, &mut pixels);
-            }
-            x += 1.0;
-        }
-
-        let sqrt_coeff = sqrt_coeff.recip();
-        let y_range = sqr_b / hyp + 1.0;
-        let mut y = 0.0;
-        while y <= y_range {
-            let x = sqrt_coeff * (sqr_b - y * y).sqrt();
-            self.plot_ellipse_pixels(x.round(), y, x_c, y_c, &mut pixels);
-            y += 1.0;
-        }
-
-        pixels
-    }
-
-    pub fn draw_parametric(&self) -> Vec<Pos2> {
-        let mut pixels = vec![];
-
-        let (x_c, y_c) = (self.center.x, self.center.y);
-
-        let t_range = std::f32::consts::FRAC_PI_2;
-        let t_step = (if self.radius.x > self.radius.y {
-            self.radius.x
-        } else {
-            self.radius.y
+/// Контур суперэллипса с показателем `n`.
+pub fn superellipse_path(center: Pos2, radius: Vec2, n: f32) -> Vec<Pos2> {
+    let n_points = (radius.x.max(radius.y) as usize * 4).clamp(64, 4096);
+    let power = |v: f32| v.signum() * v.abs().powf(2.0 / n);
+    (0..n_points)
+        .map(|i| {
+            let t = std::f32::consts::TAU * i as f32 / n_points as f32;
+            let (sin, cos) = t.sin_cos();
+            center + Vec2::new(radius.x * power(cos), radius.y * power(sin))
         })
-        .recip();
-        let mut t = 0.0;
-        while t <= t_range {
-            let x = self.radius.x * t.cos();
-            let y = self.radius.y * t.sin();
-            if !self.profile {
-                self.plot_ellipse_pixels(x.round(), y.round(), x_c, y_c, &mut pixels);
-            }
-            t += t_step;
-        }
-
-        pixels
-    }
+        .collect()
+}
 
-    pub fn draw_bresenham(&self) -> Vec<Pos2> {
-        let mut pixels = vec![];
-        let (x_c, y_c) = (self.center.x, self.center.y);
-        let (ra, rb) = (self.radius.x as i128, self.radius.y as i128);
+/// Незамкнутый контур дуги эллипса; углы полярные, как в `ArcRange`.
+pub fn ellipse_arc_path(center: Pos2, radius: Vec2, arc: &ArcRange) -> Vec<Pos2> {
+    let n = ((arc.span().to_radians() * radius.x.max(radius.y)) as usize).clamp(2, 1024);
+    (0..=n)
+        .map(|i| {
+            let t = (arc.start() + arc.span() * i as f32 / n as f32).to_radians();
+            let (sin, cos) = t.sin_cos();
+            let r = ((cos / radius.x).powi(2) + (sin / radius.y).powi(2)).sqrt().recip();
+            center + Vec2::new(cos, sin) * r
+        })
+        .collect()
+}
 
-        let sqr_a = ra * ra;
-        let sqr_b = rb * rb;
+fn painter() -> egui::Painter {
+    egui::Painter::new(
+        egui::Context::default(),
+        egui::LayerId::debug(),
+        egui::Rect::NAN,
+    )
+}
 
-        let mut x = 0;
-        let mut y = rb;
-        let mut cap_delta: i128 = sqr_b - sqr_a * (2 * rb - 1); // b^2(x+1)+a^2(y-1)^2-a^2b^2 в (0, b)
+pub fn draw_builtin_circle(circle: &Circle, _: &mut Counter) {
+    painter().circle_stroke(
+        circle.center().into(),
+        circle.radius(),
+        egui::Stroke::new(1.0, egui::Color32::RED),
+    );
+}
 
-        while y >= 0 {
-            if !self.profile {
-                self.plot_ellipse_pixels(x as f32, y as f32, x_c, y_c, &mut pixels);
-            }
+pub fn draw_builtin_ellipse(ellipse: &Ellipse, _: &mut Counter) {
+    let radius = ellipse.radius();
+    painter().add(egui::Shape::closed_line(
+        ellipse_path(ellipse.center().into(), Vec2::new(radius.x, radius.y)),
+        egui::Stroke::new(1.0, egui::Color32::RED),
+    ));
+}
 
-            if cap_delta < 0 {
-                let delta = 2 * cap_delta + sqr_a * (2 * y - 1);
+pub fn draw_builtin_rotated(ellipse: &RotatedEllipse, _: &mut Counter) {
+    let radius = ellipse.radius();
+    painter().add(egui::Shape::closed_line(
+        rotated_ellipse_path(
+            ellipse.center().into(),
+            Vec2::new(radius.x, radius.y),
+            ellipse.angle(),
+        ),
+        egui::Stroke::new(1.0, egui::Color32::RED),
+    ));
+}
 
-                if delta <= 0 {
-                    x += 1;
-                    cap_delta += sqr_b * (2 * x + 1);
-                } else {
-                    x += 1;
-                    y -= 1;
-                    cap_delta += 2 * x * sqr_b - 2 * y * sqr_a + sqr_a + sqr_b;
-                }
-            } else if cap_delta > 0 {
-                let delta = 2 * cap_delta - sqr_b * (2 * x + 1);
+pub fn draw_builtin_superellipse(superellipse: &Superellipse, _: &mut Counter) {
+    let radius = superellipse.radius();
+    painter().add(egui::Shape::closed_line(
+        superellipse_path(
+            superellipse.center().into(),
+            Vec2::new(radius.x, radius.y),
+            superellipse.exponent(),
+        ),
+        egui::Stroke::new(1.0, egui::Color32::RED),
+    ));
+}
 
-                if delta <= 0 {
-                    x += 1;
-                    y -= 1;
-                    cap_delta += 2 * x * sqr_b - 2 * y * sqr_a + sqr_a + sqr_b;
-                } else {
-                    y -= 1;
-                    cap_delta += sqr_a * (-2 * y + 1);
+impl Measurable for Circle {
+    const SHAPE: &'static str = "circle";
+
+    fn measure_time(&mut self, draw_type: DrawType, rad: f32) -> Option<f64> {
+        self.set_radius(rad);
+        let f: fn(&Circle, &mut Counter) = match draw_type {
+            DrawType::CANONICAL => Circle::draw_canonic_to,
+            DrawType::BRESENHAM => Circle::draw_bresenham_to,
+            DrawType::BresenhamInteger => {
+                // при переполнении замера нет, как и рисунка в `draw_circle`
+                self.draw_bresenham_integer_to(&mut Counter::default())
+                    .ok()?;
+                |circle, counter| {
+                    circle
+                        .draw_bresenham_integer_to(counter)
+                        .expect("переполнение проверено до замера");
                 }
-            } else {
-                x += 1;
-                y -= 1;
-                cap_delta += 2 * x * sqr_b - 2 * y * sqr_a + sqr_a + sqr_b;
             }
-        }
-
-        pixels
+            DrawType::PARAMETRIC => Circle::draw_parametric_to,
+            DrawType::MIDPOINT => Circle::draw_midpoint_to,
+            DrawType::WU => Circle::draw_wu_to,
+            DrawType::MARCHING => return None,
+            DrawType::BuiltIn => draw_builtin_circle,
+        };
+        Some(average_us(self, f, 5000))
     }
+}
 
-    pub fn draw_midpoint(&self) -> Vec<Pos2> {
-        let mut pixels = vec![];
-        let (x_c, y_c) = (self.center.x, self.center.y);
-        let (ra, rb) = (self.radius.x as i128, self.radius.y as i128);
-
-        let sqr_a = ra * ra;
-        let sqr_b = rb * rb;
-        let sqr_a2 = 2 * sqr_a;
-        let sqr_b2 = 2 * sqr_b;
-
-        let mut x = 0;
-        let mut y = rb;
-        let mut trial: i128 = 2 * (sqr_b2 - sqr_a2 * (rb - 1));
-        let mut dx = 0;
-        let mut dy = sqr_a2 * y;
-
-        // первый интервал
-        while dx <= dy {
-            if !self.profile {
-                self.plot_ellipse_pixels(x as f32, y as f32, x_c, y_c, &mut pixels);
-            }
-            x += 1;
-            dx += sqr_b2;
-
-            if trial >= 0 {
-                y -= 1;
-                dy -= sqr_a2;
-                trial -= 4 * dy;
-            }
-
-            trial += 4 * (dx + sqr_b);
-        }
-
-        // второй интервал
-        trial -= 4 * (sqr_b * (x + 3) + sqr_a * (y - 3));
-        while dy >= 0 {
-            if !self.profile {
-                self.plot_ellipse_pixels(x as f32, y as f32, x_c, y_c, &mut pixels);
-            }
-            y -= 1;
-            dy -= sqr_a2;
-            if trial < 0 {
-                x += 1;
-                dx += sqr_b2;
-                trial += 4 * dx;
+impl Measurable for Ellipse {
+    const SHAPE: &'static str = "ellipse";
+
+    fn measure_time(&mut self, draw_type: DrawType, rad: f32) -> Option<f64> {
+        self.set_radius([rad, rad].into());
+        let f: fn(&Ellipse, &mut Counter) = match draw_type {
+            DrawType::CANONICAL => Ellipse::draw_canonic_to,
+            DrawType::BRESENHAM => Ellipse::draw_bresenham_to,
+            DrawType::BresenhamInteger => {
+                // при переполнении замера нет, как и рисунка в `draw_ellipse`
+                self.draw_bresenham_integer_to(&mut Counter::default())
+                    .ok()?;
+                |ellipse, counter| {
+                    ellipse
+                        .draw_bresenham_integer_to(counter)
+                        .expect("переполнение проверено до замера");
+                }
             }
-            trial -= 4 * (dy - sqr_a);
-
-        }
-        pixels
+            DrawType::PARAMETRIC => Ellipse::draw_parametric_to,
+            DrawType::MIDPOINT => Ellipse::draw_midpoint_to,
+            DrawType::WU => Ellipse::draw_wu_to,
+            DrawType::MARCHING => return None,
+            DrawType::BuiltIn => draw_builtin_ellipse,
+        };
+        Some(average_us(self, f, 2500))
     }
+}
 
-    pub fn draw_builtin(&self) -> Vec<Pos2> {
-        let _x = egui::Painter::ellipse_stroke(
-            &mut egui::Painter::new(
-                egui::Context::default(),
-                egui::LayerId::debug(),
-                egui::Rect::NAN,
-            ),
-            self.center.clone(),
-            self.radius,
-            egui::Stroke::new(1.0, egui::Color32::RED),
-        );
-        vec![]
+impl Measurable for RotatedEllipse {
+    const SHAPE: &'static str = "rotated";
+
+    fn measure_time(&mut self, draw_type: DrawType, rad: f32) -> Option<f64> {
+        self.set_radius([rad, rad / 2.0].into());
+        self.set_angle(30.0);
+        let f: fn(&RotatedEllipse, &mut Counter) = match draw_type {
+            DrawType::CANONICAL => RotatedEllipse::draw_canonic_to,
+            DrawType::BRESENHAM => RotatedEllipse::draw_bresenham_to,
+            DrawType::PARAMETRIC => RotatedEllipse::draw_parametric_to,
+            DrawType::MIDPOINT => RotatedEllipse::draw_midpoint_to,
+            DrawType::BresenhamInteger | DrawType::WU | DrawType::MARCHING => return None,
+            DrawType::BuiltIn => draw_builtin_rotated,
+        };
+        Some(average_us(self, f, 500))
     }
 }
 
-impl Measurable for Ellipse {
-    fn measure_time(&mut self, draw_type: DrawType, rad: f32, _file: &std::fs::File) {
-        let mut file = OpenOptions::new()
-            .write(true)
-            .open("/dev/null").unwrap();
-        self.radius = [rad, rad].into();
-        self.profile = true;
+/// Перебор клеток обходит всю площадь фигуры и на радиусах замеров
+/// слишком долог, поэтому не замеряется.
+impl Measurable for Superellipse {
+    const SHAPE: &'static str = "superellipse";
+
+    fn measure_time(&mut self, draw_type: DrawType, rad: f32) -> Option<f64> {
+        self.set_radius([rad, rad / 2.0].into());
+        let f: fn(&Superellipse, &mut Counter) = match draw_type {
+            DrawType::MIDPOINT => Superellipse::draw_midpoint_to,
+            DrawType::BuiltIn => draw_builtin_superellipse,
+            _ => return None,
+        };
+        Some(average_us(self, f, 10))
+    }
+}
 
-        let f = match draw_type {
-            DrawType::CANONICAL => Self::draw_canonic,
-            DrawType::BRESENHAM => Self::draw_bresenham,
-            DrawType::PARAMETRIC => Self::draw_parametric,
-            DrawType::MIDPOINT => Self::draw_midpoint,
-            DrawType::BuiltIn => Self::draw_builtin,
+/// Гипербола с полуосями в четверть радиуса в квадрате со стороной в два
+/// радиуса.
+impl Measurable for Conic {
+    const SHAPE: &'static str = "conic";
+
+    fn measure_time(&mut self, draw_type: DrawType, rad: f32) -> Option<f64> {
+        self.set_coeffs(Conic::hyperbola_coeffs([rad / 4.0, rad / 4.0].into()));
+        self.set_extent([rad, rad].into());
+        let f: fn(&Conic, &mut Counter) = match draw_type {
+            DrawType::MIDPOINT => Conic::draw_midpoint_to,
+            _ => return None,
         };
-        let runs = 2500;
-        let start = std::time::Instant::now();
-        for _ in 0..runs {
-            f(self);
-        }
-        let n = start.elapsed().as_micros() / runs;
-        file.write_all(format!("{} {}\n", rad, n).as_bytes()).unwrap();
+        Some(average_us(self, f, 10))
     }
 }
@@ -1,35 +1,48 @@
-use std::fs::File;
 use crate::logic::algorithms::{DrawType, Measurable};
-use std::collections::HashMap;
-use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use raster::profile::{Record, Run};
+use std::path::PathBuf;
 
-pub fn run_profile(obj: &mut impl Measurable, draw_type: DrawType, file: &mut File) {
-        for i in (1000..=10000).step_by(1000) {
-            obj.measure_time(draw_type, i as f32, file)
-        }
+/// Хранилище замеров: путь из переменной окружения `LAB4_PROFILE`, иначе
+/// `data/profile.csv` в текущем каталоге.
+pub fn profile_path() -> PathBuf {
+    std::env::var_os("LAB4_PROFILE")
+        .map_or_else(|| PathBuf::from("data/profile.csv"), PathBuf::from)
 }
 
-pub fn prof(file: &mut File) -> Vec<(i32, f64)>{
-    file.seek(SeekFrom::Start(0)).unwrap();
-    let reader = BufReader::new(file);
-    let mut sums: HashMap<i32, (i64, usize)> = HashMap::new();
-
-    for line in reader.lines() {
-        let line = line.unwrap();
-
-        let mut parts = line.split_whitespace();
-        let key: i32 = parts.next().unwrap().parse().unwrap();
-        let value: i64 = parts.next().unwrap().parse().unwrap();
+/// Замеры алгоритма на радиусах от 1000 до 10000; алгоритмы, которых у
+/// фигуры нет, дают пустой список.
+pub fn run_profile<M: Measurable>(obj: &mut M, draw_type: DrawType, run: &Run) -> Vec<Record> {
+    (1000..=10000)
+        .step_by(1000)
+        .map_while(|rad| {
+            let time = obj.measure_time(draw_type, rad as f32)?;
+            Some(run.record(M::SHAPE, draw_type.name(), rad, time))
+        })
+        .collect()
+}
 
-        let (sum, count) = sums.entry(key).or_insert((0, 0));
-        *sum += value;
-        *count += 1;
-    }
+/// Серии из хранилища в виде для графика, с русскими названиями.
+pub fn graph_values(series: Vec<(String, Vec<(u32, f64)>)>) -> Vec<(String, Vec<(i32, f64)>)> {
+    series
+        .into_iter()
+        .map(|(name, points)| {
+            let points = points.into_iter().map(|(r, t)| (r as i32, t)).collect();
+            (algo_title(&name).to_owned(), points)
+        })
+        .collect()
+}
 
-    let mut averages: Vec<(i32, f64)> = Vec::new();
-    for (key, (sum, count)) in sums {
-        averages.push((key, sum as f64 / count as f64));
+/// Название алгоритма из сводки бенчмарка или хранилища для легенды графика.
+pub fn algo_title(name: &str) -> &str {
+    match name {
+        "canonic" => "Каноническое уравнение",
+        "parametric" => "Параметрическое уравнение",
+        "bresenham" => "Алгоритм Брезенхема",
+        "bresenham-integer" => "Брезенхем на целых числах",
+        "midpoint" => "Алгоритм средней точки",
+        "wu" => "Алгоритм Ву",
+        "marching" => "Перебор клеток",
+        "builtin" => "Встроенный",
+        _ => name,
     }
-    averages.sort_by(|a, b| a.0.cmp(&b.0));
-    averages
-}
\ No newline at end of file
+}
@@ -69,15 +69,11 @@ impl GraphWindow {
         self.enabled = false;
     }
 
-    pub fn enable(&mut self) {
-        self.enabled = true;
-    }
-
     pub fn graph(points: &(String, Vec<(i32, f64)>), color: Color32) -> egui_plot::Line {
         egui_plot::Line::new(egui_plot::PlotPoints::new({
             points.1.clone()
                 .into_iter()
-                .map(|(x, y)| [x.to_owned() as f64, y.to_owned() as f64])
+                .map(|(x, y)| [x.to_owned() as f64, y.to_owned()])
                 .collect::<Vec<[f64; 2]>>()
         }))
         .color(color).name(points.0.clone())
@@ -97,13 +93,18 @@ impl GraphWindow {
             .allow_double_click_reset(false)
             .allow_drag(false)
             .x_axis_label("Радиус")
-            .y_axis_label("Время (мс)")
+            .y_axis_label("Время (мкс)")
             .show(ui, |plot_ui| {
-                plot_ui.line(Self::graph(&self.data[0], Color32::RED));
-                plot_ui.line(Self::graph(&self.data[1], Color32::GREEN));
-                plot_ui.line(Self::graph(&self.data[2], Color32::BLUE));
-                plot_ui.line(Self::graph(&self.data[3], Color32::BLACK));
-                plot_ui.line(Self::graph(&self.data[4], Color32::GRAY));
+                let colors = [
+                    Color32::RED,
+                    Color32::GREEN,
+                    Color32::BLUE,
+                    Color32::BLACK,
+                    Color32::GRAY,
+                ];
+                for (data, color) in self.data.iter().zip(colors.into_iter().cycle()) {
+                    plot_ui.line(Self::graph(data, color));
+                }
             });
     }
     pub fn update(&mut self, ctx: &egui::Context) {
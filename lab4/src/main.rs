@@ -1,17 +1,55 @@
 mod logic;
 
-use std::fs::OpenOptions;
 use eframe::egui;
 use egui::{Pos2, Vec2};
 use logic::algorithms::*;
 use logic::windows::*;
 use logic::utils::*;
+use raster::arc::ArcRange;
+use raster::circle::Circle;
+use raster::conic::Conic;
+use raster::ellipse::{rotated_ellipse_path, Ellipse, RotatedEllipse};
+use raster::profile::{self, Run};
+use raster::superellipse::Superellipse;
+use raster::{abate_color, timing};
 
 #[derive(Default, Debug, PartialEq)]
 enum ShapeType {
     #[default]
     CIRCLE,
     ELLIPSE,
+    ROTATED,
+    SUPERELLIPSE,
+    CONIC,
+}
+
+impl ShapeType {
+    /// Имя фигуры в хранилище замеров.
+    fn name(&self) -> &'static str {
+        match self {
+            ShapeType::CIRCLE => "circle",
+            ShapeType::ELLIPSE => "ellipse",
+            ShapeType::ROTATED => "rotated",
+            ShapeType::SUPERELLIPSE => "superellipse",
+            ShapeType::CONIC => "conic",
+        }
+    }
+
+    /// Есть ли у фигуры такой алгоритм.
+    fn supports(&self, draw_type: DrawType) -> bool {
+        match self {
+            ShapeType::CIRCLE | ShapeType::ELLIPSE => draw_type != DrawType::MARCHING,
+            ShapeType::ROTATED => !matches!(
+                draw_type,
+                DrawType::BresenhamInteger | DrawType::WU | DrawType::MARCHING
+            ),
+            ShapeType::SUPERELLIPSE => matches!(
+                draw_type,
+                DrawType::MIDPOINT | DrawType::MARCHING | DrawType::BuiltIn
+            ),
+            ShapeType::CONIC => matches!(draw_type, DrawType::MIDPOINT | DrawType::MARCHING),
+        }
+    }
 }
 
 #[derive(Default, Debug, PartialEq)]
@@ -25,7 +63,11 @@ enum ShowType {
 enum CanonicalShapes {
     Circle(Pos2, f32, egui::Color32),
     Ellipse(Pos2, Vec2, egui::Color32),
-    Path(Vec<egui::Pos2>, egui::Color32),
+    RotatedEllipse(Pos2, Vec2, f32, egui::Color32),
+    Superellipse(Pos2, Vec2, f32, egui::Color32),
+    Arc(Pos2, Vec2, ArcRange, egui::Color32),
+    Path(Vec<raster::Point>, egui::Color32),
+    Shaded(Vec<(raster::Point, f32)>, egui::Color32),
 }
 
 #[derive(Debug)]
@@ -43,8 +85,17 @@ struct MyApp {
     buf_radius: String,
     buf_axe1: String,
     buf_axe2: String,
+    buf_angle: String,
+    buf_exponent: String,
+    buf_coeffs: String,
+    buf_arc_start: String,
+    buf_arc_end: String,
     buf_count: String,
     buf_step: String,
+    buf_run: String,
+    runs: Vec<Run>,
+    selected_run: Option<Run>,
+    session: Option<Run>,
 }
 
 impl Default for MyApp {
@@ -63,8 +114,19 @@ impl Default for MyApp {
             buf_radius: "".to_string(),
             buf_axe1: "".to_string(),
             buf_axe2: "".to_string(),
+            buf_angle: "".to_string(),
+            buf_exponent: "".to_string(),
+            buf_coeffs: "".to_string(),
+            buf_arc_start: "".to_string(),
+            buf_arc_end: "".to_string(),
             buf_count: "".to_string(),
             buf_step: "".to_string(),
+            buf_run: "".to_string(),
+            runs: profile::load(profile_path())
+                .map(|records| profile::runs(&records))
+                .unwrap_or_default(),
+            selected_run: None,
+            session: None,
         }
     }
 }
@@ -103,11 +165,20 @@ impl MyApp {
                 use egui::color_picker::{color_edit_button_srgba, Alpha};
                 color_edit_button_srgba(ui, &mut self.background, Alpha::Opaque);
             });
-            ui.radio_value(&mut self.draw_type, DrawType::CANONICAL, "Каноническое");
-            ui.radio_value(&mut self.draw_type, DrawType::PARAMETRIC, "Параметрическое");
-            ui.radio_value(&mut self.draw_type, DrawType::MIDPOINT, "Средняя точка");
-            ui.radio_value(&mut self.draw_type, DrawType::BRESENHAM, "Брезензем");
-            ui.radio_value(&mut self.draw_type, DrawType::BuiltIn, "Библиотечная");
+            for (draw_type, title) in [
+                (DrawType::CANONICAL, "Каноническое"),
+                (DrawType::PARAMETRIC, "Параметрическое"),
+                (DrawType::MIDPOINT, "Средняя точка"),
+                (DrawType::BRESENHAM, "Брезензем"),
+                (DrawType::BresenhamInteger, "Брезенхем на целых"),
+                (DrawType::WU, "Ву (сглаживание)"),
+                (DrawType::MARCHING, "Перебор клеток"),
+                (DrawType::BuiltIn, "Библиотечная"),
+            ] {
+                if self.shape_type.supports(draw_type) {
+                    ui.radio_value(&mut self.draw_type, draw_type, title);
+                }
+            }
             ui.separator();
             ui.horizontal(|ui| {
                 ui.label("Цвет фигуры");
@@ -119,7 +190,13 @@ impl MyApp {
             ui.horizontal_wrapped(|ui| {
                 ui.radio_value(&mut self.shape_type, ShapeType::CIRCLE, "Окружность");
                 ui.radio_value(&mut self.shape_type, ShapeType::ELLIPSE, "Эллипс");
+                ui.radio_value(&mut self.shape_type, ShapeType::ROTATED, "Повёрнутый эллипс");
+                ui.radio_value(&mut self.shape_type, ShapeType::SUPERELLIPSE, "Суперэллипс");
+                ui.radio_value(&mut self.shape_type, ShapeType::CONIC, "Коника");
             });
+            if !self.shape_type.supports(self.draw_type) {
+                self.draw_type = DrawType::MIDPOINT;
+            }
             match self.shape_type {
                 ShapeType::CIRCLE => {
                     ui.add(egui::TextEdit::singleline(&mut self.buf_radius).hint_text("Радиус"));
@@ -128,6 +205,39 @@ impl MyApp {
                     ui.add(egui::TextEdit::singleline(&mut self.buf_axe1).hint_text("Полуось x"));
                     ui.add(egui::TextEdit::singleline(&mut self.buf_axe2).hint_text("Полуось y"));
                 }
+                ShapeType::ROTATED => {
+                    ui.add(egui::TextEdit::singleline(&mut self.buf_axe1).hint_text("Полуось x"));
+                    ui.add(egui::TextEdit::singleline(&mut self.buf_axe2).hint_text("Полуось y"));
+                    ui.add(egui::TextEdit::singleline(&mut self.buf_angle).hint_text("Угол"));
+                }
+                ShapeType::SUPERELLIPSE => {
+                    ui.add(egui::TextEdit::singleline(&mut self.buf_axe1).hint_text("Полуось x"));
+                    ui.add(egui::TextEdit::singleline(&mut self.buf_axe2).hint_text("Полуось y"));
+                    ui.add(egui::TextEdit::singleline(&mut self.buf_exponent).hint_text("Показатель n"));
+                }
+                ShapeType::CONIC => {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.buf_coeffs)
+                            .hint_text("A B C D E F: Ax² + Bxy + Cy² + Dx + Ey + F = 0"),
+                    );
+                    ui.add(egui::TextEdit::singleline(&mut self.buf_axe1).hint_text("Полуширина области"));
+                    ui.add(egui::TextEdit::singleline(&mut self.buf_axe2).hint_text("Полувысота области"));
+                }
+            }
+            if matches!(self.shape_type, ShapeType::CIRCLE | ShapeType::ELLIPSE) {
+                ui.horizontal(|ui| {
+                    ui.label("Дуга");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.buf_arc_start)
+                            .hint_text("от")
+                            .desired_width(60.0),
+                    );
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.buf_arc_end)
+                            .hint_text("до")
+                            .desired_width(60.0),
+                    );
+                });
             }
             ui.separator();
             ui.horizontal_wrapped(|ui| {
@@ -153,12 +263,69 @@ impl MyApp {
 
             ui.separator();
             ui.label("Замеры времени");
+            ui.add(egui::TextEdit::singleline(&mut self.buf_run).hint_text("Название запуска"));
             ui.vertical_centered_justified(|ui| {
                 if ui.button("Измерить окружности").clicked() {
-                    self.measure_circle()
+                    self.measure(&mut Circle::new([700.0, 500.0].into(), 500.0))
                 }
                 if ui.button("Измерить эллипсы").clicked() {
-                    self.measure_ellipse()
+                    self.measure(&mut Ellipse::new([700.0, 500.0].into(), [500.0, 500.0].into()))
+                }
+                if ui.button("Измерить повёрнутые эллипсы").clicked() {
+                    self.measure(&mut RotatedEllipse::new(
+                        [700.0, 500.0].into(),
+                        [500.0, 250.0].into(),
+                        30.0,
+                    ))
+                }
+                if ui.button("Измерить суперэллипсы").clicked() {
+                    self.measure(&mut Superellipse::new(
+                        [700.0, 500.0].into(),
+                        [500.0, 250.0].into(),
+                        4.0,
+                    ))
+                }
+                if ui.button("Измерить коники").clicked() {
+                    self.measure(&mut Conic::hyperbola(
+                        [700.0, 500.0].into(),
+                        [125.0, 125.0].into(),
+                        [500.0, 500.0].into(),
+                    ))
+                }
+            });
+            let labels = profile::labels(&self.runs);
+            let selected = self
+                .selected_run
+                .as_ref()
+                .and_then(|selected| self.runs.iter().position(|run| run == selected))
+                .map_or("Все запуски".to_owned(), |i| labels[i].clone());
+            egui::ComboBox::from_label("Запуск")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.selected_run, None, "Все запуски");
+                    for (run, label) in self.runs.iter().zip(&labels) {
+                        ui.selectable_value(
+                            &mut self.selected_run,
+                            Some(run.clone()),
+                            format!("{label} ({})", run.machine),
+                        );
+                    }
+                });
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Показать замеры фигуры").clicked() {
+                    self.show_runs()
+                }
+                if ui.button("Сравнить запуски алгоритма").clicked() {
+                    self.compare_runs()
+                }
+                if ui.button("Бенчмарк окружностей").clicked() {
+                    self.load_benchmark("circle")
+                }
+                if ui.button("Бенчмарк эллипсов").clicked() {
+                    self.load_benchmark("ellipse")
+                }
+                if ui.button("Бенчмарк повёрнутых эллипсов").clicked() {
+                    self.load_benchmark("rotated")
                 }
             });
             ui.separator();
@@ -196,9 +363,32 @@ impl MyApp {
             CanonicalShapes::Ellipse(center, axes, stroke) => {
                 let center = *center * unit;
                 let axes = *axes * unit;
-                egui::Shape::ellipse_stroke(
-                    to_screen.transform_pos(center.to_owned()),
-                    axes.to_owned(),
+                egui::Shape::closed_line(
+                    ellipse_path(to_screen.transform_pos(center.to_owned()), axes.to_owned()),
+                    egui::Stroke::new(unit, stroke.to_owned()),
+                )
+            }
+            CanonicalShapes::RotatedEllipse(center, axes, angle, stroke) => {
+                let center = *center * unit;
+                let axes = *axes * unit;
+                egui::Shape::closed_line(
+                    rotated_ellipse_path(to_screen.transform_pos(center), axes, *angle),
+                    egui::Stroke::new(unit, stroke.to_owned()),
+                )
+            }
+            CanonicalShapes::Superellipse(center, axes, n, stroke) => {
+                let center = *center * unit;
+                let axes = *axes * unit;
+                egui::Shape::closed_line(
+                    superellipse_path(to_screen.transform_pos(center), axes, *n),
+                    egui::Stroke::new(unit, stroke.to_owned()),
+                )
+            }
+            CanonicalShapes::Arc(center, axes, arc, stroke) => {
+                let center = *center * unit;
+                let axes = *axes * unit;
+                egui::Shape::line(
+                    ellipse_arc_path(to_screen.transform_pos(center), axes, arc),
                     egui::Stroke::new(unit, stroke.to_owned()),
                 )
             }
@@ -213,7 +403,27 @@ impl MyApp {
                             ]
                             .into(),
                             0.0,
-                            stroke.clone(),
+                            *stroke,
+                        )
+                    })
+                    .collect(),
+            ),
+            CanonicalShapes::Shaded(pix, stroke) => egui::Shape::Vec(
+                pix.iter()
+                    .map(|(p, i)| {
+                        let p = Pos2::new(p.x * unit, p.y * unit);
+                        egui::Shape::rect_filled(
+                            [
+                                to_screen.transform_pos(p),
+                                to_screen.transform_pos(p + [unit, unit].into()),
+                            ]
+                            .into(),
+                            0.0,
+                            egui::Color32::from(abate_color(
+                                (*stroke).into(),
+                                self.background.into(),
+                                *i,
+                            )),
                         )
                     })
                     .collect(),
@@ -224,106 +434,121 @@ impl MyApp {
 
 
 
-    fn measure_circle(&mut self) {
-            self.graph.enable();
-
-            let mut b = OpenOptions::new()
-                .write(true)
-                .append(true)
-                .read(true)
-                .open("./src/data/circle/bresenham.txt").unwrap();
-
-            let mut c =  OpenOptions::new()
-                .read(true)
-                .append(true)
-                .read(true)
-                .open("./src/data/circle/canonical.txt").unwrap();
-
-            let mut m =  OpenOptions::new()
-                .append(true)
-                .write(true)
-                .read(true)
-                .open("./src/data/circle/midpoint.txt").unwrap();
-
-            let mut p = OpenOptions::new()
-                .append(true)
-                .write(true)
-                .read(true)
-                .open("./src/data/circle/parametric.txt").unwrap();
-
-            let mut i = OpenOptions::new()
-                .append(true)
-                .write(true)
-                .read(true)
-                .open("./src/data/circle/builtin.txt").unwrap();
-
-            let mut circle = Circle::new([700.0, 500.0].into(), 500.0);
-            run_profile(&mut circle, DrawType::BRESENHAM, &mut b);
-            run_profile(&mut circle, DrawType::CANONICAL, &mut c);
-            run_profile(&mut circle, DrawType::MIDPOINT, &mut m);
-            run_profile(&mut circle, DrawType::PARAMETRIC, &mut p);
-            run_profile(&mut circle, DrawType::BuiltIn, &mut i);
-
-            let values = vec![
-                ("Каноническое уравнение".to_owned(), prof(&mut b)),
-                ("Параметрическое уравнение".to_owned(), prof(&mut p)),
-                ("Алгоритм средней точки".to_owned(), prof(&mut c)),
-                ("Алгоритм Брезенхема".to_owned(), prof(&mut m)),
-                ("Встроенный".to_owned(), prof(&mut i)),
-            ];
+    /// Замеры всех алгоритмов фигуры дописываются в хранилище под именем
+    /// запуска из поля ввода и сразу показываются на графике. Пока название
+    /// не меняется, замеры разных фигур идут в один запуск; то же название
+    /// в другой сессии — уже другой запуск.
+    fn measure<M: Measurable>(&mut self, obj: &mut M) {
+        let run = match self.session.take() {
+            Some(run) if run.name == self.buf_run.trim() => run,
+            _ => Run::new(&self.buf_run),
+        };
+        let path = profile_path();
+        let records = DrawType::ALL
+            .into_iter()
+            .flat_map(|draw_type| run_profile(obj, draw_type, &run))
+            .collect::<Vec<_>>();
+        if let Err(e) = profile::append(&path, &records) {
+            self.error.set_error(
+                "Ошибка".to_string(),
+                format!("Не удалось сохранить замеры в {}: {e}", path.display()),
+            );
+            return;
+        }
+        if !self.runs.contains(&run) {
+            self.runs.push(run.clone());
+        }
+        self.session = Some(run);
+        self.graph
+            .set_values(&graph_values(profile::series(&records, M::SHAPE, None)));
+    }
+
+    fn load_records(&mut self) -> Option<Vec<profile::Record>> {
+        let path = profile_path();
+        match profile::load(&path) {
+            Ok(records) => {
+                self.runs = profile::runs(&records);
+                Some(records)
+            }
+            Err(e) => {
+                self.error.set_error(
+                    "Ошибка".to_string(),
+                    format!("Не удалось прочитать замеры {}: {e}", path.display()),
+                );
+                None
+            }
+        }
+    }
+
+    /// Средние по алгоритмам для выбранной фигуры — выбранного запуска или всех.
+    fn show_runs(&mut self) {
+        let Some(records) = self.load_records() else {
+            return;
+        };
+        let shape = self.shape_type.name();
+        let values = graph_values(profile::series(&records, shape, self.selected_run.as_ref()));
+        self.show_values(values);
+    }
+
+    /// Выбранный алгоритм выбранной фигуры во всех сохранённых запусках.
+    fn compare_runs(&mut self) {
+        let Some(records) = self.load_records() else {
+            return;
+        };
+        let (shape, algorithm) = (self.shape_type.name(), self.draw_type.name());
+        let values = profile::compare_runs(&records, shape, algorithm)
+            .into_iter()
+            .map(|(run, points)| (run, points.into_iter().map(|(r, t)| (r as i32, t)).collect()))
+            .collect();
+        self.show_values(values);
+    }
+
+    fn show_values(&mut self, values: Vec<(String, Vec<(i32, f64)>)>) {
+        if values.is_empty() {
+            self.error.set_error(
+                "Ошибка".to_string(),
+                "Нет сохранённых замеров для этой фигуры".to_string(),
+            );
+        } else {
             self.graph.set_values(&values);
+        }
     }
+}
 
-    fn measure_ellipse(&mut self) {
-        self.graph.enable();
-        let mut b = OpenOptions::new()
-            .write(true)
-            .append(true)
-            .read(true)
-            .open("./src/data/ellipse/bresenham.txt").unwrap();
-            // .open("/dev/null").unwrap();
-
-        let mut c =  OpenOptions::new()
-            .read(true)
-            .append(true)
-            .read(true)
-            .open("./src/data/ellipse/canonical.txt").unwrap();
-            // .open("/dev/null").unwrap();
-
-        let mut m =  OpenOptions::new()
-            .append(true)
-            .write(true)
-            .read(true)
-            .open("./src/data/ellipse/midpoint.txt").unwrap();
-            // .open("/dev/null").unwrap();
-
-        let mut p = OpenOptions::new()
-            .append(true)
-            .write(true)
-            .read(true)
-            .open("./src/data/ellipse/parametric.txt").unwrap();
-            // .open("/dev/null").unwrap();
-
-        let mut i = OpenOptions::new()
-            .append(true)
-            .write(true)
-            .read(true)
-            .open("./src/data/ellipse/builtin.txt").unwrap();
-            // .open("/dev/null").unwrap();
-
-        let mut ellipse = Ellipse::new([700.0, 500.0].into(), [500.0, 500.0].into());
-        run_profile(&mut ellipse, DrawType::BRESENHAM, &mut b);
-        run_profile(&mut ellipse, DrawType::CANONICAL, &mut c);
-        run_profile(&mut ellipse, DrawType::MIDPOINT, &mut m);
-        run_profile(&mut ellipse, DrawType::PARAMETRIC, &mut p);
-        run_profile(&mut ellipse, DrawType::BuiltIn, &mut i);
-        let values = vec![
-            ("Каноническое уравнение".to_owned(), prof(&mut b)),
-            ("Параметрическое уравнение".to_owned(), prof(&mut c)),
-            ("Алгоритм Брезенхема".to_owned(), prof(&mut p)),
-            ("Алгоритм средней точки".to_owned(), prof(&mut m)),
-            ("Встроенный".to_owned(), prof(&mut i)),
-        ];
+impl MyApp {
+    /// Результаты `cargo bench -p raster` для группы `circle` или `ellipse`.
+    fn load_benchmark(&mut self, group: &str) {
+        let path = timing::summary_csv(&timing::criterion_dir());
+        let samples = match timing::load_csv(&path) {
+            Ok(samples) => samples,
+            Err(e) => {
+                self.error.set_error(
+                    "Ошибка".to_string(),
+                    format!(
+                        "Нет результатов замеров {}: {e}\nЗапустите cargo bench -p raster",
+                        path.display()
+                    ),
+                );
+                return;
+            }
+        };
+        let values = timing::series(&samples, group)
+            .into_iter()
+            .map(|(name, points)| {
+                let points = points
+                    .into_iter()
+                    .map(|(r, ns)| (r as i32, ns / 1000.0))
+                    .collect();
+                (algo_title(&name).to_owned(), points)
+            })
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            self.error.set_error(
+                "Ошибка".to_string(),
+                format!("В замерах нет группы {group}"),
+            );
+            return;
+        }
         self.graph.set_values(&values);
     }
 }
@@ -342,13 +567,24 @@ impl MyApp {
         })
     }
 
+    /// Промежуток дуги из полей «от» и «до»; оба пустые — полная кривая.
+    fn parse_arc(&mut self) -> Result<ArcRange, ()> {
+        if self.buf_arc_start.trim().is_empty() && self.buf_arc_end.trim().is_empty() {
+            return Ok(ArcRange::full());
+        }
+        let start = self.parse_field::<f32>(self.buf_arc_start.trim().to_owned())?;
+        let end = self.parse_field::<f32>(self.buf_arc_end.trim().to_owned())?;
+        Ok(ArcRange::new(start, end))
+    }
+
     fn parse_to_draw_circle(&mut self) {
-        if let (Ok(x), Ok(y), Ok(r)) = (
+        if let (Ok(x), Ok(y), Ok(r), Ok(arc)) = (
             self.parse_field::<u32>(self.buf_x.clone()),
             self.parse_field::<u32>(self.buf_y.clone()),
             self.parse_field::<u32>(self.buf_radius.clone()),
+            self.parse_arc(),
         ) {
-            self.draw_circle([x as f32, y as f32].into(), r as f32);
+            self.draw_circle([x as f32, y as f32].into(), r as f32, arc);
         } else {
             self.error.enable();
         }
@@ -358,82 +594,226 @@ impl MyApp {
         if self.buf_axe1 == "0" || self.buf_axe2 == "0" {
             self.error.set_error("Ошибка".to_owned(), "0 полуось нельзя".to_owned());
             self.error.enable();
-            return;
         }
-        else if let (Ok(x), Ok(y), Ok(a1), Ok(a2)) = (
+        else if let (Ok(x), Ok(y), Ok(a1), Ok(a2), Ok(arc)) = (
             self.parse_field::<u32>(self.buf_x.clone()),
             self.parse_field::<u32>(self.buf_y.clone()),
             self.parse_field::<u32>(self.buf_axe1.clone()),
             self.parse_field::<u32>(self.buf_axe2.clone()),
+            self.parse_arc(),
         ) {
-            self.draw_ellipse([x as f32 , y as f32 ].into(), [a1 as f32, a2 as f32].into());
+            self.draw_ellipse([x as f32 , y as f32 ].into(), [a1 as f32, a2 as f32].into(), arc);
         } else {
             self.error.enable()
         }
     }
 
-    fn draw_circle(&mut self, center: Pos2, r: f32) {
-        match self.draw_type {
-            DrawType::CANONICAL => {
-                self.shapes.push(CanonicalShapes::Path(
-                    Circle::new(center, r).draw_canonic(),
-                    self.stroke,
-                ));
-            }
-            DrawType::PARAMETRIC => {
-                self.shapes.push(CanonicalShapes::Path(
-                    Circle::new(center, r).draw_parametric(),
-                    self.stroke,
-                ));
+    fn draw_circle(&mut self, center: Pos2, r: f32, arc: ArcRange) {
+        let mut circle = Circle::new(center.into(), r);
+        circle.set_arc(arc);
+        let pixels = match self.draw_type {
+            DrawType::CANONICAL => circle.draw_canonic(),
+            DrawType::PARAMETRIC => circle.draw_parametric(),
+            DrawType::MIDPOINT => circle.draw_midpoint(),
+            DrawType::BRESENHAM => circle.draw_bresenham(),
+            DrawType::BresenhamInteger => match circle.draw_bresenham_integer() {
+                Ok(pixels) => pixels,
+                Err(_) => return self.overflow(),
+            },
+            DrawType::WU => {
+                self.shapes
+                    .push(CanonicalShapes::Shaded(circle.draw_wu(), self.stroke));
+                return;
             }
-            DrawType::MIDPOINT => {
-                self.shapes.push(CanonicalShapes::Path(
-                    Circle::new(center, r).draw_midpoint(),
-                    self.stroke,
-                ));
+            DrawType::MARCHING => return self.unsupported(),
+            DrawType::BuiltIn if arc.is_full() => {
+                self.shapes.push(CanonicalShapes::Circle(center, r, self.stroke));
+                return;
             }
-            DrawType::BRESENHAM => {
-                self.shapes.push(CanonicalShapes::Path(
-                    Circle::new(center, r).draw_bresenham(),
-                    self.stroke,
-                ));
+            DrawType::BuiltIn => {
+                self.shapes
+                    .push(CanonicalShapes::Arc(center, Vec2::splat(r), arc, self.stroke));
+                return;
             }
-            DrawType::BuiltIn => self
-                .shapes
-                .push(CanonicalShapes::Circle(center, r, self.stroke)),
-        }
+        };
+        self.shapes.push(CanonicalShapes::Path(pixels, self.stroke));
     }
 
-    fn draw_ellipse(&mut self, center: Pos2, radius: Vec2) {
-        match self.draw_type {
-            DrawType::CANONICAL => {
-                self.shapes.push(CanonicalShapes::Path(
-                    Ellipse::new(center, radius).draw_canonic(),
-                    self.stroke,
-                ));
+    fn draw_ellipse(&mut self, center: Pos2, radius: Vec2, arc: ArcRange) {
+        let mut ellipse = Ellipse::new(center.into(), radius.into());
+        ellipse.set_arc(arc);
+        let pixels = match self.draw_type {
+            DrawType::CANONICAL => ellipse.draw_canonic(),
+            DrawType::PARAMETRIC => ellipse.draw_parametric(),
+            DrawType::MIDPOINT => ellipse.draw_midpoint(),
+            DrawType::BRESENHAM => ellipse.draw_bresenham(),
+            DrawType::BresenhamInteger => match ellipse.draw_bresenham_integer() {
+                Ok(pixels) => pixels,
+                Err(_) => return self.overflow(),
+            },
+            DrawType::WU => {
+                self.shapes
+                    .push(CanonicalShapes::Shaded(ellipse.draw_wu(), self.stroke));
+                return;
             }
-            DrawType::PARAMETRIC => {
-                self.shapes.push(CanonicalShapes::Path(
-                    Ellipse::new(center, radius).draw_parametric(),
-                    self.stroke,
-                ));
+            DrawType::MARCHING => return self.unsupported(),
+            DrawType::BuiltIn if arc.is_full() => {
+                self.shapes
+                    .push(CanonicalShapes::Ellipse(center, radius, self.stroke));
+                return;
             }
-            DrawType::MIDPOINT => {
-                self.shapes.push(CanonicalShapes::Path(
-                    Ellipse::new(center, radius).draw_midpoint(),
-                    self.stroke,
-                ));
+            DrawType::BuiltIn => {
+                self.shapes
+                    .push(CanonicalShapes::Arc(center, radius, arc, self.stroke));
+                return;
             }
-            DrawType::BRESENHAM => {
-                self.shapes.push(CanonicalShapes::Path(
-                    Ellipse::new(center, radius).draw_bresenham(),
+        };
+        self.shapes.push(CanonicalShapes::Path(pixels, self.stroke));
+    }
+
+    fn draw_rotated(&mut self, center: Pos2, radius: Vec2, angle: f32) {
+        let ellipse = RotatedEllipse::new(center.into(), radius.into(), angle);
+        let pixels = match self.draw_type {
+            DrawType::CANONICAL => ellipse.draw_canonic(),
+            DrawType::PARAMETRIC => ellipse.draw_parametric(),
+            DrawType::MIDPOINT => ellipse.draw_midpoint(),
+            DrawType::BRESENHAM => ellipse.draw_bresenham(),
+            DrawType::WU => {
+                self.error.set_error(
+                    "Ошибка".to_owned(),
+                    "Сглаживание для повёрнутого эллипса не поддерживается".to_owned(),
+                );
+                return;
+            }
+            DrawType::BresenhamInteger | DrawType::MARCHING => return self.unsupported(),
+            DrawType::BuiltIn => {
+                self.shapes.push(CanonicalShapes::RotatedEllipse(
+                    center,
+                    radius,
+                    angle,
                     self.stroke,
                 ));
+                return;
             }
+        };
+        self.shapes.push(CanonicalShapes::Path(pixels, self.stroke));
+    }
+
+    fn draw_superellipse(&mut self, center: Pos2, radius: Vec2, n: f32) {
+        let superellipse = Superellipse::new(center.into(), radius.into(), n);
+        let pixels = match self.draw_type {
+            DrawType::MIDPOINT => superellipse.draw_midpoint(),
+            DrawType::MARCHING => superellipse.draw_marching(),
             DrawType::BuiltIn => {
                 self.shapes
-                    .push(CanonicalShapes::Ellipse(center, radius, self.stroke))
+                    .push(CanonicalShapes::Superellipse(center, radius, n, self.stroke));
+                return;
+            }
+            _ => return self.unsupported(),
+        };
+        self.shapes.push(CanonicalShapes::Path(pixels, self.stroke));
+    }
+
+    fn draw_conic(&mut self, center: Pos2, coeffs: [f64; 6], extent: Vec2) {
+        let conic = Conic::new(center.into(), coeffs, extent.into());
+        let pixels = match self.draw_type {
+            DrawType::MIDPOINT => conic.draw_midpoint(),
+            DrawType::MARCHING => conic.draw_marching(),
+            _ => return self.unsupported(),
+        };
+        self.shapes.push(CanonicalShapes::Path(pixels, self.stroke));
+    }
+
+    fn unsupported(&mut self) {
+        self.error.set_error(
+            "Ошибка".to_owned(),
+            "Этот алгоритм для выбранной фигуры не поддерживается".to_owned(),
+        );
+    }
+
+    fn overflow(&mut self) {
+        self.error.set_error(
+            "Ошибка".to_owned(),
+            "Фигура слишком велика для целочисленного алгоритма".to_owned(),
+        );
+    }
+
+    fn parse_to_draw_superellipse(&mut self) {
+        if self.buf_axe1 == "0" || self.buf_axe2 == "0" {
+            self.error.set_error("Ошибка".to_owned(), "0 полуось нельзя".to_owned());
+        } else if let (Ok(x), Ok(y), Ok(a1), Ok(a2), Ok(n)) = (
+            self.parse_field::<u32>(self.buf_x.clone()),
+            self.parse_field::<u32>(self.buf_y.clone()),
+            self.parse_field::<u32>(self.buf_axe1.clone()),
+            self.parse_field::<u32>(self.buf_axe2.clone()),
+            self.parse_field::<f32>(self.buf_exponent.clone()),
+        ) {
+            if n > 0.0 {
+                self.draw_superellipse(
+                    [x as f32, y as f32].into(),
+                    [a1 as f32, a2 as f32].into(),
+                    n,
+                );
+            } else {
+                self.error
+                    .set_error("Ошибка".to_owned(), "Показатель должен быть больше 0".to_owned());
             }
+        } else {
+            self.error.enable()
+        }
+    }
+
+    /// Шесть коэффициентов через пробел или запятую.
+    fn parse_coeffs(&mut self) -> Result<[f64; 6], ()> {
+        let coeffs = self
+            .buf_coeffs
+            .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .filter(|s| !s.is_empty())
+            .map(str::parse::<f64>)
+            .collect::<Result<Vec<_>, _>>();
+        match coeffs.ok().and_then(|v| <[f64; 6]>::try_from(v).ok()) {
+            Some(coeffs) => Ok(coeffs),
+            None => {
+                self.error.set_error(
+                    "Ошибка".to_owned(),
+                    format!("Нужно шесть коэффициентов A B C D E F, а не «{}»", self.buf_coeffs),
+                );
+                Err(())
+            }
+        }
+    }
+
+    fn parse_to_draw_conic(&mut self) {
+        if let (Ok(x), Ok(y), Ok(w), Ok(h), Ok(coeffs)) = (
+            self.parse_field::<u32>(self.buf_x.clone()),
+            self.parse_field::<u32>(self.buf_y.clone()),
+            self.parse_field::<u32>(self.buf_axe1.clone()),
+            self.parse_field::<u32>(self.buf_axe2.clone()),
+            self.parse_coeffs(),
+        ) {
+            self.draw_conic([x as f32, y as f32].into(), coeffs, [w as f32, h as f32].into());
+        } else {
+            self.error.enable()
+        }
+    }
+
+    fn parse_to_draw_rotated(&mut self) {
+        if self.buf_axe1 == "0" || self.buf_axe2 == "0" {
+            self.error.set_error("Ошибка".to_owned(), "0 полуось нельзя".to_owned());
+        } else if let (Ok(x), Ok(y), Ok(a1), Ok(a2), Ok(angle)) = (
+            self.parse_field::<u32>(self.buf_x.clone()),
+            self.parse_field::<u32>(self.buf_y.clone()),
+            self.parse_field::<u32>(self.buf_axe1.clone()),
+            self.parse_field::<u32>(self.buf_axe2.clone()),
+            self.parse_field::<f32>(self.buf_angle.clone()),
+        ) {
+            self.draw_rotated(
+                [x as f32, y as f32].into(),
+                [a1 as f32, a2 as f32].into(),
+                angle,
+            );
+        } else {
+            self.error.enable()
         }
     }
 
@@ -442,24 +822,34 @@ impl MyApp {
             ShowType::ONE => match self.shape_type {
                 ShapeType::CIRCLE => self.parse_to_draw_circle(),
                 ShapeType::ELLIPSE => self.parse_to_draw_ellipse(),
+                ShapeType::ROTATED => self.parse_to_draw_rotated(),
+                ShapeType::SUPERELLIPSE => self.parse_to_draw_superellipse(),
+                ShapeType::CONIC => self.parse_to_draw_conic(),
             },
             ShowType::SPECTRE => match self.shape_type {
                 ShapeType::CIRCLE => self.parse_to_draw_circle_spectre(),
                 ShapeType::ELLIPSE => self.parse_to_draw_ellipse_spectre(),
+                ShapeType::ROTATED => self.parse_to_draw_rotated_spectre(),
+                ShapeType::SUPERELLIPSE => self.parse_to_draw_superellipse_spectre(),
+                ShapeType::CONIC => self.error.set_error(
+                    "Ошибка".to_owned(),
+                    "Спектр для коники не строится".to_owned(),
+                ),
             },
         }
     }
 
     fn parse_to_draw_circle_spectre(&mut self) {
-        if let (Ok(x), Ok(y), Ok(r), Ok(step), Ok(count)) = (
+        if let (Ok(x), Ok(y), Ok(r), Ok(step), Ok(count), Ok(arc)) = (
             self.parse_field::<u32>(self.buf_x.clone()),
             self.parse_field::<u32>(self.buf_y.clone()),
             self.parse_field::<u32>(self.buf_radius.clone()),
             self.parse_field::<u32>(self.buf_step.clone()),
             self.parse_field::<u32>(self.buf_count.clone()),
+            self.parse_arc(),
         ) {
             for i in 0..count {
-                self.draw_circle([x as f32, y as f32].into(), (r + (i * step)) as f32);
+                self.draw_circle([x as f32, y as f32].into(), (r + (i * step)) as f32, arc);
             }
         } else {
             self.error.enable();
@@ -471,19 +861,21 @@ impl MyApp {
             self.error.enable();
             return;
         }
-        if let (Ok(x), Ok(y), Ok(a1), Ok(a2), Ok(step), Ok(count)) = (
+        if let (Ok(x), Ok(y), Ok(a1), Ok(a2), Ok(step), Ok(count), Ok(arc)) = (
             self.parse_field::<u32>(self.buf_x.clone()),
             self.parse_field::<u32>(self.buf_y.clone()),
             self.parse_field::<u32>(self.buf_axe1.clone()),
             self.parse_field::<u32>(self.buf_axe2.clone()),
             self.parse_field::<u32>(self.buf_step.clone()),
             self.parse_field::<u32>(self.buf_count.clone()),
+            self.parse_arc(),
         ) {
                 let y_step = step as f32 * a2 as f32 / a1 as f32;
                 for i in 0..count {
                     self.draw_ellipse(
                         [x as f32, y as f32].into(),
-                        [(a1 + (i * step)) as f32, (a2 as f32 + (i as f32 * y_step.round()) as f32)].into(),
+                        [(a1 + (i * step)) as f32, (a2 as f32 + (i as f32 * y_step.round()))].into(),
+                        arc,
                     );
                 }
         } else {
@@ -491,6 +883,65 @@ impl MyApp {
         }
     }
 
+    fn parse_to_draw_rotated_spectre(&mut self) {
+        if self.buf_axe1 == "0" || self.buf_axe2 == "0" {
+            self.error.set_error("Ошибка".to_owned(), "0 полуось нельзя".to_owned());
+            return;
+        }
+        if let (Ok(x), Ok(y), Ok(a1), Ok(a2), Ok(angle), Ok(step), Ok(count)) = (
+            self.parse_field::<u32>(self.buf_x.clone()),
+            self.parse_field::<u32>(self.buf_y.clone()),
+            self.parse_field::<u32>(self.buf_axe1.clone()),
+            self.parse_field::<u32>(self.buf_axe2.clone()),
+            self.parse_field::<f32>(self.buf_angle.clone()),
+            self.parse_field::<u32>(self.buf_step.clone()),
+            self.parse_field::<u32>(self.buf_count.clone()),
+        ) {
+            let y_step = step as f32 * a2 as f32 / a1 as f32;
+            for i in 0..count {
+                self.draw_rotated(
+                    [x as f32, y as f32].into(),
+                    [(a1 + (i * step)) as f32, (a2 as f32 + (i as f32 * y_step.round()))].into(),
+                    angle,
+                );
+            }
+        } else {
+            self.error.enable();
+        }
+    }
+
+    fn parse_to_draw_superellipse_spectre(&mut self) {
+        if self.buf_axe1 == "0" || self.buf_axe2 == "0" {
+            self.error.set_error("Ошибка".to_owned(), "0 полуось нельзя".to_owned());
+            return;
+        }
+        if let (Ok(x), Ok(y), Ok(a1), Ok(a2), Ok(n), Ok(step), Ok(count)) = (
+            self.parse_field::<u32>(self.buf_x.clone()),
+            self.parse_field::<u32>(self.buf_y.clone()),
+            self.parse_field::<u32>(self.buf_axe1.clone()),
+            self.parse_field::<u32>(self.buf_axe2.clone()),
+            self.parse_field::<f32>(self.buf_exponent.clone()),
+            self.parse_field::<u32>(self.buf_step.clone()),
+            self.parse_field::<u32>(self.buf_count.clone()),
+        ) {
+            if n <= 0.0 {
+                self.error
+                    .set_error("Ошибка".to_owned(), "Показатель должен быть больше 0".to_owned());
+                return;
+            }
+            let y_step = step as f32 * a2 as f32 / a1 as f32;
+            for i in 0..count {
+                self.draw_superellipse(
+                    [x as f32, y as f32].into(),
+                    [(a1 + (i * step)) as f32, (a2 as f32 + (i as f32 * y_step.round()))].into(),
+                    n,
+                );
+            }
+        } else {
+            self.error.enable();
+        }
+    }
+
     fn clear(&mut self) {
         self.shapes.clear();
     }
@@ -1,47 +1,109 @@
 use eframe::egui::{Color32, Pos2};
-use itertools::Itertools;
-use std::cmp::Ordering;
-use std::collections::{BTreeSet, HashMap};
-use std::sync::{Arc, Mutex};
-
-#[derive(Debug, Clone, Copy)]
-pub struct EdgeInfo {
-    x: f32,
-    dy: f32,
-    dx: f32,
-}
+use raster::paint::Paint;
+use raster::{point, Point, Sink};
 
-impl Eq for EdgeInfo {}
+/// Кусок заливки: пиксели одной строки (центр пикселя — целая точка, его
+/// края — на полпикселя в стороны). В `pixels` — цвет и ослабление от 0 до
+/// 255, как в `abate_color`, каждого пикселя слева направо или одна пара на
+/// весь кусок.
+#[derive(Debug, Clone)]
+pub struct Piece {
+    pub min: Pos2,
+    pub max: Pos2,
+    pub pixels: Vec<(Color32, f32)>,
+}
 
-impl PartialEq<Self> for EdgeInfo {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.dx == other.dx && self.dy == other.dy
+impl Piece {
+    /// Прямоугольники с цветом и ослаблением: кусок одного цвета — один
+    /// прямоугольник, иначе — по пикселю.
+    pub fn rects(&self) -> impl Iterator<Item = (Pos2, Pos2, Color32, f32)> + '_ {
+        let whole = self.pixels.len() == 1;
+        self.pixels.iter().enumerate().map(move |(k, &(color, i))| {
+            if whole {
+                (self.min, self.max, color, i)
+            } else {
+                let x = self.min.x + k as f32;
+                (
+                    Pos2::new(x, self.min.y),
+                    Pos2::new(x + 1.0, self.max.y),
+                    color,
+                    i,
+                )
+            }
+        })
     }
 }
 
-impl PartialOrd<Self> for EdgeInfo {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+/// Куски заливки, которые отдаёт растеризатор, по строкам: заливки пишут
+/// строку целиком, прежде чем перейти к следующей. Цвет каждого пикселя
+/// берётся из `paint`; отрезок остаётся одним куском, у одноцветного и цвет
+/// один на весь кусок.
+#[derive(Debug)]
+pub struct Pieces {
+    paint: Paint,
+    rows: Vec<Vec<Piece>>,
+}
+
+impl Pieces {
+    pub fn new(paint: Paint) -> Self {
+        Self {
+            paint,
+            rows: vec![],
+        }
+    }
+
+    /// Куски, сгруппированные по строкам в порядке заливки.
+    pub fn into_rows(self) -> Vec<Vec<Piece>> {
+        self.rows
+    }
+
+    fn push(&mut self, from: Point, to: Point, pixels: Vec<(Color32, f32)>) {
+        let piece = Piece {
+            min: Pos2::new(from.x - 0.5, from.y - 0.5),
+            max: Pos2::new(to.x + 0.5, to.y + 0.5),
+            pixels,
+        };
+        match self.rows.last_mut() {
+            Some(row) if row[0].min.y == piece.min.y => row.push(piece),
+            _ => self.rows.push(vec![piece]),
+        }
     }
 }
 
-impl Ord for EdgeInfo {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.x
-            .partial_cmp(&other.x)
-            .unwrap_or(Ordering::Equal)
-            .then_with(|| {
-                self.dy
-                    .partial_cmp(&other.dy)
-                    .unwrap_or(Ordering::Equal)
-                    .then_with(|| self.dx.partial_cmp(&other.dx).unwrap_or(Ordering::Equal))
-            })
+impl Sink for Pieces {
+    fn plot(&mut self, p: Point) {
+        self.plot_shaded(p, 0.0);
+    }
+
+    /// Пиксель сразу справа от куска с цветом на каждый пиксель продолжает
+    /// этот кусок, так что края сглаженной заливки не дробятся по пикселю.
+    fn plot_shaded(&mut self, p: Point, i: f32) {
+        let pixel = (self.paint.at(p).into(), i);
+        if let Some(piece) = self.rows.last_mut().and_then(|row| row.last_mut()) {
+            let per_pixel = piece.pixels.len() as f32 == piece.max.x - piece.min.x;
+            if per_pixel && piece.min.y == p.y - 0.5 && piece.max.x == p.x - 0.5 {
+                piece.max.x += 1.0;
+                piece.pixels.push(pixel);
+                return;
+            }
+        }
+        self.push(p, p, vec![pixel]);
+    }
+
+    fn span(&mut self, y: f32, x_from: f32, x_to: f32) {
+        let pixels = match self.paint.solid() {
+            Some(color) => vec![(color.into(), 0.0)],
+            None => (x_from as i32..=x_to as i32)
+                .map(|x| (self.paint.at(point(x as f32, y)).into(), 0.0))
+                .collect(),
+        };
+        self.push(point(x_from, y), point(x_to, y), pixels);
     }
 }
 
 #[derive(Debug)]
 pub struct Canvas {
-    filler: Vec<(Pos2, Pos2)>,
+    filler: Vec<Piece>,
     edges: Vec<(usize, usize)>,
 
     last_closed: Vec<usize>,
@@ -50,9 +112,6 @@ pub struct Canvas {
 
     points: Vec<Pos2>,
     color32: Color32,
-    active_edges: Vec<(f32, f32, f32)>,
-
-    y_groups: HashMap<i32, BTreeSet<EdgeInfo>>,
 }
 
 impl Canvas {
@@ -65,8 +124,6 @@ impl Canvas {
             max_bound: [-f32::INFINITY; 2].into(),
             points: vec![],
             color32: Color32::WHITE,
-            active_edges: vec![],
-            y_groups: HashMap::new(),
         }
     }
 
@@ -74,10 +131,6 @@ impl Canvas {
         self.last_closed.last().unwrap().to_owned()
     }
 
-    pub fn points_rem(&mut self, c: usize) {
-        self.points.remove(c);
-    }
-
     pub fn all_closed(&self) -> &[usize] {
         &self.last_closed
     }
@@ -92,11 +145,11 @@ impl Canvas {
         self.color32 = color32;
     }
 
-    pub fn fill_string(&mut self, pos1: Pos2, pos2: Pos2) {
-        self.filler.push((pos1, pos2));
+    pub fn fill_row(&mut self, row: Vec<Piece>) {
+        self.filler.extend(row);
     }
 
-    pub fn filler(&self) -> &[(Pos2, Pos2)] {
+    pub fn filler(&self) -> &[Piece] {
         &self.filler
     }
 
@@ -134,15 +187,10 @@ impl Canvas {
         self.max_bound = [-f32::INFINITY; 2].into();
         self.edges.clear();
         self.filler.clear();
-        self.y_groups.clear();
-        self.active_edges.clear();
     }
 
     pub fn clean(&mut self) {
         self.filler.clear();
-        self.active_edges.clear();
-        self.y_groups.clear();
-        self.update_y_group(0);
     }
 
     // pub fn test_figure(&self) -> bool {
@@ -154,8 +202,8 @@ impl Canvas {
         if self.points.len() > last && self.points[last] == pos2 {
             self.close();
         } else {
-            self.update_bounds(pos2.clone());
-            self.points.push(pos2.clone());
+            self.update_bounds(pos2);
+            self.points.push(pos2);
             let len = self.points.len();
             if len - last >= 2 {
                 self.add_line(len - 2, len - 1);
@@ -163,96 +211,64 @@ impl Canvas {
         }
     }
 
-    fn update_bounds(&mut self, pos2: Pos2) {
-        self.min_bound.x = self.min_bound.x.min(pos2.x);
-        self.min_bound.y = self.min_bound.y.min(pos2.y);
+    /// Готовый замкнутый многоугольник (например, сектор); вершины
+    /// добавляются без проверки на совпадение с первой.
+    pub fn add_polygon(&mut self, vertices: &[Pos2]) -> Option<()> {
+        if self.points.len() != self.last_closed() {
+            return None;
+        }
+        for &pos2 in vertices {
+            self.update_bounds(pos2);
+            self.points.push(pos2);
+            let len = self.points.len();
+            if len - self.last_closed() >= 2 {
+                self.add_line(len - 2, len - 1);
+            }
+        }
+        self.close()
+    }
 
-        self.max_bound.x = self.max_bound.x.max(pos2.x);
-        self.max_bound.y = self.max_bound.y.max(pos2.y);
+    /// Прямоугольник, охватывающий все вершины.
+    pub fn bounds(&self) -> (Pos2, Pos2) {
+        (self.min_bound, self.max_bound)
     }
 
-    fn create_y_groups(&mut self) {
-        fn get_edge(mut begin: Pos2, mut end: Pos2) -> Option<(i32, EdgeInfo)> {
-            if begin.y > end.y {
-                std::mem::swap(&mut end, &mut begin)
-            }
-            let dy = end.y - begin.y;
-            if dy == 0.0 {
-                None
-            } else {
-                Some((
-                    end.y as i32,
-                    EdgeInfo {
-                        x: end.x,
-                        dy,
-                        dx: (begin.x - end.x) / dy,
-                    },
-                ))
-            }
-        }
-        self.last_closed.windows(2).for_each(|pair| {
-            let edges = self.points[pair[0]..pair[1]]
-                .windows(2)
-                .flat_map(|window| window.iter().zip(window.iter().skip(1)))
-                .filter_map(|(p1, p2)| get_edge(*p1, *p2))
-                .chain(get_edge(self.points[pair[0]], self.points[pair[1] - 1]));
-            edges.for_each(|(y, edge)| {
-                self.y_groups
-                    .entry(y)
-                    .or_insert_with(BTreeSet::new)
-                    .insert(edge);
-            });
-        });
+    /// Замкнутые фигуры как контуры для заливки.
+    pub fn contours(&self) -> Vec<Vec<raster::Point>> {
+        self.last_closed
+            .windows(2)
+            .map(|pair| {
+                self.points[pair[0]..pair[1]]
+                    .iter()
+                    .map(|&p| p.into())
+                    .collect()
+            })
+            .collect()
     }
 
-    fn update_y_group(&mut self, y: i32) {
-        if let Some((_, edges)) = self.y_groups.remove_entry(&(y + 1)) {
-            let edges = edges.into_iter().filter_map(|edge| {
-                if edge.dy != 1.0 {
-                    Some(EdgeInfo {
-                        x: edge.x + edge.dx,
-                        dy: edge.dy - 1.0,
-                        dx: edge.dx,
-                    })
-                } else {
-                    None
-                }
-            });
-            self.y_groups
-                .entry(y)
-                .or_insert_with(BTreeSet::new)
-                .extend(edges);
-        }
+    /// Вершины фигуры, которая ещё не замкнута.
+    pub fn open_points(&self) -> &[Pos2] {
+        &self.points[self.last_closed()..]
     }
 
-    pub fn filling(canvas: &Arc<Mutex<Self>>, dur: &mut Arc<Mutex<std::time::Duration>>, delay: u64) {
-        let start = std::time::Instant::now();
-        let mut canvas_locked = canvas.lock().unwrap();
-        canvas_locked.create_y_groups();
-        let (min_y, max_y) = (
-            canvas_locked.min_bound.y as i32,
-            canvas_locked.max_bound.y as i32,
-        );
-        drop(canvas_locked);
-        for y in (min_y + 1..=max_y).rev() {
-            let cur_y_group = canvas
-                .lock()
-                .unwrap()
-                .y_groups
-                .get(&y)
-                .unwrap_or(&BTreeSet::new())
-                .clone()
-                .into_iter();
-            for mut line in &cur_y_group.chunks(2) {
-                let line1 = line.next().unwrap();
-                let line2 = line.next().unwrap();
-                let p1 = Pos2::new(line1.x, y as f32);
-                let p2 = Pos2::new(line2.x, y as f32);
-                std::thread::sleep(std::time::Duration::from_millis(delay));
-                canvas.lock().unwrap().fill_string(p1.round(), p2.round());
-            }
-            *dur.lock().unwrap() = start.elapsed();
-            canvas.lock().unwrap().update_y_group(y - 1);
+    /// Убрать незамкнутую фигуру вместе с её рёбрами и вернуть её вершины.
+    pub fn take_open(&mut self) -> Vec<Pos2> {
+        let last = self.last_closed();
+        let open = self.points.split_off(last);
+        self.edges.retain(|&(a, b)| a < last && b < last);
+        self.min_bound = [f32::INFINITY; 2].into();
+        self.max_bound = [-f32::INFINITY; 2].into();
+        for pos2 in self.points.clone() {
+            self.update_bounds(pos2);
         }
+        open
+    }
+
+    fn update_bounds(&mut self, pos2: Pos2) {
+        self.min_bound.x = self.min_bound.x.min(pos2.x);
+        self.min_bound.y = self.min_bound.y.min(pos2.y);
+
+        self.max_bound.x = self.max_bound.x.max(pos2.x);
+        self.max_bound.y = self.max_bound.y.max(pos2.y);
     }
 }
@@ -3,14 +3,24 @@ mod logic;
 use eframe::egui;
 use eframe::egui::Pos2;
 use egui_extras::{Column, TableBuilder};
-use logic::utils::Canvas;
+use logic::utils::{Canvas, Piece, Pieces};
 use logic::windows::ErrorWindow;
+use raster::arc::{ArcRange, Sector, SectorKind};
+use raster::bezier::Flattening;
+use raster::fill::{self, Rule};
+use raster::paint::{self, Paint};
+use raster::spline::{Kind, Spline};
+use raster::Color;
 use std::{
-    sync::{Arc, Mutex},
+    sync::{mpsc, Arc, Mutex},
     thread,
+    time::{Duration, Instant},
 };
 // use eframe::egui::CursorIcon::Default;
 
+/// Допуск замены сплайна ломаной, в пикселях.
+const SPLINE_TOLERANCE: f32 = 0.5;
+
 pub fn are_collinear(
     x1: f32,
     y1: f32,
@@ -30,15 +40,28 @@ pub fn are_collinear(
 struct MyApp {
     background: egui::Color32,
     stroke: egui::Color32,
+    second_color: egui::Color32,
+    paint_kind: paint::Kind,
     error: ErrorWindow,
     canvas: Arc<Mutex<Canvas>>,
 
     buf_x: String,
     buf_y: String,
 
+    buf_rad1: String,
+    buf_rad2: String,
+    buf_arc_start: String,
+    buf_arc_end: String,
+    sector_kind: SectorKind,
+    spline: Option<Kind>,
+    fill_rule: Rule,
+    antialiasing: bool,
+
     buf_dur: String,
-    dur_res: Arc<Mutex<std::time::Duration>>,
+    dur_res: Duration,
     timeout: bool,
+    /// Строки заливки, которые поток воспроизведения отдаёт с задержкой.
+    replay: Option<mpsc::Receiver<Vec<Piece>>>,
 }
 
 impl Default for MyApp {
@@ -47,12 +70,23 @@ impl Default for MyApp {
             error: Default::default(),
             background: egui::Color32::WHITE,
             stroke: egui::Color32::RED,
+            second_color: egui::Color32::BLUE,
+            paint_kind: paint::Kind::Solid,
             canvas: Arc::new(Mutex::new(Canvas::new())),
             buf_x: "".to_string(),
             buf_y: "".to_string(),
+            buf_rad1: "".to_string(),
+            buf_rad2: "".to_string(),
+            buf_arc_start: "".to_string(),
+            buf_arc_end: "".to_string(),
+            sector_kind: SectorKind::Pie,
+            spline: None,
+            fill_rule: Rule::EvenOdd,
+            antialiasing: false,
             buf_dur: "".to_string(),
-            dur_res: Arc::new(Mutex::new(std::time::Duration::new(0, 0))),
+            dur_res: Duration::ZERO,
             timeout: false,
+            replay: None,
         }
     }
 }
@@ -60,6 +94,7 @@ impl Default for MyApp {
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         ctx.request_repaint();
+        self.receive_pieces();
         if self.error.enabled() {
             self.error.update(ctx);
         }
@@ -97,6 +132,20 @@ impl MyApp {
                 color_edit_button_srgba(ui, &mut self.stroke, Alpha::Opaque);
                 canvas.set_color(self.stroke);
             });
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Заливка");
+                ui.radio_value(&mut self.paint_kind, paint::Kind::Solid, "Сплошная");
+                ui.radio_value(&mut self.paint_kind, paint::Kind::Linear, "Линейный градиент");
+                ui.radio_value(&mut self.paint_kind, paint::Kind::Radial, "Радиальный градиент");
+                ui.radio_value(&mut self.paint_kind, paint::Kind::Pattern, "Узор");
+            });
+            if self.paint_kind != paint::Kind::Solid {
+                ui.horizontal(|ui| {
+                    ui.label("Второй цвет");
+                    use egui::color_picker::{color_edit_button_srgba, Alpha};
+                    color_edit_button_srgba(ui, &mut self.second_color, Alpha::Opaque);
+                });
+            }
 
             ui.vertical_centered_justified(|ui| {
                 ui.vertical_centered_justified(|ui| {
@@ -107,13 +156,44 @@ impl MyApp {
                     self.add_point();
                 }
             });
+            ui.separator();
+            ui.vertical_centered_justified(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.buf_rad1).hint_text("Полуось x: "));
+                ui.add(egui::TextEdit::singleline(&mut self.buf_rad2).hint_text("Полуось y: "));
+                ui.add(egui::TextEdit::singleline(&mut self.buf_arc_start).hint_text("Начальный угол: "));
+                ui.add(egui::TextEdit::singleline(&mut self.buf_arc_end).hint_text("Конечный угол: "));
+                ui.horizontal_wrapped(|ui| {
+                    ui.radio_value(&mut self.sector_kind, SectorKind::Pie, "Сектор");
+                    ui.radio_value(&mut self.sector_kind, SectorKind::Chord, "Сегмент");
+                });
+                if ui.button("Добавить сектор").clicked() {
+                    self.add_sector();
+                }
+            });
+            ui.separator();
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Сплайн");
+                ui.radio_value(&mut self.spline, None, "Нет");
+                ui.radio_value(&mut self.spline, Some(Kind::BSpline), "B-сплайн");
+                ui.radio_value(&mut self.spline, Some(Kind::CatmullRom), "Катмулл — Ром");
+            });
             self.update_table(ui);
             ui.vertical_centered_justified(|ui| {
                 if ui.button("Замкнуть фигуру").clicked() {
                     self.close_figure();
                 }
+                if self.spline.is_some() && ui.button("Замкнуть сплайном").clicked()
+                {
+                    self.close_spline();
+                }
                 ui.separator();
 
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Правило заливки");
+                    ui.radio_value(&mut self.fill_rule, Rule::EvenOdd, "Чётно-нечётное");
+                    ui.radio_value(&mut self.fill_rule, Rule::NonZero, "Ненулевое");
+                });
+                ui.checkbox(&mut self.antialiasing, "Сглаживание");
                 ui.horizontal_wrapped(|ui| {
                     ui.checkbox(&mut self.timeout, "Задержка");
                     if self.timeout {
@@ -136,7 +216,7 @@ impl MyApp {
 
                 ui.label(format!(
                     "Время заливки {:.5} сек.",
-                    self.dur_res.lock().unwrap().as_secs_f64()
+                    self.dur_res.as_secs_f64()
                 ));
             });
         });
@@ -294,7 +374,7 @@ impl MyApp {
                         to_screen.transform_pos(pos1.to_owned() * unit),
                         to_screen.transform_pos(pos2 * unit),
                     ],
-                    egui::Stroke::new(unit, self.stroke.clone()),
+                    egui::Stroke::new(unit, self.stroke),
                 ));
             }
         }
@@ -345,15 +425,15 @@ impl MyApp {
             }
         }
 
-        let filler = canvas.filler().iter().map(|(pos1, pos2)| {
-            let pos1 = pos1.clone();
-            let pos2 = pos2.clone();
-            egui::Shape::line_segment(
-                [
-                    to_screen.transform_pos(pos1 * unit),
-                    to_screen.transform_pos(pos2 * unit),
-                ],
-                egui::Stroke::new(unit, self.stroke.clone()),
+        let rects = canvas.filler().iter().flat_map(Piece::rects);
+        let filler = rects.map(|(min, max, color, i)| {
+            egui::Shape::rect_filled(
+                egui::Rect::from_min_max(
+                    to_screen.transform_pos(min * unit),
+                    to_screen.transform_pos(max * unit),
+                ),
+                0.0,
+                color.abate(self.background, i),
             )
         });
 
@@ -367,11 +447,20 @@ impl MyApp {
                     to_screen.transform_pos(p1 * unit),
                     to_screen.transform_pos(p2 * unit),
                 ],
-                egui::Stroke::new(unit, self.stroke.clone()),
+                egui::Stroke::new(unit, self.stroke),
             )
         });
+        let spline = self.open_spline(&canvas).map(|spline| {
+            let points = spline
+                .flatten(Flattening::Subdivision, SPLINE_TOLERANCE)
+                .into_iter()
+                .map(|p| to_screen.transform_pos(Pos2::from(p) * unit))
+                .collect();
+            egui::Shape::line(points, egui::Stroke::new(unit, egui::Color32::GRAY))
+        });
         painter.extend(filler);
         painter.extend(edges);
+        painter.extend(spline);
     }
 }
 
@@ -405,6 +494,92 @@ impl MyApp {
         }
     }
 
+    /// Сектор с центром в (X, Y) добавляется как замкнутый многоугольник.
+    fn add_sector(&mut self) {
+        if let (Ok(x), Ok(y), Ok(a), Ok(b), Ok(start), Ok(end)) = (
+            self.parse_field::<u32>(self.buf_x.clone()),
+            self.parse_field::<u32>(self.buf_y.clone()),
+            self.parse_field::<u32>(self.buf_rad1.clone()),
+            self.parse_field::<u32>(self.buf_rad2.clone()),
+            self.parse_field::<f32>(self.buf_arc_start.clone()),
+            self.parse_field::<f32>(self.buf_arc_end.clone()),
+        ) {
+            let sector = Sector::new(
+                [x as f32, y as f32].into(),
+                [a as f32, b as f32].into(),
+                ArcRange::new(start, end),
+                self.sector_kind,
+            );
+            let polygon = sector.polygon();
+            let vertices = polygon.vertices();
+            // последняя вершина повторяет первую, замыкает сам холст
+            let vertices: Vec<Pos2> = vertices[..vertices.len() - 1]
+                .iter()
+                .map(|&p| p.into())
+                .collect();
+            let mut canvas = self.canvas.lock().unwrap();
+            if vertices.len() < 3 {
+                self.error
+                    .set_error("Ошибка".to_string(), "Вырожденный сектор".to_string())
+                    .enable();
+            } else if canvas.add_polygon(&vertices).is_none() {
+                self.error
+                    .set_error("Ошибка".to_string(), "Фигура не замкнута!".to_string())
+                    .enable();
+            }
+        } else {
+            self.error.enable();
+        }
+    }
+
+    /// Незамкнутый сплайн через вершины текущей фигуры, если выбран сплайн.
+    fn open_spline(&self, canvas: &Canvas) -> Option<Spline> {
+        let points = canvas
+            .open_points()
+            .iter()
+            .map(|&p| p.into())
+            .collect::<Vec<_>>();
+        Spline::new(self.spline?, &points, false).ok()
+    }
+
+    /// Вершины текущей фигуры заменяются замкнутым сплайном через них; он
+    /// добавляется как многоугольник и заливается как обычная фигура.
+    fn close_spline(&mut self) {
+        let Some(kind) = self.spline else {
+            return;
+        };
+        let mut canvas = self.canvas.lock().unwrap();
+        let points = canvas
+            .open_points()
+            .iter()
+            .map(|&p| p.into())
+            .collect::<Vec<_>>();
+        let polygon = Spline::new(kind, &points, true)
+            .ok()
+            .and_then(|spline| spline.polygon(Flattening::Subdivision, SPLINE_TOLERANCE));
+        let Some(polygon) = polygon else {
+            self.error
+                .set_error(
+                    "Ошибка".to_string(),
+                    "Для замкнутого сплайна нужны хотя бы три точки".to_string(),
+                )
+                .enable();
+            return;
+        };
+        let vertices = polygon.vertices();
+        // последняя вершина повторяет первую, замыкает сам холст
+        let vertices: Vec<Pos2> = vertices[..vertices.len() - 1]
+            .iter()
+            .map(|&p| p.into())
+            .collect();
+        canvas.take_open();
+        if canvas.add_polygon(&vertices).is_none() {
+            self.error
+                .set_error("Ошибка".to_string(), "Фигура не замкнута!".to_string())
+                .enable();
+        }
+    }
+
     fn close_figure(&mut self) {
         let mut canvas = self.canvas.lock().unwrap();
         if canvas.points().len() - canvas.last_closed() > 2 {
@@ -436,24 +611,81 @@ impl MyApp {
 
     fn clear_figure(&mut self) {
         let mut canvas = self.canvas.lock().unwrap();
-        *self.dur_res.lock().unwrap() = Default::default();
+        self.dur_res = Default::default();
+        self.replay = None;
         canvas.clear();
     }
 
     fn clean_figure(&mut self) {
         let mut canvas = self.canvas.lock().unwrap();
-        *self.dur_res.lock().unwrap() = Default::default();
+        self.dur_res = Default::default();
+        self.replay = None;
         canvas.clean();
     }
 
+    /// Заливка считается сразу, время заливки — время только этого расчёта.
+    /// Цвет пикселей берётся из градиента или узора, растянутого на все
+    /// фигуры. Сглаженная отдаёт частично покрытые пиксели со степенью
+    /// покрытия, их цвет смешивается с фоном при рисовании. С задержкой куски
+    /// по строке за раз отдаёт отдельный поток, а холст забирает готовые в
+    /// `receive_pieces`.
     fn start_filling(&mut self, d: u64) {
-        let canvas = self.canvas.clone();
-        let mut dur = self.dur_res.clone();
+        let (contours, (min, max)) = {
+            let canvas = self.canvas.lock().unwrap();
+            (canvas.contours(), canvas.bounds())
+        };
+        let paint = Paint::new(
+            self.paint_kind,
+            self.stroke.into(),
+            self.second_color.into(),
+            min.into(),
+            max.into(),
+        );
+        let mut pieces = Pieces::new(paint);
+        let start = Instant::now();
+        if self.antialiasing {
+            fill::antialiased_to(&contours, self.fill_rule, &mut pieces);
+        } else {
+            fill::spans_to(&contours, self.fill_rule, &mut pieces);
+        }
+        self.dur_res = start.elapsed();
+        if d == 0 {
+            let mut canvas = self.canvas.lock().unwrap();
+            for row in pieces.into_rows() {
+                canvas.fill_row(row);
+            }
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        self.replay = Some(receiver);
         thread::spawn(move || {
-            Canvas::filling(&canvas, &mut dur, d);
+            for row in pieces.into_rows() {
+                thread::sleep(Duration::from_millis(d));
+                // заливку очистили, получателя больше нет
+                if sender.send(row).is_err() {
+                    break;
+                }
+            }
         });
     }
 
+    fn receive_pieces(&mut self) {
+        let Some(replay) = &self.replay else {
+            return;
+        };
+        let mut canvas = self.canvas.lock().unwrap();
+        loop {
+            match replay.try_recv() {
+                Ok(row) => canvas.fill_row(row),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.replay = None;
+                    break;
+                }
+            }
+        }
+    }
+
     fn fill_figure_run(&mut self) {
         if self.buf_dur.is_empty() || !self.timeout {
             self.start_filling(0);
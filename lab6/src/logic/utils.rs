@@ -1,112 +1,29 @@
 use eframe::egui::{Color32, Pos2};
-use std::collections::{HashMap, VecDeque as Stack};
+use raster::arc::Sector;
+use raster::circle::Circle;
+use raster::ellipse::{Ellipse, RotatedEllipse};
+use raster::line::dda;
+use raster::paint::Paint;
+use raster::point;
+use std::collections::{HashMap, HashSet, VecDeque as Stack};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 static CANVAS_WIDTH: u32 = 1500;
 static CANVAS_HEIGHT: u32 = 1024;
 
-pub fn plot_circle_pixels(x: f32, y: f32, x_c: f32, y_c: f32, pixels: &mut Vec<Pos2>) {
-    let (mut sx, mut sy) = (1.0, 1.0);
-    for _ in 0..4 {
-        let xsx = x * sx;
-        let ysy = y * sy;
-        pixels.push(Pos2::new(x_c + xsx, y_c + ysy));
-        pixels.push(Pos2::new(x_c + ysy, y_c + xsx));
-        sx *= -1.0;
-        sy *= -1.0 * sx;
-    }
-}
-
-pub fn plot_ellipse_pixels(x: f32, y: f32, x_c: f32, y_c: f32, pixels: &mut Vec<Pos2>) {
-    let (mut sx, mut sy) = (1.0, 1.0);
-    for _ in 0..4 {
-        let xsx = x * sx;
-        let ysy = y * sy;
-        pixels.push(Pos2::new(x_c + xsx, y_c + ysy));
-        sx *= -1.0;
-        sy *= -1.0 * sx;
-    }
-}
-
-
-pub fn draw_circle(center: Pos2, radius: f32) -> Vec<Pos2> {
-    let mut pixels = vec![];
-    let (x_c, y_c) = (center.x, center.y);
-    let sqr_radius = (radius * radius) as u32;
-    let x_range = radius / std::f32::consts::SQRT_2 + 1.0;
-    let mut x = 0;
-    while x as f32 <= x_range {
-        let y: f32 = ((sqr_radius - x * x) as f32).sqrt();
-        plot_circle_pixels(x as f32, y.round(), x_c, y_c, &mut pixels);
-        x += 1;
-    }
-    pixels
-}
-
-pub fn draw_ellipse(center: Pos2, radius: Pos2) -> Vec<Pos2> {
-    let mut pixels = vec![];
-    let (x_c, y_c) = (center.x, center.y);
-    let sqr_a = radius.x * radius.x;
-    let sqr_b = radius.y * radius.y;
-    let sqrt_coeff = radius.y / radius.x;
-    let hyp = (sqr_a + sqr_b).sqrt();
-    let x_range = sqr_a / hyp + 1.0;
-    let mut x = 0.0;
-    while x <= x_range {
-        let y = sqrt_coeff * (sqr_a - x * x).sqrt();
-        plot_ellipse_pixels(x, y.round(), x_c, y_c, &mut pixels);
-        x += 1.0;
-    }
-
-    let sqrt_coeff = sqrt_coeff.recip();
-    let y_range = sqr_b / hyp + 1.0;
-    let mut y = 0.0;
-    while y <= y_range {
-        let x = sqrt_coeff * (sqr_b - y * y).sqrt();
-        plot_ellipse_pixels(x.round(), y, x_c, y_c, &mut pixels);
-        y += 1.0;
-    }
-
-    pixels
-}
-
-
-pub fn dda(points: [Pos2; 2]) -> Vec<Pos2> {
-    if points[0] == points[1] {
-        return [points[0]].into();
-    }
-    let mut res = Vec::new();
-    let (x1, y1) = (points[0].x, points[0].y);
-    let (x2, y2) = (points[1].x, points[1].y);
-
-    let dx = x2 - x1;
-    let dy = y2 - y1;
-    let abs_x = dx.abs();
-    let abs_y = dy.abs();
-    let l = if abs_x >= abs_y { abs_x } else { abs_y };
-
-    let dx = dx / l;
-    let dy = dy / l;
-
-    let mut x = x1;
-    let mut y = y1;
-    for _ in 0..l as i32 {
-        res.push(Pos2::new(x.round(), y.round()));
-        x += dx;
-        y += dy;
-    }
-    res
-}
-
 #[derive(Debug)]
 pub struct Canvas {
     pub strings: Vec<((Pos2, Pos2), Color32)>,
     points: Vec<(Pos2, Color32)>,
     pub circles: Vec<(Pos2, f32, Color32)>,
-    pub ellipse: Vec<(Pos2, Pos2, Color32)>,
+    pub ellipse: Vec<(Pos2, Pos2, f32, Color32)>,
+    pub sectors: Vec<(Vec<Pos2>, Color32)>,
     pixels_fill: HashMap<(u32, u32), (u8, u8, u8)>,
     pub pixels_edges: HashMap<(u32, u32), (u8, u8, u8)>,
+    /// Пиксели, залитые текущей заливкой: цвет у них может быть любым, так
+    /// что по цвету их не отличить.
+    filled: HashSet<(u32, u32)>,
     close: Vec<usize>,
     pub background: Color32,
 
@@ -120,8 +37,10 @@ impl Canvas {
             points: vec![],
             circles: vec![],
             ellipse: vec![],
+            sectors: vec![],
             pixels_fill: Default::default(),
             pixels_edges: Default::default(),
+            filled: Default::default(),
             bebra: vec![],
             close: vec![0],
             background: Color32::WHITE,
@@ -130,7 +49,7 @@ impl Canvas {
 
     pub fn last_closed_point(&self) -> Option<&Pos2> {
         let len = self.close.last()?;
-        if self.points.len() > len.to_owned() {
+        if self.points.len() > *len {
             self.last_point()
         } else {
             None
@@ -153,32 +72,52 @@ impl Canvas {
     pub fn add_point(&mut self, pos2: Pos2, color32: Color32) {
         if self.points.len() - self.close.last().unwrap() >= 1 {
             let pos1 = self.points[self.points.len() - 1].0;
-            let pos2 = pos2;
-            for (&i, c) in dda([pos1, pos2]).iter().map(|x| (x, color32)) {
+            for (i, c) in dda(&[pos1.into(), pos2.into()]).into_iter().map(|x| (x, color32)) {
                 let (r, g, b, _) = c.to_tuple();
                 self.pixels_edges.insert((i.x as u32, i.y as u32), (r, g, b));
-                self.bebra.push((i, c));
+                self.bebra.push((i.into(), c));
             }
         }
         self.points.push((pos2, color32));
     }
 
     pub fn add_circle(&mut self, pos2: Pos2, r: f32, color32: Color32) {
-        for (&i, c) in draw_circle(pos2, r).iter().map(|x| (x, color32)) {
+        for (i, c) in Circle::new(pos2.into(), r).draw_canonic().into_iter().map(|x| (x, color32)) {
             let (r, g, b, _) = c.to_tuple();
             self.pixels_edges.insert((i.x as u32, i.y as u32), (r, g, b));
-            self.bebra.push((i, c));
+            self.bebra.push((i.into(), c));
         }
         self.circles.push((pos2, r, color32));
     }
 
-    pub fn add_ellipse(&mut self, pos1: Pos2, pos2: Pos2, color32: Color32) {
-        for (&i, c) in draw_ellipse(pos1, pos2).iter().map(|x| (x, color32)) {
+    /// Эллипс с центром `pos1` и полуосями `pos2`, повёрнутый на `angle` градусов.
+    pub fn add_ellipse(&mut self, pos1: Pos2, pos2: Pos2, angle: f32, color32: Color32) {
+        let pixels = if angle == 0.0 {
+            Ellipse::new(pos1.into(), pos2.into()).draw_canonic()
+        } else {
+            RotatedEllipse::new(pos1.into(), pos2.into(), angle).draw_midpoint()
+        };
+        for (i, c) in pixels.into_iter().map(|x| (x, color32)) {
+            let (r, g, b, _) = c.to_tuple();
+            self.pixels_edges.insert((i.x as u32, i.y as u32), (r, g, b));
+            self.bebra.push((i.into(), c));
+        }
+        self.ellipse.push((pos1, pos2, angle, color32));
+    }
+
+    /// Граница сектора: дуга по Брезенхему и радиусы или хорда.
+    pub fn add_sector(&mut self, sector: &Sector, color32: Color32) {
+        for (i, c) in sector
+            .border(sector.ellipse().draw_bresenham())
+            .into_iter()
+            .map(|x| (x, color32))
+        {
             let (r, g, b, _) = c.to_tuple();
             self.pixels_edges.insert((i.x as u32, i.y as u32), (r, g, b));
-            self.bebra.push((i, c));
+            self.bebra.push((i.into(), c));
         }
-        self.ellipse.push((pos1, pos2, color32));
+        let outline = sector.polygon().vertices().iter().map(|&p| p.into()).collect();
+        self.sectors.push((outline, color32));
     }
 
     pub fn clear(&mut self) {
@@ -189,13 +128,24 @@ impl Canvas {
         self.bebra.clear();
         self.pixels_fill.clear();
         self.pixels_edges.clear();
+        self.filled.clear();
         self.circles.clear();
         self.ellipse.clear();
+        self.sectors.clear();
     }
 
     pub fn clean(&mut self) {
         self.strings.clear();
         self.pixels_fill.clear();
+        self.filled.clear();
+    }
+
+    /// Прямоугольник, охватывающий все нарисованные границы.
+    pub fn bounds(&self) -> Option<(Pos2, Pos2)> {
+        let (xs, ys): (Vec<_>, Vec<_>) = self.pixels_edges.keys().copied().unzip();
+        let min = Pos2::new(*xs.iter().min()? as f32, *ys.iter().min()? as f32);
+        let max = Pos2::new(*xs.iter().max()? as f32, *ys.iter().max()? as f32);
+        Some((min, max))
     }
 
     pub fn close(&mut self) -> Option<()> {
@@ -220,18 +170,35 @@ impl Canvas {
         Color32::from_rgb(r, g, b)
     }
 
-    pub fn draw_line(&mut self, pos1: (u32, u32), pos2: (u32, u32), color32: Color32) {
-        let (r, g, b, _) = color32.to_tuple();
+    /// Цвет каждого пикселя берётся из `paint`; одноцветная строка рисуется
+    /// одним отрезком.
+    pub fn draw_line(&mut self, pos1: (u32, u32), pos2: (u32, u32), paint: &Paint) {
+        let y = pos2.1;
         for i in pos1.0..=pos2.0 {
-            self.pixels_fill.insert((i, pos2.1), (r, g, b));
+            let color32: Color32 = paint.at(point(i as f32, y as f32)).into();
+            let (r, g, b, _) = color32.to_tuple();
+            self.pixels_fill.insert((i, y), (r, g, b));
+            self.filled.insert((i, y));
+            if paint.solid().is_none() {
+                self.strings.push((
+                    (Pos2::new(i as f32 - 0.5, y as f32), Pos2::new(i as f32 + 0.5, y as f32)),
+                    color32,
+                ));
+            }
+        }
+        if let Some(color) = paint.solid() {
+            self.strings.push((
+                (
+                    Pos2::new(pos1.0 as f32 - 1., pos1.1 as f32),
+                    Pos2::new(pos2.0 as f32 + 1., pos2.1 as f32),
+                ),
+                color.into(),
+            ))
         }
-        self.strings.push((
-            (
-                Pos2::new(pos1.0 as f32 - 1., pos1.1 as f32),
-                Pos2::new(pos2.0 as f32 + 1., pos2.1 as f32),
-            ),
-            color32,
-        ))
+    }
+
+    pub fn is_filled(&self, x: u32, y: u32) -> bool {
+        self.filled.contains(&(x, y))
     }
 
     pub fn eq_color(&self, x: u32, y: u32, color32: Color32) -> bool {
@@ -242,18 +209,19 @@ impl Canvas {
     pub fn filling(
         seed: Pos2,
         canvas: &Arc<Mutex<Self>>,
-        fill: Color32,
+        fill: Paint,
         border: Color32,
         dur: &mut Arc<Mutex<std::time::Duration>>,
         delay: u64,
         rec: bool,
     ) {
         let (x, y) = (seed.x as u32, seed.y as u32);
+        canvas.lock().unwrap().filled.clear();
         if rec {
             let start = std::time::Instant::now();
-            fill_recursive(x, y, canvas, fill, border, dur, delay, start);
+            fill_recursive(x, y, canvas, &fill, border, dur, delay, start);
         } else {
-            filling_ordinary(x, y, canvas, fill, border, dur, delay);
+            filling_ordinary(x, y, canvas, &fill, border, dur, delay);
         }
     }
 }
@@ -261,7 +229,7 @@ impl Canvas {
 pub fn filling_ordinary(
     x: u32, y: u32,
     canvas: &Arc<Mutex<Canvas>>,
-    fill: Color32,
+    fill: &Paint,
     border: Color32,
     dur: &mut Arc<Mutex<std::time::Duration>>,
     delay: u64,
@@ -274,7 +242,7 @@ pub fn filling_ordinary(
         let right_x = (x + 1..=CANVAS_WIDTH)
             .take_while(|&tmp_x| {
                 !canvas.lock().unwrap().eq_color(tmp_x, y, border)
-                    && !canvas.lock().unwrap().eq_color(tmp_x, y, fill)
+                    && !canvas.lock().unwrap().is_filled(tmp_x, y)
             })
             .last()
             .unwrap_or(x);
@@ -283,7 +251,7 @@ pub fn filling_ordinary(
             .rev()
             .take_while(|&tmp_x| {
                 !canvas.lock().unwrap().eq_color(tmp_x, y, border)
-                    && !canvas.lock().unwrap().eq_color(tmp_x, y, fill)
+                    && !canvas.lock().unwrap().is_filled(tmp_x, y)
             })
             .last()
             .map_or(x, |tmp_x| tmp_x);
@@ -295,21 +263,20 @@ pub fn filling_ordinary(
             let mut tmp_x = left_x;
             while tmp_x <= right_x {
                 let mut flag = false;
-                while tmp_x <= right_x && !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().eq_color(tmp_x, y, fill) {
+                while tmp_x <= right_x && !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().is_filled(tmp_x, y) {
                     flag = true;
                     tmp_x += 1;
                 }
                 if flag && y < CANVAS_HEIGHT - 1 && y > 0 {
-                    let x = if tmp_x <= right_x && !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().eq_color(tmp_x, y, fill) {
+                    let x = if tmp_x <= right_x && !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().is_filled(tmp_x, y) {
                         tmp_x
                     } else {
                         tmp_x - 1
                     };
                     stack.push_back((x, y));
-                    flag = false;
                 }
                 let begin_x = tmp_x;
-                while tmp_x <= right_x && (canvas.lock().unwrap().eq_color(tmp_x, y, border) || canvas.lock().unwrap().eq_color(tmp_x, y, fill)) {
+                while tmp_x <= right_x && (canvas.lock().unwrap().eq_color(tmp_x, y, border) || canvas.lock().unwrap().is_filled(tmp_x, y)) {
                     tmp_x += 1;
                 }
                 if tmp_x == begin_x {
@@ -320,20 +287,20 @@ pub fn filling_ordinary(
     }
 }
 
-pub fn fill_recursive(x: u32, y: u32, canvas: &Arc<Mutex<Canvas>>, fill: Color32, border: Color32, dur: &mut Arc<Mutex<std::time::Duration>>,
+pub fn fill_recursive(x: u32, y: u32, canvas: &Arc<Mutex<Canvas>>, fill: &Paint, border: Color32, dur: &mut Arc<Mutex<std::time::Duration>>,
                       delay: u64, start: Instant) {
-    if canvas.lock().unwrap().eq_color(x, y, border) || canvas.lock().unwrap().eq_color(x, y, fill) {
+    if canvas.lock().unwrap().eq_color(x, y, border) || canvas.lock().unwrap().is_filled(x, y) {
         return;
     }
 
     let right_x = (x + 1..=CANVAS_WIDTH)
-        .take_while(|&tmp_x| !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().eq_color(tmp_x, y, fill))
+        .take_while(|&tmp_x| !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().is_filled(tmp_x, y))
         .last()
         .unwrap_or(x);
 
     let left_x = (0..=x)
         .rev()
-        .take_while(|&tmp_x| !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().eq_color(tmp_x, y, fill))
+        .take_while(|&tmp_x| !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().is_filled(tmp_x, y))
         .last()
         .map_or(x, |tmp_x| tmp_x + 1);
 
@@ -344,12 +311,12 @@ pub fn fill_recursive(x: u32, y: u32, canvas: &Arc<Mutex<Canvas>>, fill: Color32
         let mut tmp_x = left_x;
         while tmp_x <= right_x {
             let mut flag = false;
-            while tmp_x <= right_x && !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().eq_color(tmp_x, y, fill) {
+            while tmp_x <= right_x && !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().is_filled(tmp_x, y) {
                 flag = true;
                 tmp_x += 1;
             }
             if flag && y < CANVAS_HEIGHT - 1 && y > 0 {
-                let x = if tmp_x <= right_x && !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().eq_color(tmp_x, y, fill) {
+                let x = if tmp_x <= right_x && !canvas.lock().unwrap().eq_color(tmp_x, y, border) && !canvas.lock().unwrap().is_filled(tmp_x, y) {
                     tmp_x
                 } else {
                     tmp_x - 1
@@ -357,7 +324,7 @@ pub fn fill_recursive(x: u32, y: u32, canvas: &Arc<Mutex<Canvas>>, fill: Color32
                 fill_recursive(x, y, canvas, fill, border, dur, delay, start);
             }
             let begin_x = tmp_x;
-            while tmp_x <= right_x && (canvas.lock().unwrap().eq_color(tmp_x, y, border) || canvas.lock().unwrap().eq_color(tmp_x, y, fill)) {
+            while tmp_x <= right_x && (canvas.lock().unwrap().eq_color(tmp_x, y, border) || canvas.lock().unwrap().is_filled(tmp_x, y)) {
                 tmp_x += 1;
             }
             if tmp_x == begin_x {
@@ -7,6 +7,9 @@ use epaint::{Stroke,
              vec2};
 use logic::utils::Canvas;
 use logic::windows::ErrorWindow;
+use raster::arc::{ArcRange, Sector, SectorKind};
+use raster::ellipse::rotated_ellipse_path;
+use raster::paint::{self, Paint};
 use std::{
     sync::{Arc, Mutex},
     thread,
@@ -17,12 +20,15 @@ enum DrawMode {
     Line,
     Ellipse,
     Circle,
+    Sector,
 }
 
 #[derive(Debug)]
 struct MyApp {
     background: egui::Color32,
     stroke: egui::Color32,
+    second_color: egui::Color32,
+    paint_kind: paint::Kind,
     border_color: egui::Color32,
     error: ErrorWindow,
     canvas: Arc<Mutex<Canvas>>,
@@ -42,6 +48,10 @@ struct MyApp {
 
     buf_rad1: String,
     buf_rad2: String,
+    buf_angle: String,
+    buf_arc_start: String,
+    buf_arc_end: String,
+    sector_kind: SectorKind,
 
     buf_ellipse: (Option<Pos2>, Option<Pos2>),
     buf_circle: (Option<Pos2>, Option<Pos2>),
@@ -54,6 +64,8 @@ impl Default for MyApp {
             error: Default::default(),
             background: egui::Color32::WHITE,
             stroke: egui::Color32::RED,
+            second_color: egui::Color32::BLUE,
+            paint_kind: paint::Kind::Solid,
             border_color: egui::Color32::BLACK,
             canvas: Arc::new(Mutex::new(Canvas::new())),
             buf_x: "".to_string(),
@@ -69,6 +81,10 @@ impl Default for MyApp {
 
             buf_rad1: Default::default(),
             buf_rad2: Default::default(),
+            buf_angle: Default::default(),
+            buf_arc_start: Default::default(),
+            buf_arc_end: Default::default(),
+            sector_kind: SectorKind::Pie,
             buf_ellipse: (None, None),
             buf_circle: (None, None),
             bibl: false
@@ -118,6 +134,20 @@ impl MyApp {
 
                 color_edit_button_srgba(ui, &mut self.stroke, Alpha::Opaque);
             });
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Заливка");
+                ui.radio_value(&mut self.paint_kind, paint::Kind::Solid, "Сплошная");
+                ui.radio_value(&mut self.paint_kind, paint::Kind::Linear, "Линейный градиент");
+                ui.radio_value(&mut self.paint_kind, paint::Kind::Radial, "Радиальный градиент");
+                ui.radio_value(&mut self.paint_kind, paint::Kind::Pattern, "Узор");
+            });
+            if self.paint_kind != paint::Kind::Solid {
+                ui.horizontal(|ui| {
+                    ui.label("Второй цвет");
+                    use egui::color_picker::{color_edit_button_srgba, Alpha};
+                    color_edit_button_srgba(ui, &mut self.second_color, Alpha::Opaque);
+                });
+            }
 
             ui.separator();
             ui.horizontal(|ui| {
@@ -130,6 +160,7 @@ impl MyApp {
                 ui.radio_value(&mut self.mode, DrawMode::Line, "Ломанная");
                 ui.radio_value(&mut self.mode, DrawMode::Circle, "Окружность");
                 ui.radio_value(&mut self.mode, DrawMode::Ellipse, "Эллипс");
+                ui.radio_value(&mut self.mode, DrawMode::Sector, "Сектор");
             });
 
             ui.vertical_centered_justified(|ui| {
@@ -143,6 +174,18 @@ impl MyApp {
                     if self.mode == DrawMode::Ellipse {
                         ui.add(egui::TextEdit::singleline(&mut self.buf_rad1).hint_text("Полуось x: "));
                         ui.add(egui::TextEdit::singleline(&mut self.buf_rad2).hint_text("Полуось y: "));
+                        ui.add(egui::TextEdit::singleline(&mut self.buf_angle).hint_text("Угол: "));
+                    }
+
+                    if self.mode == DrawMode::Sector {
+                        ui.add(egui::TextEdit::singleline(&mut self.buf_rad1).hint_text("Полуось x: "));
+                        ui.add(egui::TextEdit::singleline(&mut self.buf_rad2).hint_text("Полуось y: "));
+                        ui.add(egui::TextEdit::singleline(&mut self.buf_arc_start).hint_text("Начальный угол: "));
+                        ui.add(egui::TextEdit::singleline(&mut self.buf_arc_end).hint_text("Конечный угол: "));
+                        ui.horizontal_wrapped(|ui| {
+                            ui.radio_value(&mut self.sector_kind, SectorKind::Pie, "Сектор");
+                            ui.radio_value(&mut self.sector_kind, SectorKind::Chord, "Сегмент");
+                        });
                     }
                 });
                 match self.mode {
@@ -161,6 +204,11 @@ impl MyApp {
                             self.add_ellipse();
                         }
                     }
+                    DrawMode::Sector => {
+                        if ui.button("Добавить Сектор").clicked() {
+                            self.add_sector();
+                        }
+                    }
                 }
             });
             self.update_table(ui);
@@ -313,11 +361,13 @@ impl MyApp {
                     if let Some(c) = self.buf_ellipse.0 {
                         let dx = (c.x - pos1.x).abs();
                         let dy = (c.y - pos1.y).abs();
-                        painter.add(
-                            egui::Shape::ellipse_stroke(c * unit, vec2(dx, dy) * unit, Stroke::new(unit, self.border_color))
-                        );
+                        painter.add(egui::Shape::closed_line(
+                            rotated_ellipse_path(c * unit, vec2(dx, dy) * unit, self.mouse_angle()),
+                            Stroke::new(unit, self.border_color),
+                        ));
                     }
                 }
+                DrawMode::Sector => {}
                 DrawMode::Circle => {
                     if let Some(x) = self.buf_circle.0 {
                         let dx = std::cmp::max((x.x - pos1.x).abs() as u32, (x.y - pos1.y).abs() as u32);
@@ -355,11 +405,12 @@ impl MyApp {
                         self.buf_ellipse = (None, None);
                         let dx = (x.x - pos1.x).abs();
                         let dy = (x.y - pos1.y).abs();
-                        canvas.add_ellipse(x, [dx, dy].into(), self.border_color);
+                        canvas.add_ellipse(x, [dx, dy].into(), self.mouse_angle(), self.border_color);
                     } else {
                         self.buf_ellipse.0 = Some(pos1);
                     }
                 }
+                DrawMode::Sector => {}
                 DrawMode::Circle => {
                     if let Some(x) = self.buf_circle.0 {
                         self.buf_circle = (None, None);
@@ -412,8 +463,19 @@ impl MyApp {
             });
             painter.extend(shapes);
 
-            let shapes = canvas.ellipse.iter().map(|&(a, b, c)| {
-                egui::Shape::ellipse_stroke(a * unit, b.to_vec2() * unit, Stroke::new(unit, c))
+            let shapes = canvas.ellipse.iter().map(|&(a, b, angle, c)| {
+                egui::Shape::closed_line(
+                    rotated_ellipse_path(a * unit, b.to_vec2() * unit, angle),
+                    Stroke::new(unit, c),
+                )
+            });
+            painter.extend(shapes);
+
+            let shapes = canvas.sectors.iter().map(|(outline, c)| {
+                egui::Shape::line(
+                    outline.iter().map(|&p| p * unit).collect(),
+                    Stroke::new(unit, *c),
+                )
             });
             painter.extend(shapes);
         } else {
@@ -464,15 +526,51 @@ impl MyApp {
         }
     }
 
+    /// Угол эллипса, рисуемого мышью: пустое или ошибочное поле — без поворота.
+    fn mouse_angle(&self) -> f32 {
+        self.buf_angle.trim().parse().unwrap_or(0.0)
+    }
+
     fn add_ellipse(&mut self) {
-        if let (Ok(x), Ok(y), Ok(r1), Ok(r2)) = (
+        let angle = if self.buf_angle.trim().is_empty() {
+            Ok(0.0)
+        } else {
+            self.parse_field::<f32>(self.buf_angle.trim().to_owned())
+        };
+        if let (Ok(x), Ok(y), Ok(r1), Ok(r2), Ok(angle)) = (
             self.parse_field::<u32>(self.buf_x.clone()),
             self.parse_field::<u32>(self.buf_y.clone()),
             self.parse_field::<u32>(self.buf_rad1.clone()),
             self.parse_field::<u32>(self.buf_rad2.clone()),
+            angle,
         ) {
             let mut canvas = self.canvas.lock().unwrap();
-            canvas.add_ellipse([x as f32, y as f32].into(), [r1 as f32, r2 as f32].into(), self.border_color);
+            canvas.add_ellipse([x as f32, y as f32].into(), [r1 as f32, r2 as f32].into(), angle, self.border_color);
+        } else {
+            self.error.enable();
+        }
+    }
+
+    /// Сектор с центром в (X, Y); затравка сразу ставится внутрь него.
+    fn add_sector(&mut self) {
+        if let (Ok(x), Ok(y), Ok(a), Ok(b), Ok(start), Ok(end)) = (
+            self.parse_field::<u32>(self.buf_x.clone()),
+            self.parse_field::<u32>(self.buf_y.clone()),
+            self.parse_field::<u32>(self.buf_rad1.clone()),
+            self.parse_field::<u32>(self.buf_rad2.clone()),
+            self.parse_field::<f32>(self.buf_arc_start.clone()),
+            self.parse_field::<f32>(self.buf_arc_end.clone()),
+        ) {
+            let sector = Sector::new(
+                [x as f32, y as f32].into(),
+                [a as f32, b as f32].into(),
+                ArcRange::new(start, end),
+                self.sector_kind,
+            );
+            self.canvas.lock().unwrap().add_sector(&sector, self.border_color);
+            let border = sector.border(sector.ellipse().draw_bresenham());
+            let seed = sector.seed(&border);
+            self.set_seed_pos(seed.x as u32, seed.y as u32);
         } else {
             self.error.enable();
         }
@@ -525,9 +623,17 @@ impl MyApp {
         if let Some(seed) = self.seed {
             let canvas = self.canvas.clone();
             let mut dur = self.dur_res.clone();
-            let fill = self.stroke.clone();
-            let border = self.border_color.clone();
-            let rec = self.recursive.clone();
+            // градиент или узор растягивается на все нарисованные границы
+            let (min, max) = canvas.lock().unwrap().bounds().unwrap_or((seed, seed));
+            let fill = Paint::new(
+                self.paint_kind,
+                self.stroke.into(),
+                self.second_color.into(),
+                min.into(),
+                max.into(),
+            );
+            let border = self.border_color;
+            let rec = self.recursive;
 
             thread::spawn(move || {
                 Canvas::filling(seed, &canvas, fill, border, &mut dur, d, rec);
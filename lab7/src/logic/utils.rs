@@ -1,4 +1,4 @@
-use eframe::egui::{Color32, Pos2};
+use eframe::egui::Pos2;
 
 fn get_code(rect: (Pos2, Pos2), a: Pos2) -> u8 {
     let (left, right) = rect;
@@ -59,7 +59,7 @@ fn find_r_point(start: Pos2, end: Pos2, cutter_l: Pos2, cutter_r: Pos2) -> Optio
 }
 
 
-pub fn cut(rect: (Pos2, Pos2), lines: &Vec<(Pos2, Pos2)>) -> Vec<(Pos2, Pos2)> {
+pub fn cut(rect: (Pos2, Pos2), lines: &[(Pos2, Pos2)]) -> Vec<(Pos2, Pos2)> {
     let (cutter_l, cutter_r) = rect;
     lines.iter().filter_map(|&(p1, p2)| {
         let (code1, code2) = (get_code(rect, p1), get_code(rect, p2));
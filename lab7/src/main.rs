@@ -2,14 +2,9 @@ mod logic;
 
 use eframe::egui;
 use eframe::egui::{Color32, Pos2};
-use egui_extras::{Column, TableBuilder};
-use epaint::{pos2, Stroke, vec2};
+use epaint::pos2;
 use logic::utils::cut;
 use logic::windows::ErrorWindow;
-use std::{
-    sync::{Arc, Mutex},
-    thread,
-};
 
 
 #[derive(Debug)]
@@ -255,7 +250,7 @@ impl MyApp {
                 painter.rect_stroke(
                     [left, pos].into(),
                     1.0, egui::Stroke::new(unit, self.cutter_color));
-            } else if let (Some(mut left), None) = self.buf_line {
+            } else if let (Some(left), None) = self.buf_line {
                 let mut pos = pos1 * unit;
                 if ui.input(|ui| ui.modifiers.matches_logically(egui::Modifiers::SHIFT)) {
                     let dx = (left.x - pos.x).abs();
@@ -267,12 +262,12 @@ impl MyApp {
                     }
                 }
                 painter.line_segment(
-                    [left, pos].into(),
+                    [left, pos],
                     egui::Stroke::new(unit, self.line_color));
             }
         }
 
-        if let (Some(mut left), Some(mut right)) = self.cutter {
+        if let (Some(left), Some(right)) = self.cutter {
             painter.rect_stroke(
                 [left, right].into(),
                 1.0, egui::Stroke::new(unit, self.cutter_color));
@@ -315,7 +310,7 @@ impl MyApp {
             if x1 > x2 {
                 std::mem::swap(&mut x1, &mut x2);
             }
-            if y1 > y1 {
+            if y1 > y2 {
                 std::mem::swap(&mut y1, &mut y2);
             }
             self.cutter = (Some(pos2(x1 as f32, y1 as f32)), Some(pos2(x2 as f32, y2 as f32)));
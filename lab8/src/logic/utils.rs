@@ -1,57 +1,5 @@
 use eframe::egui::{Pos2, pos2, vec2, Vec2};
 
-#[derive(Debug, Default)]
-pub struct Polygon {
-    vertices: Vec<Pos2>,
-    closed: bool,
-}
-
-impl Polygon {
-    pub fn push(&mut self, pos2: Pos2) -> &mut Self {
-        if let Some(&x) = self.vertices.last() {
-            if x == pos2 {
-                return self;
-            }
-        }
-        self.vertices.push(pos2);
-        self
-    }
-
-    pub fn vertices(&mut self) -> &[Pos2] {
-        &self.vertices
-    }
-
-    pub fn last(&mut self) -> Option<&Pos2> {
-        self.vertices.last()
-    }
-
-    pub fn clear(&mut self) -> &mut Self {
-        self.vertices.clear();
-        self.open();
-        self.closed = false;
-        self
-    }
-
-    pub fn closed(&self) -> bool {
-        self.closed
-    }
-
-    pub fn close(&mut self) -> &mut Self {
-        if self.vertices.len() < 3 || self.closed == true {
-            return self;
-        }
-        self.vertices.push(self.vertices[0]);
-        self.closed = true;
-        self
-    }
-
-    pub fn open(&mut self) -> &mut Self {
-        self.vertices.clear();
-        self.closed = false;
-        self
-    }
-}
-
 fn scalar(a: Vec2, b: Vec2) -> f32 {
     a.x * b.x + a.y * b.y
 }
@@ -3,7 +3,8 @@ mod logic;
 use eframe::egui;
 use eframe::egui::{Color32, Pos2};
 use epaint::{pos2};
-use logic::utils::{cut, Polygon};
+use logic::utils::cut;
+use raster::polygon::Polygon;
 use logic::windows::ErrorWindow;
 
 fn angle(a: Pos2, b: Pos2, c: Pos2, d: Pos2) -> f32 {
@@ -56,7 +57,7 @@ struct MyApp {
     buf_x2: String,
     buf_y2: String,
 
-    cutter: Polygon,
+    cutter: Polygon<Pos2>,
     buf_line: (Option<Pos2>, Option<Pos2>),
     cut_lines: Vec<(Pos2, Pos2)>,
     lines: Vec<(Pos2, Pos2)>,
@@ -238,7 +239,7 @@ impl MyApp {
                 }
             }
 
-            self.cutter.push(pos1);
+            self.cutter.push_distinct(pos1);
         }
 
         if response.clicked_by(egui::PointerButton::Secondary) {
@@ -304,47 +305,44 @@ impl MyApp {
                                 }
                             }
                             painter.line_segment(
-                                [last, pos1].into(),
+                                [last, pos1],
                                 egui::Stroke::new(unit, self.cutter_color),
                             );
                         }
                     }
                 }
                 State::LINE => {
-                    match self.buf_line {
-                        (Some(left), None) => {
-                            if ui.input(|ui| ui.modifiers.matches_logically(egui::Modifiers::SHIFT)) {
-                                let dx = (left.x - pos1.x).abs();
-                                let dy = (left.y - pos1.y).abs();
-                                if dy > dx {
-                                    pos1.x = left.x;
-                                } else {
-                                    pos1.y = left.y;
-                                }
+                    if let (Some(left), None) = self.buf_line {
+                        if ui.input(|ui| ui.modifiers.matches_logically(egui::Modifiers::SHIFT)) {
+                            let dx = (left.x - pos1.x).abs();
+                            let dy = (left.y - pos1.y).abs();
+                            if dy > dx {
+                                pos1.x = left.x;
+                            } else {
+                                pos1.y = left.y;
                             }
-                            if ui.input(|ui| ui.modifiers.matches_logically(egui::Modifiers::CTRL)) {
-                                if let Some([e0, e1]) = self.cutter.vertices().windows(2).min_by_key(|edge| {
-                                    angle(edge[0], edge[1], left, pos1).to_bits()
-                                }) {
-                                    let edge_dx = e1.x - e0.x;
-                                    let edge_dy = e1.y - e0.y;
+                        }
+                        if ui.input(|ui| ui.modifiers.matches_logically(egui::Modifiers::CTRL)) {
+                            if let Some([e0, e1]) = self.cutter.vertices().windows(2).min_by_key(|edge| {
+                                angle(edge[0], edge[1], left, pos1).to_bits()
+                            }) {
+                                let edge_dx = e1.x - e0.x;
+                                let edge_dy = e1.y - e0.y;
 
-                                    let line_dx = pos1.x - left.x;
-                                    let line_dy = pos1.y - left.y;
+                                let line_dx = pos1.x - left.x;
+                                let line_dy = pos1.y - left.y;
 
-                                    let edge_length = (edge_dx * edge_dx + edge_dy * edge_dy).sqrt();
-                                    let projection_length = (line_dx * edge_dx + line_dy * edge_dy) / edge_length;
+                                let edge_length = (edge_dx * edge_dx + edge_dy * edge_dy).sqrt();
+                                let projection_length = (line_dx * edge_dx + line_dy * edge_dy) / edge_length;
 
-                                    pos1.x = left.x + (projection_length * edge_dx) / edge_length;
-                                    pos1.y = left.y + (projection_length * edge_dy) / edge_length;
-                                }
+                                pos1.x = left.x + (projection_length * edge_dx) / edge_length;
+                                pos1.y = left.y + (projection_length * edge_dy) / edge_length;
                             }
-                            painter.line_segment(
-                                [left, pos1].into(),
-                                egui::Stroke::new(unit, self.line_color),
-                            );
                         }
-                        _ => {}
+                        painter.line_segment(
+                            [left, pos1],
+                            egui::Stroke::new(unit, self.line_color),
+                        );
                     }
                 }
             }
@@ -376,8 +374,8 @@ impl MyApp {
         if self.cutter.closed() {
             painter.line_segment(
                 [
-                    self.cutter.vertices().last().unwrap().clone(),
-                    self.cutter.vertices().first().unwrap().clone(),
+                    *self.cutter.vertices().last().unwrap(),
+                    *self.cutter.vertices().first().unwrap(),
                 ],
                 egui::Stroke::new(unit, self.cutter_color));
         }
@@ -390,7 +388,7 @@ impl MyApp {
             self.error
                 .set_error(
                     "Ошибка".to_string(),
-                    format!("Многоугольник не замкнут"),
+                    "Многоугольник не замкнут".to_string(),
                 )
                 .enable();
             return;
@@ -401,7 +399,7 @@ impl MyApp {
             self.error
                 .set_error(
                     "Ошибка".to_string(),
-                    format!("Многоугольник не выпуклый"),
+                    "Многоугольник не выпуклый".to_string(),
                 )
                 .enable()
         }
@@ -422,7 +420,7 @@ impl MyApp {
             if self.cutter.closed() {
                 self.cutter.open();
             }
-            self.cutter.push(Pos2 {x: x as f32, y: y as f32});
+            self.cutter.push_distinct(Pos2 {x: x as f32, y: y as f32});
 
         } else {
             self.error.enable();
@@ -2,8 +2,9 @@ mod logic;
 
 use eframe::egui;
 use eframe::egui::{Color32, Pos2};
-use epaint::{pos2, Stroke};
-use logic::utils::{cut, Polygon};
+use epaint::Stroke;
+use logic::utils::cut;
+use raster::polygon::Polygon;
 use logic::windows::ErrorWindow;
 
 fn angle(a: Pos2, b: Pos2, c: Pos2, d: Pos2) -> f32 {
@@ -54,10 +55,10 @@ struct MyApp {
     buf_x_poly: String,
     buf_y_poly: String,
 
-    cutter: Polygon,
-    figure: Polygon,
+    cutter: Polygon<Pos2>,
+    figure: Polygon<Pos2>,
 
-    cut_lines: Vec<(Pos2)>,
+    cut_lines: Vec<Pos2>,
 }
 
 impl Default for MyApp {
@@ -357,7 +358,7 @@ impl MyApp {
                                 }
                             }
                             painter.line_segment(
-                                [last, pos1].into(),
+                                [last, pos1],
                                 egui::Stroke::new(unit, self.cutter_color),
                             );
                         }
@@ -417,7 +418,7 @@ impl MyApp {
                                 }
                             }
                             painter.line_segment(
-                                [last, pos1].into(),
+                                [last, pos1],
                                 egui::Stroke::new(unit, self.line_color),
                             );
                         }
@@ -434,7 +435,7 @@ impl MyApp {
             self.error
                 .set_error(
                     "Ошибка".to_string(),
-                    format!("Многоугольник не введен"),
+                    "Многоугольник не введен".to_string(),
                 )
                 .enable();
             return;
@@ -443,7 +444,7 @@ impl MyApp {
             self.error
                 .set_error(
                     "Ошибка".to_string(),
-                    format!("Многоугольник не замкнут"),
+                    "Многоугольник не замкнут".to_string(),
                 )
                 .enable();
             return;
@@ -452,7 +453,7 @@ impl MyApp {
             self.error
                 .set_error(
                     "Ошибка".to_string(),
-                    format!("Отсекатель не замкнут"),
+                    "Отсекатель не замкнут".to_string(),
                 )
                 .enable();
             return;
@@ -464,7 +465,7 @@ impl MyApp {
             self.error
                 .set_error(
                     "Ошибка".to_string(),
-                    format!("Многоугольник не выпуклый"),
+                    "Многоугольник не выпуклый".to_string(),
                 )
                 .enable()
         }
@@ -476,15 +477,6 @@ impl MyApp {
         self.cutter.clear();
     }
 
-    fn poly_clear(&mut self) {
-        self.figure.clear();
-    }
-
-    fn cutter_clear(&mut self) {
-        self.cut_lines.clear();
-        self.cutter.clear();
-    }
-
     fn line_clear(&mut self) {
         self.cut_lines.clear();
     }
@@ -0,0 +1,214 @@
+//! Консольный рендерер: те же алгоритмы, что и в лабораторных, но без окна —
+//! результат сохраняется в PNG или PPM.
+
+use clap::{Args, Parser, Subcommand};
+use raster::line::{self, Algo};
+use raster::pattern::{Dashes, Patterned};
+use raster::{accuracy, jaggies};
+use raster::{Framebuffer, Point, Rgba};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "raster",
+    version,
+    about = "Рендерер алгоритмов лабораторных без окна"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Спектр отрезков из 3-й лабораторной.
+    Spectrum(SpectrumArgs),
+    /// Ступенчатость всех алгоритмов отрезков под углами 0..=90°.
+    Jaggies(JaggiesArgs),
+    /// Точность пикселей алгоритмов окружностей и эллипсов из 4-й лабораторной.
+    Accuracy(AccuracyArgs),
+}
+
+#[derive(Debug, Args)]
+struct AccuracyArgs {
+    /// Наибольший радиус (большая полуось эллипса) в пикселях.
+    #[arg(short, long, default_value_t = 100)]
+    max_radius: u32,
+    /// Шаг радиуса в пикселях.
+    #[arg(short, long, default_value_t = 10)]
+    step: u32,
+    /// Отношение малой полуоси эллипса к большой.
+    #[arg(long, default_value_t = 0.5)]
+    ratio: f32,
+    /// Файл результата, `.csv` или `.md`.
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct JaggiesArgs {
+    /// Длина отрезка в пикселях.
+    #[arg(short, long, default_value_t = 100.0)]
+    length: f32,
+    /// Шаг угла в градусах.
+    #[arg(short, long, default_value_t = 1)]
+    step: u32,
+    /// Файл результата, `.csv` или `.md`.
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct SpectrumArgs {
+    /// Алгоритм отрезка.
+    #[arg(short, long, default_value_t = Algo::DDA)]
+    algo: Algo,
+    /// Центр спектра как `x,y`; по умолчанию — центр изображения.
+    #[arg(short, long, value_parser = parse_point)]
+    center: Option<Point>,
+    /// Длина отрезка в пикселях.
+    #[arg(short, long, default_value_t = 200.0)]
+    length: f32,
+    /// Шаг угла в градусах.
+    #[arg(short, long, default_value_t = 15.0)]
+    step: f32,
+    /// Штрихи и промежутки по очереди, например `6,3`; без него линия сплошная.
+    #[arg(long)]
+    dash: Option<Dashes>,
+    /// Цвет линии: `#rrggbb`, `#rrggbbaa` или `r,g,b`.
+    #[arg(long, default_value = "#000000")]
+    color: Rgba,
+    /// Цвет фона.
+    #[arg(long, default_value = "#ffffff")]
+    background: Rgba,
+    /// Размер изображения как `ШИРИНАxВЫСОТА`.
+    #[arg(long, default_value = "512x512", value_parser = parse_size)]
+    size: (usize, usize),
+    /// Файл результата, `.png` или `.ppm`.
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+fn parse_point(s: &str) -> Result<Point, String> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| format!("ожидалось `x,y`, а не `{s}`"))?;
+    let x = x.trim().parse::<f32>().map_err(|e| e.to_string())?;
+    let y = y.trim().parse::<f32>().map_err(|e| e.to_string())?;
+    Ok(Point::new(x, y))
+}
+
+fn parse_size(s: &str) -> Result<(usize, usize), String> {
+    let (w, h) = s
+        .split_once('x')
+        .ok_or_else(|| format!("ожидалось `ШИРИНАxВЫСОТА`, а не `{s}`"))?;
+    let w = w.parse::<usize>().map_err(|e| e.to_string())?;
+    let h = h.parse::<usize>().map_err(|e| e.to_string())?;
+    Ok((w, h))
+}
+
+fn overflow(algo: &Algo) -> String {
+    format!("{algo}: отрезок не помещается в целые координаты алгоритма")
+}
+
+fn spectrum(args: &SpectrumArgs) -> Result<(), String> {
+    if args.step <= 0.0 {
+        return Err(format!(
+            "шаг угла должен быть положительным, а не {}",
+            args.step
+        ));
+    }
+    let (width, height) = args.size;
+    let center = args
+        .center
+        .unwrap_or(Point::new(width as f32 / 2.0, height as f32 / 2.0))
+        .round();
+    let mut fb = Framebuffer::new(width, height, args.background);
+    for points in line::spectrum(center, args.length, args.step) {
+        match &args.dash {
+            Some(dash) => {
+                let pixels = Patterned::new(dash)
+                    .segment(&args.algo, &points)
+                    .map_err(|_| overflow(&args.algo))?;
+                fb.plot_line(&pixels, args.color);
+            }
+            None => args
+                .algo
+                .draw_to(&points, &mut fb.brush(args.color))
+                .map_err(|_| overflow(&args.algo))?,
+        }
+    }
+    fb.save(&args.output)
+        .map_err(|e| format!("{}: {e}", args.output.display()))
+}
+
+fn jaggies(args: &JaggiesArgs) -> Result<(), String> {
+    if args.step == 0 {
+        return Err("шаг угла должен быть положительным".to_string());
+    }
+    let mut records = vec![];
+    for algo in Algo::ALL {
+        let rows = jaggies::sweep(&algo, args.length, (0..=90).step_by(args.step as usize))
+            .map_err(|_| overflow(&algo))?;
+        records.extend(rows.into_iter().map(|(angle, steps)| jaggies::Record {
+            algorithm: algo.name().to_owned(),
+            angle,
+            steps,
+        }));
+    }
+    let res = match args.output.extension().and_then(|e| e.to_str()) {
+        Some("csv") => jaggies::save_csv(&args.output, &records),
+        Some("md") => jaggies::save_markdown(&args.output, &records),
+        _ => {
+            return Err(format!(
+                "{}: нужен файл .csv или .md",
+                args.output.display()
+            ))
+        }
+    };
+    res.map_err(|e| format!("{}: {e}", args.output.display()))
+}
+
+fn accuracy(args: &AccuracyArgs) -> Result<(), String> {
+    if args.step == 0 {
+        return Err("шаг радиуса должен быть положительным".to_string());
+    }
+    if !(args.ratio > 0.0 && args.ratio <= 1.0) {
+        return Err(format!(
+            "отношение полуосей должно быть в (0, 1], а не {}",
+            args.ratio
+        ));
+    }
+    let radii = (args.step..=args.max_radius)
+        .step_by(args.step as usize)
+        .map(|r| Point::new(r as f32, (r as f32 * args.ratio).round().max(1.0)));
+    let records = accuracy::sweep(radii);
+    let res = match args.output.extension().and_then(|e| e.to_str()) {
+        Some("csv") => accuracy::save_csv(&args.output, &records),
+        Some("md") => accuracy::save_markdown(&args.output, &records),
+        _ => {
+            return Err(format!(
+                "{}: нужен файл .csv или .md",
+                args.output.display()
+            ))
+        }
+    };
+    res.map_err(|e| format!("{}: {e}", args.output.display()))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let res = match &cli.command {
+        Command::Spectrum(args) => spectrum(args),
+        Command::Jaggies(args) => jaggies(args),
+        Command::Accuracy(args) => accuracy(args),
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("ошибка: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! Замеры времени всех алгоритмов растеризации отрезков, окружностей,
+//! эллипсов, повёрнутых эллипсов, кривых Безье и заливки многоугольников.
+//! Кроме отчётов criterion пишет сводку `rasterizers.csv` и
+//! `rasterizers.json` в каталог `timing::criterion_dir_in` — её открывают
+//! лабораторные 3 и 4. Все алгоритмы рисуют в `Counter`, так что в замеры
+//! не попадает выделение памяти под пиксели и группы сравнимы между собой.
+//!
+//! `cargo bench -p raster --bench rasterizers`
+
+use criterion::{black_box, BenchmarkId, Criterion};
+use raster::bezier::{Bezier, Flattening};
+use raster::circle::Circle;
+use raster::ellipse::{Ellipse, RotatedEllipse};
+use raster::fill::{self, Rule};
+use raster::line::{self, Algo};
+use raster::point;
+use raster::sink::Counter;
+use raster::timing::{self, Sample};
+use std::path::Path;
+use std::time::Duration;
+
+const LENGTHS: [u32; 6] = [10, 50, 100, 250, 500, 1000];
+/// Целочисленный Брезенхем окружности на `i32` переполняется при радиусах
+/// порядка 10⁹, эллипса на `i64` — при полуосях порядка 10⁶, так что
+/// `RADII` далеко от предела.
+const RADII: [u32; 6] = [10, 50, 100, 250, 500, 1000];
+const CURVE_SIZES: [u32; 5] = [100, 250, 500, 750, 1000];
+/// Допуск спрямления кривых Безье — как по умолчанию в лабораторной 3.
+const CURVE_TOLERANCE: f32 = 0.5;
+
+type CircleAlgo = fn(&Circle, &mut Counter);
+type EllipseAlgo = fn(&Ellipse, &mut Counter);
+type RotatedAlgo = fn(&RotatedEllipse, &mut Counter);
+
+const CIRCLE_ALGOS: [(&str, CircleAlgo); 6] = [
+    ("canonic", Circle::draw_canonic_to),
+    ("parametric", Circle::draw_parametric_to),
+    ("bresenham", Circle::draw_bresenham_to),
+    ("bresenham-integer", |shape, counter| {
+        shape
+            .draw_bresenham_integer_to(counter)
+            .expect("radius from RADII does not overflow")
+    }),
+    ("midpoint", Circle::draw_midpoint_to),
+    ("wu", Circle::draw_wu_to),
+];
+
+const ELLIPSE_ALGOS: [(&str, EllipseAlgo); 6] = [
+    ("canonic", Ellipse::draw_canonic_to),
+    ("parametric", Ellipse::draw_parametric_to),
+    ("bresenham", Ellipse::draw_bresenham_to),
+    ("bresenham-integer", |shape, counter| {
+        shape
+            .draw_bresenham_integer_to(counter)
+            .expect("radius from RADII does not overflow")
+    }),
+    ("midpoint", Ellipse::draw_midpoint_to),
+    ("wu", Ellipse::draw_wu_to),
+];
+
+const ROTATED_ALGOS: [(&str, RotatedAlgo); 4] = [
+    ("canonic", RotatedEllipse::draw_canonic_to),
+    ("parametric", RotatedEllipse::draw_parametric_to),
+    ("bresenham", RotatedEllipse::draw_bresenham_to),
+    ("midpoint", RotatedEllipse::draw_midpoint_to),
+];
+
+/// Один прогон `draw` в новый счётчик.
+fn count(draw: impl FnOnce(&mut Counter)) -> Counter {
+    let mut counter = Counter::default();
+    draw(&mut counter);
+    counter
+}
+
+/// Отрезки всех восьми октантов, чтобы время не зависело от направления.
+fn bench_lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("line");
+    for len in LENGTHS {
+        let segments = line::spectrum(point(0.0, 0.0), len as f32, 15.0);
+        for algo in Algo::ALL {
+            group.bench_with_input(BenchmarkId::new(algo.name(), len), &segments, |b, s| {
+                b.iter(|| {
+                    count(|counter| {
+                        for points in s {
+                            // концы отрезков из `LENGTHS` помещаются в `i32`
+                            algo.draw_to(black_box(points), counter)
+                                .expect("segment fits in i32");
+                        }
+                    })
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_circles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("circle");
+    for r in RADII {
+        let circle = Circle::new(point(0.0, 0.0), r as f32);
+        for (name, f) in CIRCLE_ALGOS {
+            group.bench_with_input(BenchmarkId::new(name, r), &circle, |b, circle| {
+                b.iter(|| count(|counter| f(black_box(circle), counter)))
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Полуоси `r` и `r / 2`.
+fn bench_ellipses(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ellipse");
+    for r in RADII {
+        let ellipse = Ellipse::new(point(0.0, 0.0), point(r as f32, r as f32 / 2.0));
+        for (name, f) in ELLIPSE_ALGOS {
+            group.bench_with_input(BenchmarkId::new(name, r), &ellipse, |b, ellipse| {
+                b.iter(|| count(|counter| f(black_box(ellipse), counter)))
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Полуоси `r` и `r / 2`, поворот на 30°.
+fn bench_rotated(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rotated");
+    for r in RADII {
+        let ellipse = RotatedEllipse::new(point(0.0, 0.0), point(r as f32, r as f32 / 2.0), 30.0);
+        for (name, f) in ROTATED_ALGOS {
+            group.bench_with_input(BenchmarkId::new(name, r), &ellipse, |b, ellipse| {
+                b.iter(|| count(|counter| f(black_box(ellipse), counter)))
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Кривая в форме буквы S, вписанная в квадрат `size`.
+fn s_curve(cubic: bool, size: f32) -> Bezier {
+    if cubic {
+        Bezier::Cubic([
+            point(0.0, 0.0),
+            point(size, 0.0),
+            point(0.0, size),
+            point(size, size),
+        ])
+    } else {
+        Bezier::Quadratic([point(0.0, 0.0), point(size, 0.0), point(size, size)])
+    }
+}
+
+/// Кривые обоими способами спрямления; звенья рисует ЦДА.
+fn bench_bezier(c: &mut Criterion) {
+    for (group, cubic) in [("bezier-quadratic", false), ("bezier-cubic", true)] {
+        let mut group = c.benchmark_group(group);
+        for size in CURVE_SIZES {
+            let curve = s_curve(cubic, size as f32);
+            for flattening in Flattening::ALL {
+                let id = BenchmarkId::new(flattening.name(), size);
+                group.bench_with_input(id, &curve, |b, curve| {
+                    b.iter(|| {
+                        count(|counter| {
+                            black_box(curve)
+                                .rasterize_to(&Algo::DDA, flattening, CURVE_TOLERANCE, counter)
+                                .expect("dda does not overflow");
+                        })
+                    })
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+/// Звезда с 32 лучами: внешний радиус `r`, внутренний `r / 2` — на каждой
+/// строке много активных рёбер. Сглаженная заливка — по правилу чётности.
+fn bench_fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fill");
+    for r in RADII {
+        let star = (0..64)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::PI / 32.0;
+                let radius = if i % 2 == 0 { r as f32 } else { r as f32 / 2.0 };
+                point(radius * angle.cos(), radius * angle.sin()).round()
+            })
+            .collect::<Vec<_>>();
+        let star = [star];
+        for rule in Rule::ALL {
+            group.bench_with_input(BenchmarkId::new(rule.name(), r), &star, |b, star| {
+                b.iter(|| count(|counter| fill::spans_to(black_box(star), rule, counter)))
+            });
+        }
+        group.bench_with_input(BenchmarkId::new("antialiased", r), &star, |b, star| {
+            b.iter(|| {
+                count(|counter| fill::antialiased_to(black_box(star), Rule::EvenOdd, counter))
+            })
+        });
+    }
+    group.finish();
+}
+
+fn read_estimate(path: &Path) -> Option<(f64, f64, f64)> {
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    let mean = &json["mean"];
+    Some((
+        mean["point_estimate"].as_f64()?,
+        mean["confidence_interval"]["lower_bound"].as_f64()?,
+        mean["confidence_interval"]["upper_bound"].as_f64()?,
+    ))
+}
+
+/// Собирает оценки criterion из `<dir>/<группа>/<алгоритм>/<размер>/new`.
+/// Отфильтрованные при запуске замеры просто пропускаются.
+fn collect(dir: &Path) -> Vec<Sample> {
+    let line_names = Algo::ALL.map(|algo| algo.name());
+    let circle_names = CIRCLE_ALGOS.map(|(name, _)| name);
+    let ellipse_names = ELLIPSE_ALGOS.map(|(name, _)| name);
+    let rotated_names = ROTATED_ALGOS.map(|(name, _)| name);
+    let [even_odd, non_zero] = Rule::ALL.map(|rule| rule.name());
+    let fill_names = [even_odd, non_zero, "antialiased"];
+    let curve_names = Flattening::ALL.map(|flattening| flattening.name());
+    let groups: [(&str, &[&str], &[u32]); 7] = [
+        ("line", &line_names, &LENGTHS),
+        ("circle", &circle_names, &RADII),
+        ("ellipse", &ellipse_names, &RADII),
+        ("rotated", &rotated_names, &RADII),
+        ("bezier-quadratic", &curve_names, &CURVE_SIZES),
+        ("bezier-cubic", &curve_names, &CURVE_SIZES),
+        ("fill", &fill_names, &RADII),
+    ];
+    let mut samples = vec![];
+    for (group, names, sizes) in groups {
+        for &name in names {
+            for &size in sizes {
+                let path = dir.join(format!("{group}/{name}/{size}/new/estimates.json"));
+                if let Some((mean_ns, lower_ns, upper_ns)) = read_estimate(&path) {
+                    samples.push(Sample {
+                        group: group.to_owned(),
+                        algorithm: name.to_owned(),
+                        size,
+                        mean_ns,
+                        lower_ns,
+                        upper_ns,
+                    });
+                }
+            }
+        }
+    }
+    samples
+}
+
+fn main() {
+    // cargo bench запускает бенчмарк из каталога пакета, а отчёты criterion
+    // лежат в target корня workspace
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let dir = timing::criterion_dir_in(&workspace);
+    let mut c = Criterion::default()
+        .output_directory(&dir)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1))
+        .configure_from_args();
+    bench_lines(&mut c);
+    bench_circles(&mut c);
+    bench_ellipses(&mut c);
+    bench_rotated(&mut c);
+    bench_bezier(&mut c);
+    bench_fill(&mut c);
+    c.final_summary();
+
+    let samples = collect(&dir);
+    if samples.is_empty() {
+        return;
+    }
+    let (csv, json) = (timing::summary_csv(&dir), timing::summary_json(&dir));
+    timing::save_csv(&csv, &samples).expect("failed to write csv summary");
+    timing::save_json(&json, &samples).expect("failed to write json summary");
+    println!("summary: {} and {}", csv.display(), json.display());
+}
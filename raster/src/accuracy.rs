@@ -0,0 +1,299 @@
+//! Точность растеризации окружностей и эллипсов: насколько пиксели
+//! алгоритма отходят от точной кривой, сколько их нарисовано повторно,
+//! где контур рвётся и сколько точек алгоритм строит на стыке симметричных
+//! октантов (у эллипса — четвертей), так что их рисуют оба отражения.
+
+use crate::circle::Circle;
+use crate::ellipse::Ellipse;
+use crate::point::{point, Point};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+const CSV_HEADER: &str = "shape,algorithm,a,b,pixels,max_error,mean_error,duplicates,gaps,overlap";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Circle,
+    Ellipse,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 2] = [Shape::Circle, Shape::Ellipse];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Shape::Circle => "circle",
+            Shape::Ellipse => "ellipse",
+        }
+    }
+
+    /// Полуоси фигуры: у окружности обе равны `radius.x`.
+    pub fn radius(self, radius: Point) -> Point {
+        match self {
+            Shape::Circle => point(radius.x, radius.x),
+            Shape::Ellipse => radius,
+        }
+    }
+}
+
+/// Алгоритмы лабораторной 4, которые строят контур без сглаживания.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Canonic,
+    Parametric,
+    Bresenham,
+    Midpoint,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 4] = [
+        Algorithm::Canonic,
+        Algorithm::Parametric,
+        Algorithm::Bresenham,
+        Algorithm::Midpoint,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Canonic => "canonic",
+            Algorithm::Parametric => "parametric",
+            Algorithm::Bresenham => "bresenham",
+            Algorithm::Midpoint => "midpoint",
+        }
+    }
+
+    /// Кривая с центром в начале координат; при `radius.x != radius.y`
+    /// окружность строится по `radius.x`. Без симметрии — только точки,
+    /// построенные самим алгоритмом.
+    pub fn draw(self, shape: Shape, radius: Point, symmetric: bool) -> Vec<Point> {
+        match shape {
+            Shape::Circle => {
+                let mut circle = Circle::new(Point::ZERO, radius.x);
+                circle.set_symmetric(symmetric);
+                match self {
+                    Algorithm::Canonic => circle.draw_canonic(),
+                    Algorithm::Parametric => circle.draw_parametric(),
+                    Algorithm::Bresenham => circle.draw_bresenham(),
+                    Algorithm::Midpoint => circle.draw_midpoint(),
+                }
+            }
+            Shape::Ellipse => {
+                let mut ellipse = Ellipse::new(Point::ZERO, radius);
+                ellipse.set_symmetric(symmetric);
+                match self {
+                    Algorithm::Canonic => ellipse.draw_canonic(),
+                    Algorithm::Parametric => ellipse.draw_parametric(),
+                    Algorithm::Bresenham => ellipse.draw_bresenham(),
+                    Algorithm::Midpoint => ellipse.draw_midpoint(),
+                }
+            }
+        }
+    }
+}
+
+/// Точность одной кривой.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Accuracy {
+    /// Сколько пикселей выдал алгоритм, с повторами.
+    pub pixels: usize,
+    /// Наибольшее и среднее радиальное отклонение: расстояние от центра
+    /// пикселя до кривой вдоль луча из центра кривой.
+    pub max_error: f32,
+    pub mean_error: f32,
+    /// Сколько пикселей нарисовано повторно.
+    pub duplicates: usize,
+    /// Сколько соседних по углу пикселей не касаются друг друга (не
+    /// 8-связны) — разрывов замкнутого контура.
+    pub gaps: usize,
+    /// Сколько построенных алгоритмом точек лежат на границе своего октанта
+    /// (четверти) или за ней и потому рисуются двумя отражениями.
+    pub overlap: usize,
+}
+
+/// Строка отчёта: фигура, алгоритм, полуоси и точность.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub shape: String,
+    pub algorithm: String,
+    pub radius: Point,
+    pub accuracy: Accuracy,
+}
+
+/// Радиальное отклонение пикселя `p` от эллипса с полуосями `radius`.
+pub fn radial_error(p: Point, center: Point, radius: Point) -> f32 {
+    let d = p - center;
+    let len = d.x.hypot(d.y);
+    let scale = (d.x / radius.x).hypot(d.y / radius.y);
+    if scale == 0.0 {
+        radius.x.min(radius.y)
+    } else {
+        (len - len / scale).abs()
+    }
+}
+
+/// Разрывы замкнутого контура: пиксели без повторов упорядочиваются по
+/// углу вокруг центра, и соседние по кругу пары должны быть 8-связны.
+pub fn gaps(pixels: &[Point], center: Point) -> usize {
+    let mut unique = unique(pixels).into_iter().collect::<Vec<_>>();
+    if unique.len() < 2 {
+        return 0;
+    }
+    let angle = |&(x, y): &(i32, i32)| (y as f32 - center.y).atan2(x as f32 - center.x);
+    unique.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+    let last = unique[unique.len() - 1];
+    std::iter::once((last, unique[0]))
+        .chain(unique.windows(2).map(|w| (w[0], w[1])))
+        .filter(|&((x1, y1), (x2, y2))| (x1 - x2).abs() > 1 || (y1 - y2).abs() > 1)
+        .count()
+}
+
+/// Точки, построенные алгоритмом до отражения, которые рисуются двумя
+/// отражениями. У окружности это точки на осях и диагонали, а также точки
+/// по ту сторону диагонали от большинства: октант задевает соседний. У
+/// эллипса — точки на осях.
+pub fn overlap(shape: Shape, generated: &[Point], center: Point) -> usize {
+    let rel = generated.iter().map(|&p| {
+        let d = p - center;
+        (d.x.abs(), d.y.abs())
+    });
+    match shape {
+        Shape::Circle => {
+            let (mut border, mut below, mut above) = (0, 0, 0);
+            for (x, y) in rel {
+                if x == 0.0 || y == 0.0 || x == y {
+                    border += 1;
+                } else if x > y {
+                    below += 1;
+                } else {
+                    above += 1;
+                }
+            }
+            border + usize::min(below, above)
+        }
+        Shape::Ellipse => rel.filter(|&(x, y)| x == 0.0 || y == 0.0).count(),
+    }
+}
+
+fn unique(pixels: &[Point]) -> HashSet<(i32, i32)> {
+    pixels.iter().map(|p| (p.x as i32, p.y as i32)).collect()
+}
+
+/// Точность кривой `pixels` с центром `center` и полуосями `radius`;
+/// `generated` — те же пиксели до отражения, по ним считается `overlap`.
+pub fn analyze(
+    shape: Shape,
+    pixels: &[Point],
+    generated: &[Point],
+    center: Point,
+    radius: Point,
+) -> Accuracy {
+    let errors = pixels.iter().map(|&p| radial_error(p, center, radius));
+    let (max_error, total) = errors.fold((0.0f32, 0.0), |(max, sum), e| (max.max(e), sum + e));
+    Accuracy {
+        pixels: pixels.len(),
+        max_error,
+        mean_error: if pixels.is_empty() {
+            0.0
+        } else {
+            total / pixels.len() as f32
+        },
+        duplicates: pixels.len() - unique(pixels).len(),
+        gaps: gaps(pixels, center),
+        overlap: overlap(shape, generated, center),
+    }
+}
+
+/// Точность алгоритма на кривой с центром в начале координат.
+pub fn measure(shape: Shape, algorithm: Algorithm, radius: Point) -> Accuracy {
+    let radius = shape.radius(radius);
+    analyze(
+        shape,
+        &algorithm.draw(shape, radius, true),
+        &algorithm.draw(shape, radius, false),
+        Point::ZERO,
+        radius,
+    )
+}
+
+/// Все алгоритмы на обеих фигурах с полуосями `radii`; у окружности
+/// радиус — `radius.x`.
+pub fn sweep(radii: impl IntoIterator<Item = Point>) -> Vec<Record> {
+    let radii = radii.into_iter().collect::<Vec<_>>();
+    let mut records = vec![];
+    for shape in Shape::ALL {
+        for algorithm in Algorithm::ALL {
+            for &radius in &radii {
+                let radius = shape.radius(radius);
+                records.push(Record {
+                    shape: shape.name().to_owned(),
+                    algorithm: algorithm.name().to_owned(),
+                    radius,
+                    accuracy: measure(shape, algorithm, radius),
+                });
+            }
+        }
+    }
+    records
+}
+
+pub fn write_csv(mut w: impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(w, "{CSV_HEADER}")?;
+    for r in records {
+        let a = &r.accuracy;
+        writeln!(
+            w,
+            "{},{},{},{},{},{:.4},{:.4},{},{},{}",
+            r.shape,
+            r.algorithm,
+            r.radius.x,
+            r.radius.y,
+            a.pixels,
+            a.max_error,
+            a.mean_error,
+            a.duplicates,
+            a.gaps,
+            a.overlap
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_markdown(mut w: impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(
+        w,
+        "| Фигура | Алгоритм | a | b | Пиксели | Макс. отклонение | Среднее отклонение | Повторы | Разрывы | Наложение октантов |"
+    )?;
+    writeln!(w, "|---|---|---:|---:|---:|---:|---:|---:|---:|---:|")?;
+    for r in records {
+        let a = &r.accuracy;
+        writeln!(
+            w,
+            "| {} | {} | {} | {} | {} | {:.4} | {:.4} | {} | {} | {} |",
+            r.shape,
+            r.algorithm,
+            r.radius.x,
+            r.radius.y,
+            a.pixels,
+            a.max_error,
+            a.mean_error,
+            a.duplicates,
+            a.gaps,
+            a.overlap
+        )?;
+    }
+    Ok(())
+}
+
+pub fn save_csv(path: impl AsRef<Path>, records: &[Record]) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_csv(&mut w, records)?;
+    w.flush()
+}
+
+pub fn save_markdown(path: impl AsRef<Path>, records: &[Record]) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_markdown(&mut w, records)?;
+    w.flush()
+}
@@ -0,0 +1,393 @@
+use crate::ellipse::Ellipse;
+use crate::line::dda;
+use crate::point::{point, Point};
+use crate::polygon::Polygon;
+use crate::sink::Sink;
+use std::collections::HashSet;
+use std::f32::consts::TAU;
+
+/// Насколько октант (или четверть) попадает в угловой промежуток.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coverage {
+    Outside,
+    Partial,
+    Inside,
+}
+
+/// Угловой промежуток от `start` до `end` градусов, отсчитываемый в ту же
+/// сторону, что и `Point::rotate`. Если `end - start` не меньше 360, это
+/// полная окружность.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArcRange {
+    start: f32,
+    span: f32,
+    from: Point,
+    to: Point,
+    octants: [Coverage; 8],
+}
+
+fn direction(angle: f32) -> Point {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    point(cos, sin)
+}
+
+fn cross(a: Point, b: Point) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Point, b: Point) -> f32 {
+    a.x * b.x + a.y * b.y
+}
+
+impl Default for ArcRange {
+    fn default() -> Self {
+        Self::full()
+    }
+}
+
+impl ArcRange {
+    pub fn new(start: f32, end: f32) -> Self {
+        let span = if end - start >= 360.0 {
+            360.0
+        } else {
+            (end - start).rem_euclid(360.0)
+        };
+        let start = start.rem_euclid(360.0);
+        let mut range = Self {
+            start,
+            span,
+            from: direction(start),
+            to: direction(start + span),
+            octants: [Coverage::Inside; 8],
+        };
+        range.octants = std::array::from_fn(|k| range.coverage(45.0 * k as f32, 45.0));
+        range
+    }
+
+    pub fn full() -> Self {
+        Self::new(0.0, 360.0)
+    }
+
+    pub fn start(&self) -> f32 {
+        self.start
+    }
+
+    pub fn end(&self) -> f32 {
+        self.start + self.span
+    }
+
+    pub fn span(&self) -> f32 {
+        self.span
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.span >= 360.0
+    }
+
+    /// Единичные векторы направлений на начало и конец дуги.
+    pub fn directions(&self) -> (Point, Point) {
+        (self.from, self.to)
+    }
+
+    /// Середина промежутка.
+    pub fn middle(&self) -> Point {
+        direction(self.start + self.span / 2.0)
+    }
+
+    /// Лежит ли направление `d` (от центра) в промежутке. Считается по знакам
+    /// векторных произведений, без тригонометрии.
+    pub fn contains(&self, d: Point) -> bool {
+        if self.is_full() {
+            return true;
+        }
+        let after_start = cross(self.from, d) >= 0.0;
+        let before_end = cross(d, self.to) >= 0.0;
+        if self.span < 180.0 {
+            // у дуги нулевой длины обе границы коллинеарны и направлению
+            // назад, его отсекает проекция на середину
+            let ahead = dot(self.from, d) + dot(self.to, d) >= 0.0;
+            after_start && before_end && ahead
+        } else if self.span == 180.0 {
+            after_start && before_end
+        } else {
+            after_start || before_end
+        }
+    }
+
+    /// Пересечение с промежутком `[from, from + width]`, `width` не больше 180.
+    pub fn coverage(&self, from: f32, width: f32) -> Coverage {
+        if self.is_full() {
+            return Coverage::Inside;
+        }
+        let offset = (from - self.start).rem_euclid(360.0);
+        if offset + width <= self.span {
+            Coverage::Inside
+        } else if offset <= self.span || (self.start - from).rem_euclid(360.0) <= width {
+            Coverage::Partial
+        } else {
+            Coverage::Outside
+        }
+    }
+
+    /// Октант `k` — промежуток `[45k, 45(k + 1)]`.
+    pub fn octant(&self, k: usize) -> Coverage {
+        self.octants[k]
+    }
+
+    /// Четверть `k` — промежуток `[90k, 90(k + 1)]`.
+    pub fn quadrant(&self, k: usize) -> Coverage {
+        match (self.octants[2 * k], self.octants[2 * k + 1]) {
+            (Coverage::Inside, Coverage::Inside) => Coverage::Inside,
+            (Coverage::Outside, Coverage::Outside) => Coverage::Outside,
+            _ => Coverage::Partial,
+        }
+    }
+}
+
+/// Как `plot_circle_pixels`, но только в октанты, попадающие в `arc`.
+/// Точка `(x, y)` берётся из октанта между 45° и 90°; проверка каждого
+/// пикселя нужна лишь в октантах, которые дуга задевает частично.
+pub fn plot_circle_arc_pixels<S: Sink + ?Sized>(
+    x: f32,
+    y: f32,
+    x_c: f32,
+    y_c: f32,
+    arc: &ArcRange,
+    sink: &mut S,
+) {
+    let mirrors = [
+        (y, x, 0),
+        (x, y, 1),
+        (-x, y, 2),
+        (-y, x, 3),
+        (-y, -x, 4),
+        (-x, -y, 5),
+        (x, -y, 6),
+        (y, -x, 7),
+    ];
+    for (dx, dy, k) in mirrors {
+        // канонический алгоритм чуть заходит за 45°, и такие точки попадают
+        // в соседний октант; точки на границе октанта принадлежат обоим
+        let inside = match arc.octant(k) {
+            _ if x >= y || x == 0.0 => arc.contains(point(dx, dy)),
+            Coverage::Outside => false,
+            Coverage::Inside => true,
+            Coverage::Partial => arc.contains(point(dx, dy)),
+        };
+        if inside {
+            sink.plot(point(x_c + dx, y_c + dy));
+        }
+    }
+}
+
+/// Как `plot_ellipse_pixels`, но только в четверти, попадающие в `arc`.
+pub fn plot_ellipse_arc_pixels<S: Sink + ?Sized>(
+    x: f32,
+    y: f32,
+    x_c: f32,
+    y_c: f32,
+    arc: &ArcRange,
+    sink: &mut S,
+) {
+    let mirrors = [(x, y, 0), (-x, y, 1), (-x, -y, 2), (x, -y, 3)];
+    for (dx, dy, k) in mirrors {
+        let inside = match arc.quadrant(k) {
+            // точки на осях принадлежат двум четвертям
+            _ if x == 0.0 || y == 0.0 => arc.contains(point(dx, dy)),
+            Coverage::Outside => false,
+            Coverage::Inside => true,
+            Coverage::Partial => arc.contains(point(dx, dy)),
+        };
+        if inside {
+            sink.plot(point(x_c + dx, y_c + dy));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectorKind {
+    /// Дуга и два радиуса к центру.
+    Pie,
+    /// Дуга и стягивающая её хорда.
+    Chord,
+}
+
+/// Замкнутая область, ограниченная дугой эллипса с полуосями `radius`
+/// и радиусами либо хордой. Углы дуги полярные, то есть граничные радиусы
+/// идут ровно под `start` и `end` градусов.
+#[derive(Debug, Clone)]
+pub struct Sector {
+    center: Point,
+    radius: Point,
+    arc: ArcRange,
+    kind: SectorKind,
+}
+
+impl Sector {
+    pub fn new(center: Point, radius: Point, arc: ArcRange, kind: SectorKind) -> Self {
+        Self {
+            center,
+            radius,
+            arc,
+            kind,
+        }
+    }
+
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    pub fn radius(&self) -> Point {
+        self.radius
+    }
+
+    pub fn arc(&self) -> ArcRange {
+        self.arc
+    }
+
+    pub fn kind(&self) -> SectorKind {
+        self.kind
+    }
+
+    /// Эллипс, рисующий только дугу сектора.
+    pub fn ellipse(&self) -> Ellipse {
+        let mut ellipse = Ellipse::new(self.center, self.radius);
+        ellipse.set_arc(self.arc);
+        ellipse
+    }
+
+    /// Точка эллипса на луче из центра в направлении `d`.
+    pub fn ray_point(&self, d: Point) -> Point {
+        let (u, v) = (d.x / self.radius.x, d.y / self.radius.y);
+        self.center + d * (u * u + v * v).sqrt().recip()
+    }
+
+    /// Многоугольник, вписанный в сектор, — вершины дуги через шаг около
+    /// пикселя и центр для `Pie`. Годится для построчной заливки.
+    pub fn polygon(&self) -> Polygon {
+        let mut polygon = Polygon::default();
+        let r = self.radius.x.max(self.radius.y).max(1.0);
+        let n = ((self.arc.span().to_radians() * r).ceil() as usize).max(1);
+        if self.kind == SectorKind::Pie && !self.arc.is_full() {
+            polygon.push_distinct(self.center.round());
+        }
+        let last = if self.arc.is_full() { n - 1 } else { n };
+        for i in 0..=last {
+            let angle = self.arc.start() + self.arc.span() * i as f32 / n as f32;
+            polygon.push_distinct(self.ray_point(direction(angle)).round());
+        }
+        polygon.close();
+        polygon
+    }
+
+    /// Граница сектора: пиксели дуги `arc`, построенной любым алгоритмом с тем
+    /// же промежутком, и отрезки ЦДА от её крайних пикселей. Контур
+    /// 8-связный, так что для затравочной заливки по 4 соседям он замкнут.
+    pub fn border(&self, mut arc: Vec<Point>) -> Vec<Point> {
+        if self.arc.is_full() {
+            return arc;
+        }
+        let (from, to) = self.arc.directions();
+        let first = self.arc_end(&arc, from, 1.0);
+        let last = self.arc_end(&arc, to, -1.0);
+        let center = self.center.round();
+        match self.kind {
+            SectorKind::Pie => {
+                arc.extend(dda(&[center, first]));
+                arc.extend(dda(&[center, last]));
+            }
+            SectorKind::Chord => arc.extend(dda(&[first, last])),
+        }
+        arc
+    }
+
+    /// Пиксель дуги, ближайший к граничному лучу `d` со стороны `side`
+    /// (1 — после луча, -1 — до него). Без учёта стороны у почти полной дуги
+    /// оба конца достались бы одному пикселю.
+    fn arc_end(&self, arc: &[Point], d: Point, side: f32) -> Point {
+        arc.iter()
+            .copied()
+            .filter(|&p| {
+                let rel = p - self.center;
+                rel.x * d.x + rel.y * d.y > 0.0 && cross(d, rel) * side >= 0.0
+            })
+            .min_by(|&a, &b| {
+                let a = cross(d, a - self.center).abs();
+                let b = cross(d, b - self.center).abs();
+                a.total_cmp(&b)
+            })
+            .unwrap_or_else(|| self.ray_point(d).round())
+    }
+
+    /// Параметр эллипса (в радианах) для точки на луче `d`.
+    fn eccentric(&self, d: Point) -> f32 {
+        (d.y / self.radius.y).atan2(d.x / self.radius.x)
+    }
+
+    /// Затравочная точка внутри сектора с границей `border` (результатом
+    /// `Sector::border`). У очень узких секторов и низких сегментов внутри может не
+    /// оказаться ни одного пикселя, тогда точка попадёт на границу.
+    pub fn seed(&self, border: &[Point]) -> Point {
+        let (from, to) = match self.kind {
+            _ if self.arc.is_full() => return self.center.round(),
+            SectorKind::Pie => (self.center, self.ray_point(self.arc.middle())),
+            SectorKind::Chord => {
+                // дальняя от хорды точка дуги — середина по параметру эллипса,
+                // а не по полярному углу
+                let (from, to) = self.arc.directions();
+                let chord = (self.ray_point(from) + self.ray_point(to)) * 0.5;
+                let t_from = self.eccentric(from);
+                let t = t_from + (self.eccentric(to) - t_from).rem_euclid(TAU) / 2.0;
+                let top = self.center + point(self.radius.x * t.cos(), self.radius.y * t.sin());
+                (chord, top)
+            }
+        };
+        inner_pixel(border, from, to).unwrap_or(((from + to) * 0.5).round())
+    }
+}
+
+/// Первый пиксель на пути от `from` к `to`, заливка из которого по 4 соседям
+/// не выходит за описанный прямоугольник `border`. Точка, найденная только
+/// по геометрии, может оказаться на границе или за ней: граница идёт по
+/// пикселям и отстоит от точной кривой больше чем на полпикселя.
+fn inner_pixel(border: &[Point], from: Point, to: Point) -> Option<Point> {
+    let border = border
+        .iter()
+        .map(|p| (p.x.round() as i32, p.y.round() as i32))
+        .collect::<HashSet<_>>();
+    let (min_x, max_x) = border.iter().fold((i32::MAX, i32::MIN), |(lo, hi), p| {
+        (lo.min(p.0), hi.max(p.0))
+    });
+    let (min_y, max_y) = border.iter().fold((i32::MAX, i32::MIN), |(lo, hi), p| {
+        (lo.min(p.1), hi.max(p.1))
+    });
+    // пиксели, из которых заливка уже вышла наружу
+    let mut outside = HashSet::new();
+    // шаг в четверть пикселя
+    let n = ((from.distance(to) * 4.0) as usize).max(1);
+    for i in 0..=n {
+        let p = (from + (to - from) * (i as f32 / n as f32)).round();
+        let start = (p.x as i32, p.y as i32);
+        if border.contains(&start) || outside.contains(&start) {
+            continue;
+        }
+        let (mut seen, mut stack) = (HashSet::from([start]), vec![start]);
+        let mut closed = true;
+        while let Some((x, y)) = stack.pop() {
+            if x <= min_x || x >= max_x || y <= min_y || y >= max_y {
+                closed = false;
+                break;
+            }
+            for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if !border.contains(&next) && seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        if closed {
+            return Some(p);
+        }
+        outside.extend(seen);
+    }
+    None
+}
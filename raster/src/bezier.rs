@@ -0,0 +1,255 @@
+//! Кривые Безье второй и третьей степени. Кривая сначала заменяется
+//! ломаной — адаптивным делением пополам по де Кастельжо или прямыми
+//! разностями с постоянным шагом, — а звенья ломаной рисует любой
+//! растеризатор отрезков (`line::Rasterizer`).
+
+use crate::integer::Overflow;
+use crate::line::{LinePixels, Rasterizer};
+use crate::point::Point;
+use crate::sink::Sink;
+
+/// Глубже деление не идёт, даже если кривая ещё не спрямилась.
+const MAX_DEPTH: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bezier {
+    Quadratic([Point; 3]),
+    Cubic([Point; 4]),
+}
+
+/// Способ замены кривой ломаной.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flattening {
+    /// Делить кривую пополам, пока опорные точки не лягут ближе допуска к
+    /// хорде.
+    #[default]
+    Subdivision,
+    /// Вычислять точки с постоянным шагом по `t` одними сложениями; число
+    /// шагов — по формуле Ванга для того же допуска.
+    ForwardDifferencing,
+}
+
+impl Flattening {
+    pub const ALL: [Flattening; 2] = [Flattening::Subdivision, Flattening::ForwardDifferencing];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Flattening::Subdivision => "subdivision",
+            Flattening::ForwardDifferencing => "forward-differencing",
+        }
+    }
+}
+
+fn lerp(a: Point, b: Point, t: f32) -> Point {
+    a + (b - a) * t
+}
+
+/// Расстояние от `p` до отрезка `ab`.
+fn segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let d = b - a;
+    let len2 = d.x * d.x + d.y * d.y;
+    if len2 == 0.0 {
+        return p.distance(a);
+    }
+    let t = (((p.x - a.x) * d.x + (p.y - a.y) * d.y) / len2).clamp(0.0, 1.0);
+    p.distance(lerp(a, b, t))
+}
+
+impl Bezier {
+    /// Опорные точки, от начала кривой к концу.
+    pub fn points(&self) -> &[Point] {
+        match self {
+            Bezier::Quadratic(p) => p,
+            Bezier::Cubic(p) => p,
+        }
+    }
+
+    pub fn points_mut(&mut self) -> &mut [Point] {
+        match self {
+            Bezier::Quadratic(p) => p,
+            Bezier::Cubic(p) => p,
+        }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.points().len() - 1
+    }
+
+    pub fn start(&self) -> Point {
+        self.points()[0]
+    }
+
+    pub fn end(&self) -> Point {
+        self.points()[self.degree()]
+    }
+
+    /// Точка кривой при `t` из `[0, 1]`.
+    pub fn eval(&self, t: f32) -> Point {
+        match *self {
+            Bezier::Quadratic([p0, p1, p2]) => lerp(lerp(p0, p1, t), lerp(p1, p2, t), t),
+            Bezier::Cubic([p0, p1, p2, p3]) => {
+                let (a, b, c) = (lerp(p0, p1, t), lerp(p1, p2, t), lerp(p2, p3, t));
+                lerp(lerp(a, b, t), lerp(b, c, t), t)
+            }
+        }
+    }
+
+    /// Две половины кривой, разделённой в точке `t`.
+    pub fn split(&self, t: f32) -> (Bezier, Bezier) {
+        match *self {
+            Bezier::Quadratic([p0, p1, p2]) => {
+                let (a, b) = (lerp(p0, p1, t), lerp(p1, p2, t));
+                let m = lerp(a, b, t);
+                (Bezier::Quadratic([p0, a, m]), Bezier::Quadratic([m, b, p2]))
+            }
+            Bezier::Cubic([p0, p1, p2, p3]) => {
+                let (a, b, c) = (lerp(p0, p1, t), lerp(p1, p2, t), lerp(p2, p3, t));
+                let (d, e) = (lerp(a, b, t), lerp(b, c, t));
+                let m = lerp(d, e, t);
+                (Bezier::Cubic([p0, a, d, m]), Bezier::Cubic([m, e, c, p3]))
+            }
+        }
+    }
+
+    /// Наибольшее расстояние от промежуточных опорных точек до хорды. Кривая
+    /// лежит в выпуклой оболочке опорных точек, так что от хорды она отходит
+    /// не дальше.
+    pub fn flatness(&self) -> f32 {
+        let points = self.points();
+        let (a, b) = (self.start(), self.end());
+        points[1..points.len() - 1]
+            .iter()
+            .map(|&p| segment_distance(p, a, b))
+            .fold(0.0, f32::max)
+    }
+
+    /// Вершины ломаной, отходящей от кривой не дальше `tolerance`:
+    /// кривая делится пополам, пока не станет плоской.
+    pub fn subdivide(&self, tolerance: f32) -> Vec<Point> {
+        fn go(curve: &Bezier, tolerance: f32, depth: u32, res: &mut Vec<Point>) {
+            if depth >= MAX_DEPTH || curve.flatness() <= tolerance {
+                res.push(curve.end());
+            } else {
+                let (left, right) = curve.split(0.5);
+                go(&left, tolerance, depth + 1, res);
+                go(&right, tolerance, depth + 1, res);
+            }
+        }
+        let mut res = vec![self.start()];
+        go(self, tolerance, 0, &mut res);
+        res
+    }
+
+    /// Число равных по `t` звеньев, при котором ломаная отходит от кривой не
+    /// дальше `tolerance` (формула Ванга).
+    pub fn segments(&self, tolerance: f32) -> usize {
+        let points = self.points();
+        let n = self.degree() as f32;
+        let m = points
+            .windows(3)
+            .map(|w| {
+                let d = w[0] - w[1] * 2.0 + w[2];
+                d.x.hypot(d.y)
+            })
+            .fold(0.0, f32::max);
+        let segments = (n * (n - 1.0) / 8.0 * m / tolerance.max(f32::EPSILON)).sqrt();
+        (segments.ceil() as usize).clamp(1, 1 << MAX_DEPTH)
+    }
+
+    /// `steps + 1` точек кривой с шагом `1 / steps` по `t`, посчитанных
+    /// прямыми разностями. Последняя точка — точно конец кривой.
+    pub fn forward_differences(&self, steps: usize) -> Vec<Point> {
+        let steps = steps.max(1);
+        let h = 1.0 / steps as f32;
+        // B(t) = a t³ + b t² + c t + d
+        let (a, b, c) = match *self {
+            Bezier::Quadratic([p0, p1, p2]) => (Point::ZERO, p0 - p1 * 2.0 + p2, (p1 - p0) * 2.0),
+            Bezier::Cubic([p0, p1, p2, p3]) => (
+                p3 - p2 * 3.0 + p1 * 3.0 - p0,
+                (p2 - p1 * 2.0 + p0) * 3.0,
+                (p1 - p0) * 3.0,
+            ),
+        };
+        let (h2, h3) = (h * h, h * h * h);
+        let mut p = self.start();
+        let mut d1 = a * h3 + b * h2 + c * h;
+        let mut d2 = a * (6.0 * h3) + b * (2.0 * h2);
+        let d3 = a * (6.0 * h3);
+        let mut res = Vec::with_capacity(steps + 1);
+        res.push(p);
+        for _ in 1..steps {
+            p = p + d1;
+            d1 = d1 + d2;
+            d2 = d2 + d3;
+            res.push(p);
+        }
+        res.push(self.end());
+        res
+    }
+
+    /// Ломаная, отходящая от кривой не дальше `tolerance`.
+    pub fn flatten(&self, flattening: Flattening, tolerance: f32) -> Vec<Point> {
+        match flattening {
+            Flattening::Subdivision => self.subdivide(tolerance),
+            Flattening::ForwardDifferencing => self.forward_differences(self.segments(tolerance)),
+        }
+    }
+
+    /// Ломаная кривой в пикселях (`pixel_polyline`).
+    pub fn polyline(&self, flattening: Flattening, tolerance: f32) -> Vec<Point> {
+        pixel_polyline(self.flatten(flattening, tolerance))
+    }
+
+    /// Пиксели звеньев ломаной (`polyline`) по порядку.
+    pub fn rasterize<R: Rasterizer + ?Sized>(
+        &self,
+        rasterizer: &R,
+        flattening: Flattening,
+        tolerance: f32,
+    ) -> Result<Vec<LinePixels>, Overflow> {
+        rasterize_polyline(&self.polyline(flattening, tolerance), rasterizer)
+    }
+
+    pub fn rasterize_to<R: Rasterizer + ?Sized, S: Sink>(
+        &self,
+        rasterizer: &R,
+        flattening: Flattening,
+        tolerance: f32,
+        sink: &mut S,
+    ) -> Result<(), Overflow> {
+        rasterize_polyline_to(&self.polyline(flattening, tolerance), rasterizer, sink)
+    }
+}
+
+/// Вершины ломаной, округлённые до центров пикселей, без повторов подряд.
+/// Ломаная, стянувшаяся в точку, даёт отрезок из одной точки.
+pub fn pixel_polyline(vertices: impl IntoIterator<Item = Point>) -> Vec<Point> {
+    let mut vertices = vertices.into_iter().map(Point::round).collect::<Vec<_>>();
+    vertices.dedup();
+    if vertices.len() == 1 {
+        vertices.push(vertices[0]);
+    }
+    vertices
+}
+
+/// Пиксели звеньев ломаной по порядку; первое же `Overflow` прерывает
+/// растеризацию.
+pub fn rasterize_polyline<R: Rasterizer + ?Sized>(
+    vertices: &[Point],
+    rasterizer: &R,
+) -> Result<Vec<LinePixels>, Overflow> {
+    vertices
+        .windows(2)
+        .map(|w| rasterizer.rasterize(&[w[0], w[1]]))
+        .collect()
+}
+
+pub fn rasterize_polyline_to<R: Rasterizer + ?Sized, S: Sink>(
+    vertices: &[Point],
+    rasterizer: &R,
+    sink: &mut S,
+) -> Result<(), Overflow> {
+    vertices
+        .windows(2)
+        .try_for_each(|w| rasterizer.rasterize_to(&[w[0], w[1]], sink))
+}
@@ -0,0 +1,208 @@
+use crate::arc::{plot_circle_arc_pixels, ArcRange};
+use crate::integer::{self, Int, Overflow};
+use crate::point::{point, Point};
+use crate::sink::{self, Shaded, Sink};
+
+/// Отражает точку первого октанта во все восемь.
+pub fn plot_circle_pixels<S: Sink + ?Sized>(x: f32, y: f32, x_c: f32, y_c: f32, sink: &mut S) {
+    let (mut sx, mut sy) = (1.0, 1.0);
+    for _ in 0..4 {
+        let xsx = x * sx;
+        let ysy = y * sy;
+        sink.plot(point(x_c + xsx, y_c + ysy));
+        sink.plot(point(x_c + ysy, y_c + xsx));
+        sx *= -1.0;
+        sy *= -sx;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Circle {
+    center: Point,
+    radius: f32,
+    arc: ArcRange,
+    symmetric: bool,
+}
+
+impl Circle {
+    pub fn new(center: Point, radius: f32) -> Self {
+        Circle {
+            center,
+            radius,
+            arc: ArcRange::full(),
+            symmetric: true,
+        }
+    }
+
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius;
+    }
+
+    pub fn arc(&self) -> ArcRange {
+        self.arc
+    }
+
+    /// Рисуется только дуга в промежутке `arc`; по умолчанию кривая полная.
+    pub fn set_arc(&mut self, arc: ArcRange) {
+        self.arc = arc;
+    }
+
+    /// Без симметрии рисуются только точки, которые алгоритм строит сам, —
+    /// до отражения в остальные октанты. Дуга при этом не учитывается.
+    pub fn set_symmetric(&mut self, symmetric: bool) {
+        self.symmetric = symmetric;
+    }
+
+    fn plot<S: Sink + ?Sized>(&self, x: f32, y: f32, sink: &mut S) {
+        if !self.symmetric {
+            sink.plot(point(self.center.x + x, self.center.y + y));
+        } else if self.arc.is_full() {
+            plot_circle_pixels(x, y, self.center.x, self.center.y, sink);
+        } else {
+            plot_circle_arc_pixels(x, y, self.center.x, self.center.y, &self.arc, sink);
+        }
+    }
+
+    fn plot_shaded<S: Sink + ?Sized>(&self, x: f32, y: f32, i: f32, sink: &mut S) {
+        self.plot(x, y, &mut Shaded::new(sink, i));
+    }
+
+    pub fn draw_canonic(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_canonic_to(pixels))
+    }
+
+    pub fn draw_canonic_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        let sqr_radius = self.radius * self.radius;
+        let x_range = (self.radius / std::f32::consts::SQRT_2 + 1.0).min(self.radius);
+        let mut x = 0.0;
+        while x <= x_range {
+            // у дробного радиуса последний шаг может выйти за окружность
+            let y = (sqr_radius - x * x).max(0.0).sqrt();
+            self.plot(x, y.round(), sink);
+            x += 1.0;
+        }
+    }
+
+    pub fn draw_parametric(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_parametric_to(pixels))
+    }
+
+    pub fn draw_parametric_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        let t_range = std::f32::consts::FRAC_PI_4;
+        let t_step = self.radius.recip();
+        let mut t = 0.0;
+        while t <= t_range {
+            let x = self.radius * t.cos();
+            let y = self.radius * t.sin();
+            self.plot(x.round(), y.round(), sink);
+            t += t_step;
+        }
+    }
+
+    /// Считает на целых, поэтому дробный радиус округляется; так же
+    /// поступают `draw_midpoint` и `draw_bresenham_integer`.
+    pub fn draw_bresenham(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_bresenham_to(pixels))
+    }
+
+    pub fn draw_bresenham_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        let mut x = 0i32;
+        let mut y = self.radius.round() as i32;
+        let mut cap_delta: i32 = 2 * (1 - self.radius.round() as i32);
+        while y >= x {
+            self.plot(x as f32, y as f32, sink);
+            if cap_delta < 0 {
+                let delta = 2 * (cap_delta + y) - 1;
+                if delta <= 0 {
+                    x += 1;
+                    cap_delta += 2 * x + 1;
+                } else {
+                    x += 1;
+                    y -= 1;
+                    cap_delta += 2 * (x - y + 1);
+                }
+            } else if cap_delta > 0 {
+                let delta = 2 * (cap_delta - x) - 1;
+                if delta <= 0 {
+                    x += 1;
+                    y -= 1;
+                    cap_delta += 2 * (x - y + 1);
+                } else {
+                    y -= 1;
+                    cap_delta -= 2 * y + 1;
+                }
+            } else {
+                x += 1;
+                y -= 1;
+                cap_delta += 2 * (x - y + 1);
+            }
+        }
+    }
+
+    /// Тот же алгоритм Брезенхема, но только на целых `i32` с проверкой
+    /// переполнения.
+    pub fn draw_bresenham_integer(&self) -> Result<Vec<Point>, Overflow> {
+        let mut pixels = vec![];
+        self.draw_bresenham_integer_to(&mut pixels)?;
+        Ok(pixels)
+    }
+
+    /// При переполнении часть пикселей может быть уже отдана в `sink`.
+    pub fn draw_bresenham_integer_to<S: Sink + ?Sized>(
+        &self,
+        sink: &mut S,
+    ) -> Result<(), Overflow> {
+        let radius = i32::from_f32(self.radius).ok_or(Overflow)?;
+        integer::circle_octant(radius, |x, y| self.plot(x as f32, y as f32, sink))
+    }
+
+    pub fn draw_midpoint(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_midpoint_to(pixels))
+    }
+
+    pub fn draw_midpoint_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        let mut x = 0;
+        let mut y = self.radius.round() as i32;
+
+        let mut trial: i32 = 5 - 4 * self.radius.round() as i32;
+        while x <= y {
+            self.plot(x as f32, y as f32, sink);
+            x += 1;
+            if trial > 0 {
+                y -= 1;
+                trial -= 8 * y;
+            }
+            trial += 8 * x + 4;
+        }
+    }
+
+    /// Сглаживание по Ву: на каждый x первого октанта — два пикселя по обе
+    /// стороны от окружности, ослабленные (как в `line::wu`) пропорционально
+    /// расстоянию до неё.
+    pub fn draw_wu(&self) -> Vec<(Point, f32)> {
+        sink::collect(|pixels| self.draw_wu_to(pixels))
+    }
+
+    pub fn draw_wu_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        const M_I: f32 = 255.0;
+
+        let sqr_radius = self.radius * self.radius;
+        let x_range = self.radius / std::f32::consts::SQRT_2;
+        let mut x = 0.0;
+        while x <= x_range {
+            let y = (sqr_radius - x * x).sqrt();
+            let fpart = y.fract();
+            self.plot_shaded(x, y.floor(), M_I * fpart, sink);
+            self.plot_shaded(x, y.floor() + 1.0, M_I * (1.0 - fpart), sink);
+            x += 1.0;
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::str::FromStr;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgba(pub [u8; 4]);
+
+impl Rgba {
+    pub const TRANSPARENT: Self = Self([0, 0, 0, 0]);
+    pub const BLACK: Self = Self::from_rgb(0, 0, 0);
+    pub const WHITE: Self = Self::from_rgb(255, 255, 255);
+    pub const RED: Self = Self::from_rgb(255, 0, 0);
+    pub const GREEN: Self = Self::from_rgb(0, 255, 0);
+    pub const BLUE: Self = Self::from_rgb(0, 0, 255);
+
+    pub const fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self([r, g, b, 255])
+    }
+
+    pub const fn from_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self([r, g, b, a])
+    }
+
+    pub fn r(&self) -> u8 {
+        self.0[0]
+    }
+
+    pub fn g(&self) -> u8 {
+        self.0[1]
+    }
+
+    pub fn b(&self) -> u8 {
+        self.0[2]
+    }
+
+    pub fn a(&self) -> u8 {
+        self.0[3]
+    }
+
+    pub fn to_tuple(&self) -> (u8, u8, u8, u8) {
+        let [r, g, b, a] = self.0;
+        (r, g, b, a)
+    }
+}
+
+/// `#rrggbb`, `#rrggbbaa` или `r,g,b`.
+impl FromStr for Rgba {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("invalid color `{s}`, expected #rrggbb, #rrggbbaa or r,g,b");
+        if let Some(hex) = s.strip_prefix('#') {
+            if !matches!(hex.len(), 6 | 8) {
+                return Err(err());
+            }
+            let channel = |i: usize| {
+                hex.get(i..i + 2)
+                    .and_then(|c| u8::from_str_radix(c, 16).ok())
+                    .ok_or_else(err)
+            };
+            let a = if hex.len() == 8 { channel(6)? } else { 255 };
+            Ok(Rgba::from_rgba(channel(0)?, channel(2)?, channel(4)?, a))
+        } else {
+            let channels = s
+                .split(',')
+                .map(|c| c.trim().parse::<u8>().map_err(|_| err()))
+                .collect::<Result<Vec<_>, _>>()?;
+            match channels[..] {
+                [r, g, b] => Ok(Rgba::from_rgb(r, g, b)),
+                _ => Err(err()),
+            }
+        }
+    }
+}
+
+/// Смешивает цвет пикселя с цветом под ним. `i` — степень ослабления
+/// от 0 (чистый `pixel`) до 255 (чистый `canvas`).
+pub fn abate_color(pixel: Rgba, canvas: Rgba, i: f32) -> Rgba {
+    let t = i.clamp(0.0, 255.0) / 255.0;
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Rgba::from_rgba(
+        mix(pixel.r(), canvas.r()),
+        mix(pixel.g(), canvas.g()),
+        mix(pixel.b(), canvas.b()),
+        mix(pixel.a(), canvas.a()),
+    )
+}
+
+/// Цвет, который можно смешать с цветом под ним.
+pub trait Color: Copy {
+    /// То же, что `abate_color(self, canvas, i)`.
+    fn abate(self, canvas: Self, i: f32) -> Self;
+}
+
+impl Color for Rgba {
+    fn abate(self, canvas: Self, i: f32) -> Self {
+        abate_color(self, canvas, i)
+    }
+}
+
+#[cfg(feature = "ecolor")]
+impl Color for ecolor::Color32 {
+    fn abate(self, canvas: Self, i: f32) -> Self {
+        abate_color(self.into(), canvas.into(), i).into()
+    }
+}
+
+#[cfg(feature = "ecolor")]
+impl From<ecolor::Color32> for Rgba {
+    fn from(c: ecolor::Color32) -> Self {
+        Self(c.to_srgba_unmultiplied())
+    }
+}
+
+#[cfg(feature = "ecolor")]
+impl From<Rgba> for ecolor::Color32 {
+    fn from(c: Rgba) -> Self {
+        let [r, g, b, a] = c.0;
+        ecolor::Color32::from_rgba_unmultiplied(r, g, b, a)
+    }
+}
@@ -0,0 +1,176 @@
+//! Коника общего вида `A x² + B x y + C y² + D x + E y + F = 0`, обрезанная
+//! прямоугольником: так рисуются отрезки парабол и гипербол, которые
+//! уходят в бесконечность.
+
+use crate::implicit::{self, Bounds, Implicit};
+use crate::point::{point, Point};
+use crate::sink::{self, Sink};
+
+#[derive(Debug, Clone)]
+pub struct Conic {
+    center: Point,
+    coeffs: [f64; 6],
+    extent: Point,
+}
+
+/// Коэффициенты `[A, B, C, D, E, F]`.
+#[derive(Debug, Clone, Copy)]
+struct Quadratic([f64; 6]);
+
+impl Implicit for Quadratic {
+    fn value(&self, x: f64, y: f64) -> f64 {
+        let [a, b, c, d, e, f] = self.0;
+        a * x * x + b * x * y + c * y * y + d * x + e * y + f
+    }
+
+    fn gradient(&self, x: f64, y: f64) -> (f64, f64) {
+        let [a, b, c, d, e, _] = self.0;
+        (2.0 * a * x + b * y + d, b * x + 2.0 * c * y + e)
+    }
+}
+
+/// Корни `q2 t² + q1 t + q0 = 0`.
+fn roots(q2: f64, q1: f64, q0: f64) -> Vec<f64> {
+    if q2 == 0.0 {
+        return if q1 == 0.0 { vec![] } else { vec![-q0 / q1] };
+    }
+    let disc = q1 * q1 - 4.0 * q2 * q0;
+    if disc < 0.0 {
+        return vec![];
+    }
+    let root = disc.sqrt();
+    vec![(-q1 - root) / (2.0 * q2), (-q1 + root) / (2.0 * q2)]
+}
+
+impl Quadratic {
+    /// Точки кривой на прямой `(t, line(t))` или `(line(t), t)`: вдоль неё
+    /// `f` — квадратный трёхчлен, коэффициенты находятся по трём значениям.
+    fn on_line(&self, line: impl Fn(f64) -> (f64, f64)) -> Vec<(f64, f64)> {
+        let g = |t: f64| {
+            let (x, y) = line(t);
+            self.value(x, y)
+        };
+        let (g0, g1, g_1) = (g(0.0), g(1.0), g(-1.0));
+        roots((g1 + g_1) / 2.0 - g0, (g1 - g_1) / 2.0, g0)
+            .into_iter()
+            .map(line)
+            .collect()
+    }
+
+    /// Точки с вертикальной и горизонтальной касательной. Через них
+    /// проходит любая замкнутая ветвь, даже если она не касается границ
+    /// области.
+    fn extremes(&self) -> Vec<(f64, f64)> {
+        let [a, b, c, d, e, _] = self.0;
+        let mut res = vec![];
+        if c != 0.0 {
+            // df/dy = B x + 2 C y + E = 0
+            res.extend(self.on_line(|x| (x, -(b * x + e) / (2.0 * c))));
+        }
+        if a != 0.0 {
+            // df/dx = 2 A x + B y + D = 0
+            res.extend(self.on_line(|y| (-(b * y + d) / (2.0 * a), y)));
+        }
+        res
+    }
+}
+
+impl Conic {
+    /// Коника с коэффициентами `[A, B, C, D, E, F]` в координатах
+    /// относительно `center`, обрезанная прямоугольником `|x| <= extent.x`,
+    /// `|y| <= extent.y`.
+    pub fn new(center: Point, coeffs: [f64; 6], extent: Point) -> Self {
+        Conic {
+            center,
+            coeffs,
+            extent,
+        }
+    }
+
+    /// Гипербола `x²/a² - y²/b² = 1`, ветви вдоль оси x.
+    pub fn hyperbola(center: Point, radius: Point, extent: Point) -> Self {
+        Self::new(center, Self::hyperbola_coeffs(radius), extent)
+    }
+
+    /// Парабола `x² = 4 p y` с фокусом `(0, p)`.
+    pub fn parabola(center: Point, focus: f32, extent: Point) -> Self {
+        Self::new(
+            center,
+            [1.0, 0.0, 0.0, 0.0, -4.0 * focus as f64, 0.0],
+            extent,
+        )
+    }
+
+    /// Коэффициенты гиперболы, умноженные на `a² b²`, чтобы не были мелкими.
+    pub fn hyperbola_coeffs(radius: Point) -> [f64; 6] {
+        let (sqr_a, sqr_b) = ((radius.x as f64).powi(2), (radius.y as f64).powi(2));
+        [sqr_b, 0.0, -sqr_a, 0.0, 0.0, -sqr_a * sqr_b]
+    }
+
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    pub fn coeffs(&self) -> [f64; 6] {
+        self.coeffs
+    }
+
+    pub fn extent(&self) -> Point {
+        self.extent
+    }
+
+    pub fn set_coeffs(&mut self, coeffs: [f64; 6]) {
+        self.coeffs = coeffs;
+    }
+
+    pub fn set_extent(&mut self, extent: Point) {
+        self.extent = extent;
+    }
+
+    fn bounds(&self) -> Bounds {
+        let (w, h) = (self.extent.x.floor() as i64, self.extent.y.floor() as i64);
+        Bounds::new((-w, -h), (w, h))
+    }
+
+    fn plot<S: Sink + ?Sized>(&self, rel: Vec<(i64, i64)>, sink: &mut S) {
+        for (x, y) in rel {
+            sink.plot(point(self.center.x + x as f32, self.center.y + y as f32));
+        }
+    }
+
+    /// Обход кривой от точек пересечения с границей области и от точек с
+    /// вертикальной или горизонтальной касательной. У вырожденной коники
+    /// (пары пересекающихся прямых) в точке пересечения градиент нулевой,
+    /// обход срывается, и кривая строится перебором клеток.
+    pub fn draw_midpoint(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_midpoint_to(pixels))
+    }
+
+    pub fn draw_midpoint_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        let curve = Quadratic(self.coeffs);
+        let bounds = self.bounds();
+        let mut seeds = implicit::border_seeds(&curve, bounds);
+        seeds.extend(
+            curve
+                .extremes()
+                .into_iter()
+                .filter(|(x, y)| x.is_finite() && y.is_finite())
+                .map(|(x, y)| (x.round() as i64, y.round() as i64)),
+        );
+        let rel = implicit::trace(&curve, &seeds, bounds)
+            .unwrap_or_else(|| implicit::march(&curve, bounds));
+        self.plot(rel, sink);
+    }
+
+    /// Перебор всех клеток области.
+    pub fn draw_marching(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_marching_to(pixels))
+    }
+
+    pub fn draw_marching_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        self.plot(
+            implicit::march(&Quadratic(self.coeffs), self.bounds()),
+            sink,
+        );
+    }
+}
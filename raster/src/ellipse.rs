@@ -0,0 +1,624 @@
+use crate::arc::{plot_ellipse_arc_pixels, ArcRange};
+use crate::integer::{self, Int, Overflow};
+use crate::line::dda_to;
+use crate::point::{point, Point};
+use crate::sink::{self, Shaded, Sink};
+
+/// Отражает точку первой четверти во все четыре.
+pub fn plot_ellipse_pixels<S: Sink + ?Sized>(x: f32, y: f32, x_c: f32, y_c: f32, sink: &mut S) {
+    let (mut sx, mut sy) = (1.0, 1.0);
+    for _ in 0..4 {
+        let xsx = x * sx;
+        let ysy = y * sy;
+        sink.plot(point(x_c + xsx, y_c + ysy));
+        sx *= -1.0;
+        sy *= -sx;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Ellipse {
+    center: Point,
+    radius: Point,
+    arc: ArcRange,
+    symmetric: bool,
+}
+
+impl Ellipse {
+    pub fn new(center: Point, radius: Point) -> Self {
+        Ellipse {
+            center,
+            radius,
+            arc: ArcRange::full(),
+            symmetric: true,
+        }
+    }
+
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    pub fn radius(&self) -> Point {
+        self.radius
+    }
+
+    pub fn set_radius(&mut self, radius: Point) {
+        self.radius = radius;
+    }
+
+    pub fn arc(&self) -> ArcRange {
+        self.arc
+    }
+
+    /// Рисуется только дуга в промежутке `arc`; по умолчанию кривая полная.
+    pub fn set_arc(&mut self, arc: ArcRange) {
+        self.arc = arc;
+    }
+
+    /// Без симметрии рисуются только точки, которые алгоритм строит сам, —
+    /// до отражения в остальные четверти. Дуга при этом не учитывается.
+    pub fn set_symmetric(&mut self, symmetric: bool) {
+        self.symmetric = symmetric;
+    }
+
+    fn plot<S: Sink + ?Sized>(&self, x: f32, y: f32, sink: &mut S) {
+        if !self.symmetric {
+            sink.plot(point(self.center.x + x, self.center.y + y));
+        } else if self.arc.is_full() {
+            plot_ellipse_pixels(x, y, self.center.x, self.center.y, sink);
+        } else {
+            plot_ellipse_arc_pixels(x, y, self.center.x, self.center.y, &self.arc, sink);
+        }
+    }
+
+    fn plot_shaded<S: Sink + ?Sized>(&self, x: f32, y: f32, i: f32, sink: &mut S) {
+        self.plot(x, y, &mut Shaded::new(sink, i));
+    }
+
+    pub fn draw_canonic(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_canonic_to(pixels))
+    }
+
+    pub fn draw_canonic_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        let sqr_a = self.radius.x * self.radius.x;
+        let sqr_b = self.radius.y * self.radius.y;
+        let sqrt_coeff = self.radius.y / self.radius.x;
+        let hyp = (sqr_a + sqr_b).sqrt();
+        let x_range = sqr_a / hyp + 1.0;
+        let mut x = 0.0;
+        while x <= x_range {
+            let y = sqrt_coeff * (sqr_a - x * x).sqrt();
+            self.plot(x, y.round(), sink);
+            x += 1.0;
+        }
+
+        let sqrt_coeff = sqrt_coeff.recip();
+        let y_range = sqr_b / hyp + 1.0;
+        let mut y = 0.0;
+        while y <= y_range {
+            let x = sqrt_coeff * (sqr_b - y * y).sqrt();
+            self.plot(x.round(), y, sink);
+            y += 1.0;
+        }
+    }
+
+    pub fn draw_parametric(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_parametric_to(pixels))
+    }
+
+    pub fn draw_parametric_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        let t_range = std::f32::consts::FRAC_PI_2;
+        let t_step = self.radius.x.max(self.radius.y).recip();
+        let mut t = 0.0;
+        while t <= t_range {
+            let x = self.radius.x * t.cos();
+            let y = self.radius.y * t.sin();
+            self.plot(x.round(), y.round(), sink);
+            t += t_step;
+        }
+    }
+
+    pub fn draw_bresenham(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_bresenham_to(pixels))
+    }
+
+    pub fn draw_bresenham_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        let (ra, rb) = (self.radius.x.round() as i128, self.radius.y.round() as i128);
+
+        let sqr_a = ra * ra;
+        let sqr_b = rb * rb;
+
+        let mut x = 0;
+        let mut y = rb;
+        let mut cap_delta: i128 = sqr_b - sqr_a * (2 * rb - 1); // b^2(x+1)+a^2(y-1)^2-a^2b^2 в (0, b)
+
+        while y >= 0 {
+            self.plot(x as f32, y as f32, sink);
+
+            if cap_delta < 0 {
+                // знак функции в середине между горизонтальным и диагональным пикселем
+                let delta = 4 * cap_delta + sqr_a * (4 * y - 3);
+
+                if delta <= 0 {
+                    x += 1;
+                    cap_delta += sqr_b * (2 * x + 1);
+                } else {
+                    x += 1;
+                    y -= 1;
+                    cap_delta += 2 * x * sqr_b - 2 * y * sqr_a + sqr_a + sqr_b;
+                }
+            } else if cap_delta > 0 {
+                let delta = 4 * cap_delta - sqr_b * (4 * x + 3);
+
+                if delta <= 0 {
+                    x += 1;
+                    y -= 1;
+                    cap_delta += 2 * x * sqr_b - 2 * y * sqr_a + sqr_a + sqr_b;
+                } else {
+                    y -= 1;
+                    cap_delta += sqr_a * (-2 * y + 1);
+                }
+            } else {
+                x += 1;
+                y -= 1;
+                cap_delta += 2 * x * sqr_b - 2 * y * sqr_a + sqr_a + sqr_b;
+            }
+        }
+    }
+
+    /// Тот же алгоритм Брезенхема, но только на целых `i64` с проверкой
+    /// переполнения.
+    pub fn draw_bresenham_integer(&self) -> Result<Vec<Point>, Overflow> {
+        let mut pixels = vec![];
+        self.draw_bresenham_integer_to(&mut pixels)?;
+        Ok(pixels)
+    }
+
+    /// При переполнении часть пикселей может быть уже отдана в `sink`.
+    pub fn draw_bresenham_integer_to<S: Sink + ?Sized>(
+        &self,
+        sink: &mut S,
+    ) -> Result<(), Overflow> {
+        let radius = (
+            i64::from_f32(self.radius.x).ok_or(Overflow)?,
+            i64::from_f32(self.radius.y).ok_or(Overflow)?,
+        );
+        integer::ellipse_quadrant(radius, |x, y| self.plot(x as f32, y as f32, sink))
+    }
+
+    pub fn draw_midpoint(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_midpoint_to(pixels))
+    }
+
+    pub fn draw_midpoint_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        let (ra, rb) = (self.radius.x.round() as i128, self.radius.y.round() as i128);
+
+        // первый интервал — от (0, b), шаг по x
+        midpoint_arc(ra, rb, |x, y| self.plot(x as f32, y as f32, sink));
+        // второй — симметрично от (a, 0), шаг по y
+        midpoint_arc(rb, ra, |y, x| self.plot(x as f32, y as f32, sink));
+    }
+
+    /// Сглаживание по Ву. До точки с наклоном 1 шаг по x и пара пикселей по
+    /// вертикали, дальше — шаг по y и пара по горизонтали. Вырожденный
+    /// эллипс — отрезок вдоль ненулевой полуоси полной интенсивности.
+    pub fn draw_wu(&self) -> Vec<(Point, f32)> {
+        sink::collect(|pixels| self.draw_wu_to(pixels))
+    }
+
+    pub fn draw_wu_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        const M_I: f32 = 255.0;
+
+        let (a, b) = (self.radius.x, self.radius.y);
+        if a == 0.0 || b == 0.0 {
+            let mut t = 0.0;
+            while t <= a.max(b) {
+                let (x, y) = if a == 0.0 { (0.0, t) } else { (t, 0.0) };
+                self.plot(x, y, sink);
+                t += 1.0;
+            }
+            return;
+        }
+        let hyp = (a * a + b * b).sqrt();
+
+        let x_range = a * a / hyp;
+        let mut y_range = b * b / hyp;
+        // точку с наклоном 1 на целых x и y уже нарисовал первый цикл
+        if x_range.fract() == 0.0 && y_range.fract() == 0.0 {
+            y_range -= 1.0;
+        }
+        let mut x = 0.0;
+        while x <= x_range {
+            let y = b / a * (a * a - x * x).sqrt();
+            let fpart = y.fract();
+            self.plot_shaded(x, y.floor(), M_I * fpart, sink);
+            self.plot_shaded(x, y.floor() + 1.0, M_I * (1.0 - fpart), sink);
+            x += 1.0;
+        }
+
+        let mut y = 0.0;
+        while y <= y_range {
+            let x = a / b * (b * b - y * y).sqrt();
+            let fpart = x.fract();
+            self.plot_shaded(x.floor(), y, M_I * fpart, sink);
+            self.plot_shaded(x.floor() + 1.0, y, M_I * (1.0 - fpart), sink);
+            y += 1.0;
+        }
+    }
+}
+
+/// Дуга эллипса с полуосями `a`, `b` от `(0, b)` до точки с наклоном 1:
+/// шаг по x, y выбирается по знаку функции в средней точке.
+fn midpoint_arc(a: i128, b: i128, mut plot: impl FnMut(i128, i128)) {
+    let sqr_a = a * a;
+    let sqr_b = b * b;
+    let sqr_a2 = 2 * sqr_a;
+    let sqr_b2 = 2 * sqr_b;
+
+    let mut x = 0;
+    let mut y = b;
+    // 4 * f(1, b - 1/2)
+    let mut trial: i128 = 4 * sqr_b - 4 * sqr_a * b + sqr_a;
+    let mut dx = 0;
+    let mut dy = sqr_a2 * y;
+
+    while dx <= dy {
+        plot(x, y);
+        x += 1;
+        dx += sqr_b2;
+
+        if trial >= 0 {
+            y -= 1;
+            dy -= sqr_a2;
+            trial -= 4 * dy;
+        }
+
+        trial += 4 * (dx + sqr_b);
+    }
+}
+
+/// Эллипс с полуосями `radius`, повёрнутый на `angle` градусов вокруг центра
+/// (в ту же сторону, что и `Point::rotate`).
+#[derive(Debug, Clone)]
+pub struct RotatedEllipse {
+    center: Point,
+    radius: Point,
+    angle: f32,
+}
+
+/// Неявное уравнение `A x² + B x y + C y² = F` повёрнутого эллипса
+/// с центром в начале координат.
+#[derive(Debug, Clone, Copy)]
+struct Conic {
+    a: f64,
+    b: f64,
+    c: f64,
+    f: f64,
+}
+
+impl Conic {
+    fn new(radius: Point, angle: f32) -> Self {
+        let (ra, rb) = (radius.x as f64, radius.y as f64);
+        let (sin, cos) = (angle as f64).to_radians().sin_cos();
+        let (sqr_a, sqr_b) = (ra * ra, rb * rb);
+        Conic {
+            a: sqr_a * sin * sin + sqr_b * cos * cos,
+            b: 2.0 * (sqr_b - sqr_a) * sin * cos,
+            c: sqr_a * cos * cos + sqr_b * sin * sin,
+            f: sqr_a * sqr_b,
+        }
+    }
+
+    /// Точка кривой с наибольшим x.
+    fn x_extreme(&self) -> (f64, f64) {
+        let det = self.a * self.c - self.b * self.b / 4.0;
+        let x = (self.c * self.f / det).sqrt();
+        (x, -self.b * x / (2.0 * self.c))
+    }
+
+    /// Точка кривой, в которой внешняя нормаль направлена по `(u, v)`.
+    fn tangent_point(&self, u: f64, v: f64) -> (f64, f64) {
+        let (x, y) = (2.0 * self.c * u - self.b * v, 2.0 * self.a * v - self.b * u);
+        let scale = (self.f / (self.a * x * x + self.b * x * y + self.c * y * y)).sqrt();
+        (x * scale, y * scale)
+    }
+
+    /// Точка кривой с наибольшим y.
+    fn y_extreme(&self) -> (f64, f64) {
+        let det = self.a * self.c - self.b * self.b / 4.0;
+        let y = (self.a * self.f / det).sqrt();
+        (-self.b * y / (2.0 * self.a), y)
+    }
+}
+
+/// Те же коэффициенты, умноженные на общий множитель и округлённые до целых,
+/// чтобы решения принимались в целочисленной арифметике. Наибольший из `A`
+/// и `C` становится `2^48`: так у тонкого эллипса меньший коэффициент и `B`
+/// сохраняют почти всю точность `f64`, а значения в удвоенных координатах
+/// помещаются в `i128` до радиусов порядка `10^9`.
+#[derive(Debug, Clone, Copy)]
+struct IntConic {
+    a: i128,
+    b: i128,
+    c: i128,
+    f: i128,
+}
+
+impl IntConic {
+    fn new(conic: &Conic) -> Self {
+        let scale = (1u64 << 48) as f64 / conic.a.max(conic.c);
+        let int = |v: f64| (v * scale).round() as i128;
+        IntConic {
+            a: int(conic.a),
+            b: int(conic.b),
+            c: int(conic.c),
+            f: int(conic.f),
+        }
+    }
+
+    /// `4 (A x² + B x y + C y² - F)` в точке `(x / 2, y / 2)`: в удвоенных
+    /// координатах середины между пикселями целые.
+    fn eval2(&self, x: i128, y: i128) -> i128 {
+        self.a * x * x + self.b * x * y + self.c * y * y - 4 * self.f
+    }
+
+    /// Производная в направлении `(dx, dy)` с точностью до положительного
+    /// множителя.
+    fn slope2(&self, x: i128, y: i128, (dx, dy): (i128, i128)) -> i128 {
+        let gx = 2 * self.a * x + self.b * y;
+        let gy = self.b * x + 2 * self.c * y;
+        gx * dx + gy * dy
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Decision {
+    /// Знак функции в середине между кандидатами.
+    Midpoint,
+    /// Меньшая по модулю невязка в самих кандидатах.
+    Bresenham,
+}
+
+/// Дуга от пикселя `from` до `to` (не включая его), на которой касательная
+/// ближе к вертикали (`y_major`) или к горизонтали. Между крайними точками
+/// и точками с наклоном ±1 кривая монотонна, так что по главной оси шаг
+/// делается всегда, а по второй выбирается.
+fn trace_arc(
+    conic: &IntConic,
+    from: (i128, i128),
+    to: (i128, i128),
+    y_major: bool,
+    decision: Decision,
+    mut plot: impl FnMut(i128, i128),
+) {
+    let (sx, sy) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+    let (main, side) = if y_major {
+        ((0, sy), (sx, 0))
+    } else {
+        ((sx, 0), (0, sy))
+    };
+    // обход против часовой стрелки (при y вверх), внутренность слева:
+    // ведёт ли шаг по второй оси внутрь эллипса
+    let inward = if y_major { sx * sy < 0 } else { sx * sy > 0 };
+    let (mut x, mut y) = from;
+    while (x, y) != to {
+        plot(x, y);
+        let step = if x == to.0 {
+            (0, sy)
+        } else if y == to.1 {
+            (sx, 0)
+        } else {
+            let (mid_x, mid_y) = (2 * x + main.0 + sx, 2 * y + main.1 + sy);
+            // снаружи середина может оказаться и перед кривой, и за
+            // противоположной стороной тонкого эллипса; вдоль второй оси
+            // функция — парабола, и знак производной показывает, в какую
+            // сторону от середины эллипс
+            let ahead = || conic.slope2(mid_x, mid_y, side) < 0;
+            let take_diagonal = match decision {
+                Decision::Midpoint => {
+                    if conic.eval2(mid_x, mid_y) <= 0 {
+                        !inward
+                    } else {
+                        ahead()
+                    }
+                }
+                Decision::Bresenham => {
+                    let f_main = conic.eval2(2 * (x + main.0), 2 * (y + main.1));
+                    let f_diagonal = conic.eval2(2 * (x + sx), 2 * (y + sy));
+                    let crossing = (f_main > 0) != (f_diagonal > 0);
+                    if crossing && (f_main > 0) == inward {
+                        f_diagonal.abs() < f_main.abs()
+                    } else if f_main > 0 && !crossing {
+                        ahead()
+                    } else {
+                        // оба внутри или между ними прошла противоположная
+                        // сторона тонкого эллипса: своя позади главного
+                        // кандидата, если шаг ведёт внутрь, иначе впереди
+                        // диагонального
+                        !inward
+                    }
+                }
+            };
+            if take_diagonal {
+                (sx, sy)
+            } else {
+                main
+            }
+        };
+        x += step.0;
+        y += step.1;
+    }
+}
+
+impl RotatedEllipse {
+    pub fn new(center: Point, radius: Point, angle: f32) -> Self {
+        RotatedEllipse {
+            center,
+            radius,
+            angle,
+        }
+    }
+
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    pub fn radius(&self) -> Point {
+        self.radius
+    }
+
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    pub fn set_radius(&mut self, radius: Point) {
+        self.radius = radius;
+    }
+
+    pub fn set_angle(&mut self, angle: f32) {
+        self.angle = angle;
+    }
+
+    fn plot<S: Sink + ?Sized>(&self, x: f32, y: f32, sink: &mut S) {
+        sink.plot(point(self.center.x + x, self.center.y + y));
+    }
+
+    /// Вырожденный эллипс — отрезок вдоль ненулевой полуоси; `false`, если
+    /// эллипс не вырожден и ничего не нарисовано.
+    fn degenerate<S: Sink + ?Sized>(&self, sink: &mut S) -> bool {
+        if self.radius.x >= 1.0 && self.radius.y >= 1.0 {
+            return false;
+        }
+        let mut end = point(self.radius.x.max(self.radius.y), 0.0);
+        if self.radius.y > self.radius.x {
+            end = point(0.0, end.x);
+        }
+        end.rotate(self.angle, Point::ZERO);
+        let ends = [self.center - end, self.center + end].map(Point::round);
+        dda_to(&ends, sink);
+        true
+    }
+
+    /// Из неявного уравнения `y` выражается через `x` для каждого столбца,
+    /// а `x` через `y` для каждой строки, чтобы крутые участки не рвались.
+    pub fn draw_canonic(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_canonic_to(pixels))
+    }
+
+    pub fn draw_canonic_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        if self.degenerate(sink) {
+            return;
+        }
+        let conic = Conic::new(self.radius, self.angle);
+        let (a, b, c, f) = (
+            conic.a as f32,
+            conic.b as f32,
+            conic.c as f32,
+            conic.f as f32,
+        );
+
+        let x_range = conic.x_extreme().0 as f32;
+        let mut x = -x_range.floor();
+        while x <= x_range {
+            let disc = (b * b - 4.0 * a * c) * x * x + 4.0 * c * f;
+            let root = disc.max(0.0).sqrt();
+            self.plot(x, ((-b * x + root) / (2.0 * c)).round(), sink);
+            self.plot(x, ((-b * x - root) / (2.0 * c)).round(), sink);
+            x += 1.0;
+        }
+
+        let y_range = conic.y_extreme().1 as f32;
+        let mut y = -y_range.floor();
+        while y <= y_range {
+            let disc = (b * b - 4.0 * a * c) * y * y + 4.0 * a * f;
+            let root = disc.max(0.0).sqrt();
+            self.plot(((-b * y + root) / (2.0 * a)).round(), y, sink);
+            self.plot(((-b * y - root) / (2.0 * a)).round(), y, sink);
+            y += 1.0;
+        }
+    }
+
+    /// Точки неповёрнутого эллипса поворачиваются и округляются.
+    pub fn draw_parametric(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_parametric_to(pixels))
+    }
+
+    pub fn draw_parametric_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        if self.degenerate(sink) {
+            return;
+        }
+        let t_range = std::f32::consts::TAU;
+        let t_step = self.radius.x.max(self.radius.y).recip();
+        let mut t = 0.0;
+        while t < t_range {
+            let mut p = point(self.radius.x * t.cos(), self.radius.y * t.sin());
+            p.rotate(self.angle, Point::ZERO);
+            self.plot(p.x.round(), p.y.round(), sink);
+            t += t_step;
+        }
+    }
+
+    /// Две дуги — от точки с наибольшим x до точки с наибольшим y и от неё
+    /// до точки с наименьшим x, каждая делится точкой с наклоном ±1; вторая
+    /// половина симметрична относительно центра.
+    fn draw_traced<S: Sink + ?Sized>(&self, decision: Decision, sink: &mut S) {
+        if self.degenerate(sink) {
+            return;
+        }
+        let conic = Conic::new(self.radius, self.angle);
+        let int_conic = IntConic::new(&conic);
+        let round = |(x, y): (f64, f64)| (x.round() as i128, y.round() as i128);
+        let right = round(conic.x_extreme());
+        let top = round(conic.y_extreme());
+        let left = (-right.0, -right.1);
+        let right_top = round(conic.tangent_point(1.0, 1.0));
+        let top_left = round(conic.tangent_point(-1.0, 1.0));
+        for (from, to, y_major) in [
+            (right, right_top, true),
+            (right_top, top, false),
+            (top, top_left, false),
+            (top_left, left, true),
+        ] {
+            trace_arc(&int_conic, from, to, y_major, decision, |x, y| {
+                self.plot(x as f32, y as f32, sink);
+                self.plot(-x as f32, -y as f32, sink);
+            });
+        }
+    }
+
+    /// Обход кривой с выбором пикселя по знаку в средней точке.
+    pub fn draw_midpoint(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_midpoint_to(pixels))
+    }
+
+    pub fn draw_midpoint_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        self.draw_traced(Decision::Midpoint, sink);
+    }
+
+    /// Обход кривой с выбором пикселя с меньшей невязкой.
+    pub fn draw_bresenham(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_bresenham_to(pixels))
+    }
+
+    pub fn draw_bresenham_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        self.draw_traced(Decision::Bresenham, sink);
+    }
+}
+
+/// Контур эллипса с полуосями `radius`, повёрнутого на `angle` градусов
+/// вокруг `center`, для отрисовки средствами egui.
+#[cfg(feature = "emath")]
+pub fn rotated_ellipse_path(
+    center: emath::Pos2,
+    radius: emath::Vec2,
+    angle: f32,
+) -> Vec<emath::Pos2> {
+    let rot = emath::Rot2::from_angle(angle.to_radians());
+    let n = (radius.x.max(radius.y) as usize).clamp(16, 1024);
+    (0..n)
+        .map(|i| {
+            let t = std::f32::consts::TAU * i as f32 / n as f32;
+            center + rot * emath::vec2(radius.x * t.cos(), radius.y * t.sin())
+        })
+        .collect()
+}
@@ -0,0 +1,158 @@
+use crate::color::{abate_color, Rgba};
+use crate::line::LinePixels;
+use crate::paint::{Paint, PaintBrush};
+use crate::point::Point;
+use crate::sink::{Brush, Image};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Framebuffer {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgba>,
+}
+
+impl Framebuffer {
+    pub fn new(width: usize, height: usize, background: Rgba) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[Rgba] {
+        &self.pixels
+    }
+
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            None
+        } else {
+            Some(y as usize * self.width + x as usize)
+        }
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<Rgba> {
+        self.index(x, y).map(|i| self.pixels[i])
+    }
+
+    /// Пиксели за пределами буфера молча отбрасываются.
+    pub fn set(&mut self, x: i64, y: i64, color: Rgba) {
+        if let Some(i) = self.index(x, y) {
+            self.pixels[i] = color;
+        }
+    }
+
+    pub fn plot(&mut self, p: Point, color: Rgba) {
+        self.set(p.x.round() as i64, p.y.round() as i64, color);
+    }
+
+    pub fn plot_all(&mut self, points: &[Point], color: Rgba) {
+        points.iter().for_each(|&p| self.plot(p, color));
+    }
+
+    /// Смешивает `color` с текущим пикселем так же, как `abate_color`.
+    pub fn blend(&mut self, x: i64, y: i64, color: Rgba, i: f32) {
+        if let Some(idx) = self.index(x, y) {
+            self.pixels[idx] = abate_color(color, self.pixels[idx], i);
+        }
+    }
+
+    pub fn plot_intensity(&mut self, p: Point, color: Rgba, i: f32) {
+        self.blend(p.x.round() as i64, p.y.round() as i64, color, i);
+    }
+
+    pub fn plot_all_intensity(&mut self, points: &[(Point, f32)], color: Rgba) {
+        points
+            .iter()
+            .for_each(|&(p, i)| self.plot_intensity(p, color, i));
+    }
+
+    pub fn plot_line(&mut self, pixels: &LinePixels, color: Rgba) {
+        pixels.plot_to(&mut self.brush(color));
+    }
+
+    /// Растеризаторы рисуют прямо в буфер через `Sink`.
+    pub fn brush(&mut self, color: Rgba) -> Brush<'_, Self> {
+        Brush::new(self, color)
+    }
+
+    /// Как `brush`, но цвет каждого пикселя берётся из `paint`.
+    pub fn paint_brush<'a>(&'a mut self, paint: &'a Paint) -> PaintBrush<'a, Self> {
+        PaintBrush::new(self, paint)
+    }
+
+    pub fn fill(&mut self, color: Rgba) {
+        self.pixels.fill(color);
+    }
+
+    /// Бинарный PPM (P6), альфа-канал отбрасывается.
+    pub fn write_ppm(&self, mut w: impl Write) -> io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        let bytes = self
+            .pixels
+            .iter()
+            .flat_map(|c| [c.r(), c.g(), c.b()])
+            .collect::<Vec<u8>>();
+        w.write_all(&bytes)
+    }
+
+    pub fn write_png(&self, w: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let bytes = self.pixels.iter().flat_map(|c| c.0).collect::<Vec<u8>>();
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&bytes))
+            .map_err(io::Error::other)
+    }
+
+    pub fn save_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        self.write_ppm(&mut w)?;
+        w.flush()
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        self.write_png(&mut w)?;
+        w.flush()
+    }
+
+    /// Формат выбирается по расширению: `.ppm` или `.png`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => self.save_ppm(path),
+            Some("png") => self.save_png(path),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unsupported image format: {}", path.display()),
+            )),
+        }
+    }
+}
+
+impl Image for Framebuffer {
+    type Color = Rgba;
+
+    fn get(&self, x: i64, y: i64) -> Option<Rgba> {
+        Framebuffer::get(self, x, y)
+    }
+
+    fn set(&mut self, x: i64, y: i64, color: Rgba) {
+        Framebuffer::set(self, x, y, color);
+    }
+}
@@ -0,0 +1,255 @@
+//! Растеризация кривых, заданных неявно: `f(x, y) = 0`. Основной способ —
+//! обход кривой от пикселя к пикселю с выбором следующего по знаку `f` в
+//! средней точке между кандидатами, как в алгоритме средней точки, только
+//! направление шага берётся из градиента. Если обход срывается (градиент
+//! обнулился или бесконечен, кривая ушла дальше пикселя), остаётся
+//! перебор клеток: пиксель закрашивается, если в углах его клетки `f`
+//! меняет знак.
+
+use std::collections::HashSet;
+
+/// Кривая `value(x, y) = 0`; координаты относительно центра фигуры.
+pub trait Implicit {
+    fn value(&self, x: f64, y: f64) -> f64;
+
+    fn gradient(&self, x: f64, y: f64) -> (f64, f64);
+
+    /// Оценка расстояния до кривой `|f| / |grad f|`; бесконечность, если
+    /// градиент вырожден.
+    fn distance(&self, x: f64, y: f64) -> f64 {
+        let (gx, gy) = self.gradient(x, y);
+        let grad = gx.hypot(gy);
+        if grad > 0.0 && grad.is_finite() {
+            self.value(x, y).abs() / grad
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+/// Прямоугольник пикселей, границы включительно.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: (i64, i64),
+    pub max: (i64, i64),
+}
+
+impl Bounds {
+    pub fn new(min: (i64, i64), max: (i64, i64)) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, (x, y): (i64, i64)) -> bool {
+        (self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+    }
+
+    /// Пиксели границы по кругу: верхняя сторона, правая, нижняя, левая.
+    pub fn perimeter(&self) -> Vec<(i64, i64)> {
+        let ((x0, y0), (x1, y1)) = (self.min, self.max);
+        let mut res = (x0..=x1).map(|x| (x, y0)).collect::<Vec<_>>();
+        res.extend((y0 + 1..=y1).map(|y| (x1, y)));
+        if y1 > y0 {
+            res.extend((x0..x1).rev().map(|x| (x, y1)));
+        }
+        if x1 > x0 {
+            res.extend((y0 + 1..y1).rev().map(|y| (x0, y)));
+        }
+        res
+    }
+}
+
+/// Дальше этого от кривой обход считается сорвавшимся.
+const MAX_DRIFT: f64 = 1.0;
+
+fn at(curve: &impl Implicit, (x, y): (i64, i64)) -> f64 {
+    curve.value(x as f64, y as f64)
+}
+
+fn distance(curve: &impl Implicit, (x, y): (i64, i64)) -> f64 {
+    curve.distance(x as f64, y as f64)
+}
+
+/// Лежит ли кривая не дальше `MAX_DRIFT` от пикселя. Оценка по градиенту
+/// у острых углов завышена, поэтому, если она велика, ищется смена знака
+/// на квадрате вокруг пикселя.
+fn near(curve: &impl Implicit, p: (i64, i64)) -> bool {
+    if distance(curve, p) <= MAX_DRIFT {
+        return true;
+    }
+    let (x, y) = (p.0 as f64, p.1 as f64);
+    let r = MAX_DRIFT;
+    let values = [
+        (-r, -r),
+        (0.0, -r),
+        (r, -r),
+        (r, 0.0),
+        (r, r),
+        (0.0, r),
+        (-r, r),
+        (-r, 0.0),
+        (0.0, 0.0),
+    ]
+    .map(|(dx, dy)| curve.value(x + dx, y + dy));
+    values.iter().any(|&v| v <= 0.0) && values.iter().any(|&v| v >= 0.0)
+}
+
+/// Следующий пиксель после `p` при касательной `t`. Шаг по главной оси
+/// касательной делается всегда, по второй — если кривая проходит между
+/// кандидатами дальше средней точки. Если кривая между кандидатами не
+/// проходит (сильно изогнута), берётся более близкий к ней.
+fn step(curve: &impl Implicit, p: (i64, i64), (tx, ty): (f64, f64)) -> (i64, i64) {
+    let sign = |v: f64| {
+        if v > 0.0 {
+            1
+        } else if v < 0.0 {
+            -1
+        } else {
+            0
+        }
+    };
+    let (main, side) = if tx.abs() >= ty.abs() {
+        ((sign(tx), 0), (0, sign(ty)))
+    } else {
+        ((0, sign(ty)), (sign(tx), 0))
+    };
+    let straight = (p.0 + main.0, p.1 + main.1);
+    let closest = |candidates: &[(i64, i64)]| {
+        candidates
+            .iter()
+            .copied()
+            .min_by(|&a, &b| distance(curve, a).total_cmp(&distance(curve, b)))
+            .unwrap()
+    };
+    if side == (0, 0) {
+        // касательная вдоль оси: кривая может уйти в любую сторону
+        let (px, py) = (main.1.abs(), main.0.abs());
+        return closest(&[
+            straight,
+            (straight.0 + px, straight.1 + py),
+            (straight.0 - px, straight.1 - py),
+        ]);
+    }
+    let diagonal = (straight.0 + side.0, straight.1 + side.1);
+    let (f_straight, f_diagonal) = (at(curve, straight), at(curve, diagonal));
+    if (f_straight > 0.0) != (f_diagonal > 0.0) {
+        let mid = curve.value(
+            (straight.0 + diagonal.0) as f64 / 2.0,
+            (straight.1 + diagonal.1) as f64 / 2.0,
+        );
+        if (mid > 0.0) == (f_diagonal > 0.0) {
+            straight
+        } else {
+            diagonal
+        }
+    } else {
+        closest(&[straight, diagonal])
+    }
+}
+
+/// Обход от `from` в одну сторону, пока кривая не выйдет за `bounds` или
+/// не придёт в уже пройденный пиксель. `None`, если обход сорвался.
+fn walk(
+    curve: &impl Implicit,
+    from: (i64, i64),
+    forward: bool,
+    bounds: Bounds,
+    visited: &mut HashSet<(i64, i64)>,
+    pixels: &mut Vec<(i64, i64)>,
+) -> Option<()> {
+    let mut p = from;
+    let mut prev: Option<(f64, f64)> = None;
+    loop {
+        let (gx, gy) = curve.gradient(p.0 as f64, p.1 as f64);
+        if !(gx.is_finite() && gy.is_finite()) || (gx == 0.0 && gy == 0.0) {
+            return None;
+        }
+        let mut t = if forward { (-gy, gx) } else { (gy, -gx) };
+        if prev.is_some_and(|(px, py)| px * t.0 + py * t.1 < 0.0) {
+            t = (-t.0, -t.1);
+        }
+        let next = step(curve, p, t);
+        if !bounds.contains(next) || visited.contains(&next) {
+            return Some(());
+        }
+        if !near(curve, next) {
+            return None;
+        }
+        visited.insert(next);
+        pixels.push(next);
+        prev = Some(t);
+        p = next;
+    }
+}
+
+/// Обход кривой в обе стороны от каждой затравки. Затравки должны лежать
+/// не дальше пикселя от кривой; уже пройденные пропускаются. `None`, если
+/// обход сорвался, — тогда кривую рисует `march`.
+pub fn trace(
+    curve: &impl Implicit,
+    seeds: &[(i64, i64)],
+    bounds: Bounds,
+) -> Option<Vec<(i64, i64)>> {
+    let mut visited = HashSet::new();
+    let mut pixels = vec![];
+    for &seed in seeds {
+        if !bounds.contains(seed) || visited.contains(&seed) {
+            continue;
+        }
+        if !near(curve, seed) {
+            return None;
+        }
+        visited.insert(seed);
+        pixels.push(seed);
+        walk(curve, seed, true, bounds, &mut visited, &mut pixels)?;
+        walk(curve, seed, false, bounds, &mut visited, &mut pixels)?;
+    }
+    Some(pixels)
+}
+
+/// Пиксели `bounds`, через клетки которых проходит кривая: в углах клетки
+/// `f` разного знака или ноль. Работает при любых особенностях кривой, но
+/// перебирает всю площадь, а контур получается 4-связным.
+pub fn march(curve: &impl Implicit, bounds: Bounds) -> Vec<(i64, i64)> {
+    let ((x0, y0), (x1, y1)) = (bounds.min, bounds.max);
+    let corner = |x: i64, y: i64| curve.value(x as f64 - 0.5, y as f64 - 0.5);
+    // значения в углах предыдущей строки клеток
+    let mut top = (x0..=x1 + 1).map(|x| corner(x, y0)).collect::<Vec<_>>();
+    let mut pixels = vec![];
+    for y in y0..=y1 {
+        let bottom = (x0..=x1 + 1).map(|x| corner(x, y + 1)).collect::<Vec<_>>();
+        for (i, x) in (x0..=x1).enumerate() {
+            let corners = [top[i], top[i + 1], bottom[i], bottom[i + 1]];
+            let (lo, hi) = corners
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                    (lo.min(v), hi.max(v))
+                });
+            if lo <= 0.0 && hi >= 0.0 {
+                pixels.push((x, y));
+            }
+        }
+        top = bottom;
+    }
+    pixels
+}
+
+/// Затравки на границе `bounds`: из каждой пары соседних пикселей, между
+/// которыми `f` меняет знак, — более близкий к кривой.
+pub fn border_seeds(curve: &impl Implicit, bounds: Bounds) -> Vec<(i64, i64)> {
+    let perimeter = bounds.perimeter();
+    let mut seeds = vec![];
+    for (i, &p) in perimeter.iter().enumerate() {
+        let q = perimeter[(i + 1) % perimeter.len()];
+        let (fp, fq) = (at(curve, p), at(curve, q));
+        if fp == 0.0 {
+            seeds.push(p);
+        } else if (fp > 0.0) != (fq > 0.0) && fq != 0.0 {
+            seeds.push(if distance(curve, p) <= distance(curve, q) {
+                p
+            } else {
+                q
+            });
+        }
+    }
+    seeds
+}
@@ -0,0 +1,269 @@
+//! Алгоритмы Брезенхема только на целых числах: отрезок, окружность и
+//! эллипс. Тип координат — любой знаковый целый (`i16` … `i128`), вся
+//! арифметика с проверкой переполнения: вместо неверных пикселей
+//! возвращается `Overflow`.
+
+use crate::point::{point, Point};
+use std::fmt;
+
+/// Знаковый целый тип координат.
+pub trait Int: Copy + Ord + fmt::Debug {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn from_i8(v: i8) -> Self;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_abs(self) -> Option<Self>;
+    fn signum(self) -> Self;
+    fn to_f32(self) -> f32;
+    /// Ближайшее целое; `None`, если оно не помещается в тип.
+    fn from_f32(v: f32) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {$(
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn from_i8(v: i8) -> Self {
+                v.into()
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                <$t>::checked_abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+
+            fn to_f32(self) -> f32 {
+                self as f32
+            }
+
+            fn from_f32(v: f32) -> Option<Self> {
+                let v = v.round();
+                // MIN — степень двойки, в f32 точна; MAX округляется вверх
+                let min = <$t>::MIN as f32;
+                (v >= min && v < -min).then(|| v as $t)
+            }
+        }
+    )*};
+}
+
+impl_int!(i16, i32, i64, i128);
+
+/// Промежуточное значение не поместилось в тип координат.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("integer overflow")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+fn add<T: Int>(a: T, b: T) -> Result<T, Overflow> {
+    a.checked_add(b).ok_or(Overflow)
+}
+
+fn sub<T: Int>(a: T, b: T) -> Result<T, Overflow> {
+    a.checked_sub(b).ok_or(Overflow)
+}
+
+fn mul<T: Int>(a: T, b: T) -> Result<T, Overflow> {
+    a.checked_mul(b).ok_or(Overflow)
+}
+
+fn int<T: Int>(v: i8) -> T {
+    T::from_i8(v)
+}
+
+/// Отрезок от `from` до `to` включительно.
+pub fn line<T: Int>(from: (T, T), to: (T, T)) -> Result<Vec<(T, T)>, Overflow> {
+    let (dx, dy) = (sub(to.0, from.0)?, sub(to.1, from.1)?);
+    let (sx, sy) = (dx.signum(), dy.signum());
+    let (dx, dy) = (
+        dx.checked_abs().ok_or(Overflow)?,
+        dy.checked_abs().ok_or(Overflow)?,
+    );
+    let swapped = dy > dx;
+    let (dx, dy) = if swapped { (dy, dx) } else { (dx, dy) };
+    let (two_dx, two_dy) = (add(dx, dx)?, add(dy, dy)?);
+
+    let (mut x, mut y) = from;
+    let mut e = sub(two_dy, dx)?;
+    let mut res = vec![];
+    let mut i = T::ZERO;
+    loop {
+        res.push((x, y));
+        if i == dx {
+            return Ok(res);
+        }
+        if e >= T::ZERO {
+            if swapped {
+                x = add(x, sx)?;
+            } else {
+                y = add(y, sy)?;
+            }
+            e = sub(e, two_dx)?;
+        }
+        if swapped {
+            y = add(y, sy)?;
+        } else {
+            x = add(x, sx)?;
+        }
+        e = add(e, two_dy)?;
+        i = add(i, T::ONE)?;
+    }
+}
+
+/// Октант окружности от `(0, r)` до диагонали, центр в начале координат.
+pub fn circle_octant<T: Int>(radius: T, mut plot: impl FnMut(T, T)) -> Result<(), Overflow> {
+    let (one, two) = (T::ONE, int::<T>(2));
+    let mut x = T::ZERO;
+    let mut y = radius;
+    let mut cap_delta = mul(two, sub(one, radius)?)?;
+    // 2 (x - y + 1) после шага по диагонали
+    let diagonal = |x: T, y: T| mul(two, add(sub(x, y)?, one)?);
+    while y >= x {
+        plot(x, y);
+        if cap_delta < T::ZERO {
+            let delta = sub(mul(two, add(cap_delta, y)?)?, one)?;
+            x = add(x, one)?;
+            if delta <= T::ZERO {
+                cap_delta = add(cap_delta, add(mul(two, x)?, one)?)?;
+            } else {
+                y = sub(y, one)?;
+                cap_delta = add(cap_delta, diagonal(x, y)?)?;
+            }
+        } else if cap_delta > T::ZERO {
+            let delta = sub(mul(two, sub(cap_delta, x)?)?, one)?;
+            y = sub(y, one)?;
+            if delta <= T::ZERO {
+                x = add(x, one)?;
+                cap_delta = add(cap_delta, diagonal(x, y)?)?;
+            } else {
+                cap_delta = sub(cap_delta, add(mul(two, y)?, one)?)?;
+            }
+        } else {
+            x = add(x, one)?;
+            y = sub(y, one)?;
+            cap_delta = add(cap_delta, diagonal(x, y)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Четверть эллипса от `(0, b)` до `(a, 0)`, центр в начале координат.
+pub fn ellipse_quadrant<T: Int>(
+    (a, b): (T, T),
+    mut plot: impl FnMut(T, T),
+) -> Result<(), Overflow> {
+    let (one, two, three, four) = (T::ONE, int::<T>(2), int::<T>(3), int::<T>(4));
+    let sqr_a = mul(a, a)?;
+    let sqr_b = mul(b, b)?;
+    // b²(x + 1)² + a²(y - 1)² - a²b² в (0, b)
+    let mut cap_delta = sub(sqr_b, mul(sqr_a, sub(mul(two, b)?, one)?)?)?;
+    let mut x = T::ZERO;
+    let mut y = b;
+    // 2x b² - 2y a² + a² + b² после шага по диагонали
+    let diagonal = |x: T, y: T| {
+        let xb = mul(mul(two, x)?, sqr_b)?;
+        let ya = mul(mul(two, y)?, sqr_a)?;
+        add(add(sub(xb, ya)?, sqr_a)?, sqr_b)
+    };
+    while y >= T::ZERO {
+        plot(x, y);
+        if cap_delta < T::ZERO {
+            // знак функции в середине между горизонтальным и диагональным пикселем
+            let delta = add(
+                mul(four, cap_delta)?,
+                mul(sqr_a, sub(mul(four, y)?, three)?)?,
+            )?;
+            x = add(x, one)?;
+            if delta <= T::ZERO {
+                cap_delta = add(cap_delta, mul(sqr_b, add(mul(two, x)?, one)?)?)?;
+            } else {
+                y = sub(y, one)?;
+                cap_delta = add(cap_delta, diagonal(x, y)?)?;
+            }
+        } else if cap_delta > T::ZERO {
+            let delta = sub(
+                mul(four, cap_delta)?,
+                mul(sqr_b, add(mul(four, x)?, three)?)?,
+            )?;
+            y = sub(y, one)?;
+            if delta <= T::ZERO {
+                x = add(x, one)?;
+                cap_delta = add(cap_delta, diagonal(x, y)?)?;
+            } else {
+                cap_delta = add(cap_delta, mul(sqr_a, sub(one, mul(two, y)?)?)?)?;
+            }
+        } else {
+            x = add(x, one)?;
+            y = sub(y, one)?;
+            cap_delta = add(cap_delta, diagonal(x, y)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Вся окружность: октант отражается в остальные семь.
+pub fn circle<T: Int>(center: (T, T), radius: T) -> Result<Vec<(T, T)>, Overflow> {
+    let mut octant = vec![];
+    circle_octant(radius, |x, y| octant.push((x, y)))?;
+    let mut res = Vec::with_capacity(octant.len() * 8);
+    for (x, y) in octant {
+        for (dx, dy) in [(x, y), (y, x)] {
+            res.extend(mirror(center, dx, dy)?);
+        }
+    }
+    Ok(res)
+}
+
+/// Весь эллипс: четверть отражается в остальные три.
+pub fn ellipse<T: Int>(center: (T, T), radius: (T, T)) -> Result<Vec<(T, T)>, Overflow> {
+    let mut quadrant = vec![];
+    ellipse_quadrant(radius, |x, y| quadrant.push((x, y)))?;
+    let mut res = Vec::with_capacity(quadrant.len() * 4);
+    for (x, y) in quadrant {
+        res.extend(mirror(center, x, y)?);
+    }
+    Ok(res)
+}
+
+/// Четыре отражения `(±x, ±y)` относительно центра.
+fn mirror<T: Int>((cx, cy): (T, T), x: T, y: T) -> Result<[(T, T); 4], Overflow> {
+    Ok([
+        (add(cx, x)?, add(cy, y)?),
+        (sub(cx, x)?, add(cy, y)?),
+        (sub(cx, x)?, sub(cy, y)?),
+        (add(cx, x)?, sub(cy, y)?),
+    ])
+}
+
+/// Целые пиксели как точки.
+pub fn points<T: Int>(pixels: &[(T, T)]) -> Vec<Point> {
+    pixels
+        .iter()
+        .map(|&(x, y)| point(x.to_f32(), y.to_f32()))
+        .collect()
+}
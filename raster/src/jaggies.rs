@@ -0,0 +1,159 @@
+//! Анализ ступенчатости отрезков. Работает с выводом любого `Rasterizer`:
+//! пиксели группируются в столбцы по главной оси отрезка, в каждом столбце
+//! берётся самый яркий пиксель (у сглаживающих алгоритмов их несколько),
+//! и по этим пикселям считаются ступеньки.
+
+use crate::integer::Overflow;
+use crate::line::{LinePixels, Rasterizer};
+use crate::point::{point, Point};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+const CSV_HEADER: &str = "algorithm,angle,steps,longest_run,mean_deviation";
+
+/// Ступенчатость одного отрезка.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Steps {
+    /// Сколько раз линия сдвигается по второстепенной оси.
+    pub steps: u32,
+    /// Самая длинная горизонтальная (для пологих) или вертикальная полка,
+    /// в пикселях.
+    pub longest_run: u32,
+    /// Среднее расстояние от центров пикселей до идеальной прямой, взвешенное
+    /// по яркости пикселя.
+    pub mean_deviation: f32,
+}
+
+/// Строка отчёта: алгоритм, угол наклона в градусах и его ступенчатость.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub algorithm: String,
+    pub angle: u32,
+    pub steps: Steps,
+}
+
+/// Пиксели со степенью ослабления; у обычных алгоритмов она 0.
+fn shaded(pixels: &LinePixels) -> Vec<(Point, f32)> {
+    match pixels {
+        LinePixels::Plain(v) => v.iter().map(|&p| (p, 0.0)).collect(),
+        LinePixels::Shaded(v) => v.clone(),
+    }
+}
+
+pub fn analyze(pixels: &LinePixels, points: &[Point; 2]) -> Steps {
+    let [a, b] = *points;
+    let d = b - a;
+    let steep = d.y.abs() > d.x.abs();
+    // (главная, второстепенная) координаты
+    let axes = |p: Point| if steep { (p.y, p.x) } else { (p.x, p.y) };
+    let len = d.x.hypot(d.y);
+    let distance = |p: Point| {
+        let rel = p - a;
+        if len == 0.0 {
+            rel.x.hypot(rel.y)
+        } else {
+            (rel.x * d.y - rel.y * d.x).abs() / len
+        }
+    };
+
+    let pixels = shaded(pixels);
+    let (mut total, mut weights) = (0.0, 0.0);
+    for &(p, i) in &pixels {
+        let w = 1.0 - i / 255.0;
+        total += w * distance(p);
+        weights += w;
+    }
+    let mean_deviation = if weights > 0.0 { total / weights } else { 0.0 };
+
+    // в столбце — самые яркие пиксели, при равенстве их среднее
+    let mut columns: Vec<(f32, f32, f32, u32)> = vec![];
+    for &(p, i) in &pixels {
+        let (major, minor) = axes(p);
+        match columns.iter_mut().find(|c| c.0 == major) {
+            Some(c) if i < c.1 => *c = (major, i, minor, 1),
+            Some(c) if i == c.1 => {
+                c.2 += minor;
+                c.3 += 1;
+            }
+            Some(_) => {}
+            None => columns.push((major, i, minor, 1)),
+        }
+    }
+    columns.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let minors = columns.iter().map(|c| c.2 / c.3 as f32);
+
+    let (mut steps, mut run, mut longest_run) = (0, 0, 0);
+    let mut prev = None;
+    for minor in minors {
+        if prev.is_some_and(|prev| prev != minor) {
+            steps += 1;
+            run = 0;
+        }
+        run += 1;
+        longest_run = longest_run.max(run);
+        prev = Some(minor);
+    }
+    Steps {
+        steps,
+        longest_run,
+        mean_deviation,
+    }
+}
+
+/// Отрезки длины `len` из начала координат под углами `angles` градусов.
+pub fn sweep<R: Rasterizer + ?Sized>(
+    rasterizer: &R,
+    len: f32,
+    angles: impl IntoIterator<Item = u32>,
+) -> Result<Vec<(u32, Steps)>, Overflow> {
+    angles
+        .into_iter()
+        .map(|angle| {
+            let mut end = point(len, 0.0);
+            end.rotate(angle as f32, Point::ZERO);
+            let points = [Point::ZERO, end.round()];
+            Ok((angle, analyze(&rasterizer.rasterize(&points)?, &points)))
+        })
+        .collect()
+}
+
+pub fn write_csv(mut w: impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(w, "{CSV_HEADER}")?;
+    for r in records {
+        writeln!(
+            w,
+            "{},{},{},{},{:.4}",
+            r.algorithm, r.angle, r.steps.steps, r.steps.longest_run, r.steps.mean_deviation
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_markdown(mut w: impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(
+        w,
+        "| Алгоритм | Угол, ° | Ступеньки | Самая длинная полка | Среднее отклонение |"
+    )?;
+    writeln!(w, "|---|---:|---:|---:|---:|")?;
+    for r in records {
+        writeln!(
+            w,
+            "| {} | {} | {} | {} | {:.4} |",
+            r.algorithm, r.angle, r.steps.steps, r.steps.longest_run, r.steps.mean_deviation
+        )?;
+    }
+    Ok(())
+}
+
+pub fn save_csv(path: impl AsRef<Path>, records: &[Record]) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_csv(&mut w, records)?;
+    w.flush()
+}
+
+pub fn save_markdown(path: impl AsRef<Path>, records: &[Record]) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_markdown(&mut w, records)?;
+    w.flush()
+}
@@ -0,0 +1,32 @@
+//! Растеризация без графического интерфейса, общая для лабораторных:
+//! алгоритмы выдают пиксели в `Sink`, а окна egui только показывают их.
+//! Преобразования в типы egui включаются фичами `emath` и `ecolor`.
+
+pub mod accuracy;
+pub mod arc;
+pub mod bezier;
+pub mod circle;
+pub mod color;
+pub mod conic;
+pub mod ellipse;
+pub mod fill;
+pub mod framebuffer;
+pub mod implicit;
+pub mod integer;
+pub mod jaggies;
+pub mod line;
+pub mod paint;
+pub mod pattern;
+pub mod point;
+pub mod polygon;
+pub mod profile;
+pub mod sink;
+pub mod spline;
+pub mod stroke;
+pub mod superellipse;
+pub mod timing;
+
+pub use color::{abate_color, Color, Rgba};
+pub use framebuffer::Framebuffer;
+pub use point::{point, Coord, Point};
+pub use sink::Sink;
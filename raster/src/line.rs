@@ -0,0 +1,573 @@
+//! Алгоритмы растеризации отрезка. У каждого две формы: `dda(points)`
+//! возвращает пиксели вектором, `dda_to(points, sink)` отдаёт их в `Sink`.
+
+use crate::integer::{self, Int, Overflow};
+use crate::point::{point, Point};
+use crate::sink::{self, Sink};
+use std::fmt;
+use std::mem::swap;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algo {
+    #[default]
+    DDA,
+    BresenhamFloat,
+    BresenhamReal,
+    BresenhamInteger,
+    BresenhamJaggiesLess,
+    WU,
+    XiaolinWu,
+    GuptaSproull,
+}
+
+/// Результат растеризации: либо просто пиксели, либо пиксели со степенью
+/// ослабления для `abate_color`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinePixels {
+    Plain(Vec<Point>),
+    Shaded(Vec<(Point, f32)>),
+}
+
+impl Algo {
+    pub const ALL: [Algo; 8] = [
+        Algo::DDA,
+        Algo::BresenhamFloat,
+        Algo::BresenhamReal,
+        Algo::BresenhamInteger,
+        Algo::BresenhamJaggiesLess,
+        Algo::WU,
+        Algo::XiaolinWu,
+        Algo::GuptaSproull,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algo::DDA => "dda",
+            Algo::BresenhamFloat => "bresenham-float",
+            Algo::BresenhamReal => "bresenham-real",
+            Algo::BresenhamInteger => "bresenham-integer",
+            Algo::BresenhamJaggiesLess => "bresenham-jaggiesless",
+            Algo::WU => "wu",
+            Algo::XiaolinWu => "xiaolin-wu",
+            Algo::GuptaSproull => "gupta-sproull",
+        }
+    }
+
+    /// `Overflow` возможен только у `BresenhamInteger`, если отрезок не
+    /// помещается в `i32`; остальные алгоритмы всегда дают пиксели.
+    pub fn draw(&self, points: &[Point; 2]) -> Result<LinePixels, Overflow> {
+        Ok(match self {
+            Algo::DDA => LinePixels::Plain(dda(points)),
+            Algo::BresenhamFloat => LinePixels::Plain(bresenham_float(points)),
+            Algo::BresenhamReal => LinePixels::Plain(bresenham_real(points)),
+            Algo::BresenhamInteger => LinePixels::Plain(bresenham_integer(points)?),
+            Algo::BresenhamJaggiesLess => LinePixels::Shaded(bresenham_jaggiesless(points)),
+            Algo::WU => LinePixels::Shaded(wu(points)),
+            Algo::XiaolinWu => LinePixels::Shaded(xiaolin_wu(points)),
+            Algo::GuptaSproull => LinePixels::Shaded(gupta_sproull(points)),
+        })
+    }
+
+    /// При `Overflow` в `sink` ничего не попадает.
+    pub fn draw_to<S: Sink + ?Sized>(
+        &self,
+        points: &[Point; 2],
+        sink: &mut S,
+    ) -> Result<(), Overflow> {
+        match self {
+            Algo::DDA => dda_to(points, sink),
+            Algo::BresenhamFloat => bresenham_float_to(points, sink),
+            Algo::BresenhamReal => bresenham_real_to(points, sink),
+            Algo::BresenhamInteger => return bresenham_integer_to(points, sink),
+            Algo::BresenhamJaggiesLess => bresenham_jaggiesless_to(points, sink),
+            Algo::WU => wu_to(points, sink),
+            Algo::XiaolinWu => xiaolin_wu_to(points, sink),
+            Algo::GuptaSproull => gupta_sproull_to(points, sink),
+        }
+        Ok(())
+    }
+}
+
+/// Общий интерфейс растеризаторов отрезка: алгоритмов этого модуля, толстой
+/// линии и т. п. Через него к любому из них применяется узор
+/// (`pattern::Patterned`). Растеризатор на целых числах сообщает о
+/// переполнении через `Overflow`.
+pub trait Rasterizer {
+    fn rasterize(&self, points: &[Point; 2]) -> Result<LinePixels, Overflow>;
+
+    fn rasterize_to(&self, points: &[Point; 2], sink: &mut dyn Sink) -> Result<(), Overflow> {
+        self.rasterize(points)?.plot_to(sink);
+        Ok(())
+    }
+}
+
+impl Rasterizer for Algo {
+    fn rasterize(&self, points: &[Point; 2]) -> Result<LinePixels, Overflow> {
+        self.draw(points)
+    }
+
+    fn rasterize_to(&self, points: &[Point; 2], sink: &mut dyn Sink) -> Result<(), Overflow> {
+        self.draw_to(points, sink)
+    }
+}
+
+impl LinePixels {
+    pub fn len(&self) -> usize {
+        match self {
+            LinePixels::Plain(v) => v.len(),
+            LinePixels::Shaded(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn plot_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        match self {
+            LinePixels::Plain(v) => v.iter().for_each(|&p| sink.plot(p)),
+            LinePixels::Shaded(v) => v.iter().for_each(|&(p, i)| sink.plot_shaded(p, i)),
+        }
+    }
+
+    /// Оставляет только пиксели, для которых `f` вернула `true`.
+    pub fn retain(&mut self, mut f: impl FnMut(Point) -> bool) {
+        match self {
+            LinePixels::Plain(v) => v.retain(|&p| f(p)),
+            LinePixels::Shaded(v) => v.retain(|&(p, _)| f(p)),
+        }
+    }
+}
+
+impl fmt::Display for Algo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algo {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Algo::ALL
+            .into_iter()
+            .find(|algo| algo.name() == s)
+            .ok_or_else(|| {
+                let names = Algo::ALL.map(|algo| algo.name()).join(", ");
+                format!("unknown algorithm `{s}`, expected one of: {names}")
+            })
+    }
+}
+
+/// Веер отрезков длины `len` из `center`, повёрнутых с шагом `angle` градусов.
+pub fn spectrum(center: Point, len: f32, angle: f32) -> Vec<[Point; 2]> {
+    let mut points = [center, center + point(len, 0.0)];
+    let mut res = vec![points];
+    let mut i = 0.0;
+    while i + angle <= 360.0 {
+        points[1].rotate(angle, center);
+        res.push(points);
+        i += angle;
+    }
+    res
+}
+
+pub fn dda(points: &[Point; 2]) -> Vec<Point> {
+    sink::collect(|pixels| dda_to(points, pixels))
+}
+
+pub fn dda_to<S: Sink + ?Sized>(points: &[Point; 2], sink: &mut S) {
+    if points[0] == points[1] {
+        sink.plot(points[0]);
+        return;
+    }
+    let (x1, y1) = (points[0].x, points[0].y);
+    let (x2, y2) = (points[1].x, points[1].y);
+
+    let dx = x2 - x1;
+    let dy = y2 - y1;
+    let l = dx.abs().max(dy.abs());
+
+    let dx = dx / l;
+    let dy = dy / l;
+
+    let mut x = x1;
+    let mut y = y1;
+    for _ in 0..=l as i32 {
+        sink.plot(point(x.round(), y.round()));
+        x += dx;
+        y += dy;
+    }
+}
+
+pub fn bresenham_float(points: &[Point; 2]) -> Vec<Point> {
+    sink::collect(|pixels| bresenham_float_to(points, pixels))
+}
+
+pub fn bresenham_float_to<S: Sink + ?Sized>(points: &[Point; 2], sink: &mut S) {
+    if points[0] == points[1] {
+        sink.plot(points[0]);
+        return;
+    }
+    let (x1, y1) = (points[0].x, points[0].y);
+    let (x2, y2) = (points[1].x, points[1].y);
+
+    let mut x = x1;
+    let mut y = y1;
+    let dx = x2 - x1;
+    let dy = y2 - y1;
+    let sx = dx.signum();
+    let sy = dy.signum();
+
+    let mut dx = dx.abs();
+    let mut dy = dy.abs();
+
+    let swapped = if dx > dy {
+        false
+    } else {
+        swap(&mut dx, &mut dy);
+        true
+    };
+
+    let m = dy / dx;
+    let mut e = m - 0.5;
+    for _ in 0..=dx as i32 {
+        sink.plot(point(x, y));
+        if !e.is_sign_negative() {
+            if swapped {
+                x += sx;
+            } else {
+                y += sy;
+            }
+            e -= 1.0;
+        }
+        if swapped {
+            y += sy;
+        } else {
+            x += sx;
+        }
+        e += m;
+    }
+}
+
+pub fn bresenham_jaggiesless(points: &[Point; 2]) -> Vec<(Point, f32)> {
+    sink::collect(|pixels| bresenham_jaggiesless_to(points, pixels))
+}
+
+pub fn bresenham_jaggiesless_to<S: Sink + ?Sized>(points: &[Point; 2], sink: &mut S) {
+    if points[0] == points[1] {
+        sink.plot_shaded(points[0], 0.0);
+        return;
+    }
+    let (x1, y1) = (points[0].x, points[0].y);
+    let (x2, y2) = (points[1].x, points[1].y);
+
+    let mut x = x1;
+    let mut y = y1;
+    let dx = x2 - x1;
+    let dy = y2 - y1;
+    let sx = dx.signum();
+    let sy = dy.signum();
+
+    let mut dx = dx.abs();
+    let mut dy = dy.abs();
+
+    let swapped = if dx > dy {
+        false
+    } else {
+        swap(&mut dx, &mut dy);
+        true
+    };
+    let intense = 255.0;
+    let m = (intense * dy) / dx;
+    let w = intense - m;
+    let mut e = 0.5 * intense;
+    for _ in 0..=dx as i32 {
+        sink.plot_shaded(point(x, y), e);
+        if e < w {
+            if !swapped {
+                x += sx;
+            } else {
+                y += sy;
+            }
+            e += m;
+        } else {
+            x += sx;
+            y += sy;
+            e -= w;
+        }
+    }
+}
+
+pub fn bresenham_real(points: &[Point; 2]) -> Vec<Point> {
+    sink::collect(|pixels| bresenham_real_to(points, pixels))
+}
+
+pub fn bresenham_real_to<S: Sink + ?Sized>(points: &[Point; 2], sink: &mut S) {
+    if points[0] == points[1] {
+        sink.plot(points[0]);
+        return;
+    }
+    let (x1, y1) = (points[0].x, points[0].y);
+    let (x2, y2) = (points[1].x, points[1].y);
+
+    let mut x = x1;
+    let mut y = y1;
+    let dx = x2 - x1;
+    let dy = y2 - y1;
+    let sx = dx.signum();
+    let sy = dy.signum();
+
+    let mut dx = dx.abs();
+    let mut dy = dy.abs();
+
+    let swapped = if dx > dy {
+        false
+    } else {
+        swap(&mut dx, &mut dy);
+        true
+    };
+
+    let mut e = 2.0 * dy - dx;
+    for _ in 0..=dx as i32 {
+        sink.plot(point(x, y));
+        if !e.is_sign_negative() {
+            if swapped {
+                x += sx;
+            } else {
+                y += sy;
+            }
+            e -= 2.0 * dx;
+        }
+        if swapped {
+            y += sy;
+        } else {
+            x += sx;
+        }
+        e += 2.0 * dy;
+    }
+}
+
+/// Брезенхем только на целых `i32`: концы округляются до пикселей. Если
+/// концы не помещаются в `i32` или разность координат переполняется —
+/// `Overflow`.
+pub fn bresenham_integer(points: &[Point; 2]) -> Result<Vec<Point>, Overflow> {
+    let mut pixels = vec![];
+    bresenham_integer_to(points, &mut pixels)?;
+    Ok(pixels)
+}
+
+/// Пиксели считаются заранее, так что при `Overflow` в `sink` ничего не
+/// попадает.
+pub fn bresenham_integer_to<S: Sink + ?Sized>(
+    points: &[Point; 2],
+    sink: &mut S,
+) -> Result<(), Overflow> {
+    let int = |p: Point| Some((i32::from_f32(p.x)?, i32::from_f32(p.y)?));
+    let (Some(from), Some(to)) = (int(points[0]), int(points[1])) else {
+        return Err(Overflow);
+    };
+    for (x, y) in integer::line(from, to)? {
+        sink.plot(point(x.to_f32(), y.to_f32()));
+    }
+    Ok(())
+}
+
+/// На каждый столбец — пара пикселей: сначала с координатой по неглавной оси,
+/// округлённой вниз, затем следующий за ним по этой оси (какой из них ближе
+/// к отрезку, не важно). Столбцы идут от начала отрезка к концу.
+pub fn wu(points: &[Point; 2]) -> Vec<(Point, f32)> {
+    sink::collect(|pixels| wu_to(points, pixels))
+}
+
+pub fn wu_to<S: Sink + ?Sized>(points: &[Point; 2], sink: &mut S) {
+    const M_I: f32 = 255.0;
+
+    if points[0] == points[1] {
+        sink.plot_shaded(points[0], 0.0);
+        return;
+    }
+    let (mut x1, mut y1) = (points[0].x, points[0].y);
+    let (mut x2, mut y2) = (points[1].x, points[1].y);
+
+    let steep = (y2 - y1).abs() > (x2 - x1).abs();
+    if steep {
+        swap(&mut x1, &mut y1);
+        swap(&mut x2, &mut y2);
+    }
+    let dx = x2 - x1;
+    let dy = y2 - y1;
+    let sx = dx.signum();
+
+    for i in 0..=dx.abs() as i32 {
+        let x = x1 + sx * i as f32;
+        let intery = y1 + dy * i as f32 / dx.abs();
+        let y = intery.floor();
+        let fpart = intery - y;
+        if steep {
+            sink.plot_shaded(point(y, x), M_I * fpart);
+            sink.plot_shaded(point(y + 1.0, x), M_I * (1.0 - fpart));
+        } else {
+            sink.plot_shaded(point(x, y), M_I * fpart);
+            sink.plot_shaded(point(x, y + 1.0), M_I * (1.0 - fpart));
+        }
+    }
+}
+
+/// Алгоритм Ву с дробными концами: крайние столбцы ослабляются пропорционально
+/// тому, какую часть пикселя по главной оси покрывает отрезок. Пары пикселей
+/// идут по столбцам от левого (верхнего) конца к правому (нижнему).
+pub fn xiaolin_wu(points: &[Point; 2]) -> Vec<(Point, f32)> {
+    sink::collect(|pixels| xiaolin_wu_to(points, pixels))
+}
+
+pub fn xiaolin_wu_to<S: Sink + ?Sized>(points: &[Point; 2], sink: &mut S) {
+    const M_I: f32 = 255.0;
+
+    if points[0] == points[1] {
+        sink.plot_shaded(points[0].round(), 0.0);
+        return;
+    }
+    let (mut x1, mut y1) = (points[0].x, points[0].y);
+    let (mut x2, mut y2) = (points[1].x, points[1].y);
+
+    let steep = (y2 - y1).abs() > (x2 - x1).abs();
+    if steep {
+        swap(&mut x1, &mut y1);
+        swap(&mut x2, &mut y2);
+    }
+    if x2 < x1 {
+        swap(&mut x1, &mut x2);
+        swap(&mut y1, &mut y2);
+    }
+    let grad = (y2 - y1) / (x2 - x1);
+
+    // `coverage` — доля пикселя, закрытая отрезком, от 0 до 1
+    let mut plot = |x: f32, y: f32, coverage: f32| {
+        let p = if steep { point(y, x) } else { point(x, y) };
+        sink.plot_shaded(p, M_I * (1.0 - coverage));
+    };
+    let mut plot_column = |x: f32, y: f32, gap: f32| {
+        let yi = y.floor();
+        let fpart = y - yi;
+        plot(x, yi, (1.0 - fpart) * gap);
+        plot(x, yi + 1.0, fpart * gap);
+    };
+
+    let xs = x1.round();
+    let ys = y1 + grad * (xs - x1);
+    let xe = x2.round();
+    let ye = y2 + grad * (xe - x2);
+    if xs == xe {
+        plot_column(xs, (ys + ye) / 2.0, x2 - x1);
+    } else {
+        plot_column(xs, ys, 1.0 - (x1 + 0.5 - (x1 + 0.5).floor()));
+        for i in 1..(xe - xs) as i32 {
+            plot_column(xs + i as f32, ys + grad * i as f32, 1.0);
+        }
+        plot_column(xe, ye, x2 + 0.5 - (x2 + 0.5).floor());
+    }
+}
+
+/// Интенсивность пикселя в зависимости от расстояния от его центра до оси
+/// отрезка единичной толщины, отфильтрованного конусом радиуса 1.
+/// Таблица нормирована так, что пиксель на оси отрезка закрашен полностью.
+fn gupta_sproull_filter(distance: f32) -> f32 {
+    const STEPS: usize = 24;
+    const MAX_DISTANCE: f32 = 1.5;
+    static TABLE: OnceLock<[f32; STEPS + 1]> = OnceLock::new();
+
+    let table = TABLE.get_or_init(|| {
+        // интеграл конуса по вертикальной прямой на расстоянии x от центра
+        let chord = |x: f32| {
+            let x = x.abs();
+            if x >= 1.0 {
+                return 0.0;
+            }
+            let h = (1.0 - x * x).sqrt();
+            if x == 0.0 {
+                h
+            } else {
+                h - x * x / 2.0 * ((1.0 + h) / (1.0 - h)).ln()
+            }
+        };
+        // по Симпсону по полосе [d - 1/2, d + 1/2]
+        let strip = |d: f32| {
+            let n = 64;
+            let (a, b) = (d - 0.5, d + 0.5);
+            let h = (b - a) / n as f32;
+            let sum = (1..n)
+                .map(|i| chord(a + i as f32 * h) * if i % 2 == 1 { 4.0 } else { 2.0 })
+                .sum::<f32>();
+            (chord(a) + chord(b) + sum) * h / 3.0
+        };
+        let center = strip(0.0);
+        std::array::from_fn(|i| strip(i as f32 * MAX_DISTANCE / STEPS as f32) / center)
+    });
+
+    let pos = distance.abs() / MAX_DISTANCE * STEPS as f32;
+    if pos >= STEPS as f32 {
+        return 0.0;
+    }
+    let i = pos as usize;
+    let t = pos - i as f32;
+    table[i] + (table[i + 1] - table[i]) * t
+}
+
+/// Алгоритм Гупты — Спрулла: отрезок строится по Брезенхему, а пиксель и два
+/// его соседа поперёк главной оси закрашиваются по расстоянию до оси отрезка.
+/// Тройки идут от начала отрезка, первым — пиксель Брезенхема.
+pub fn gupta_sproull(points: &[Point; 2]) -> Vec<(Point, f32)> {
+    sink::collect(|pixels| gupta_sproull_to(points, pixels))
+}
+
+pub fn gupta_sproull_to<S: Sink + ?Sized>(points: &[Point; 2], sink: &mut S) {
+    const M_I: f32 = 255.0;
+
+    let (x1, y1) = (points[0].x.round(), points[0].y.round());
+    let (x2, y2) = (points[1].x.round(), points[1].y.round());
+    if (x1, y1) == (x2, y2) {
+        sink.plot_shaded(point(x1, y1), 0.0);
+        return;
+    }
+
+    let steep = (y2 - y1).abs() > (x2 - x1).abs();
+    // u — главная ось, v — поперечная
+    let (u1, v1, du, dv) = if steep {
+        (y1, x1, y2 - y1, x2 - x1)
+    } else {
+        (x1, y1, x2 - x1, y2 - y1)
+    };
+    let (su, sv) = (du.signum(), dv.signum());
+    let (du, dv) = (du.abs(), dv.abs());
+
+    let mut plot = |u: f32, v: f32, distance: f32| {
+        let p = if steep { point(v, u) } else { point(u, v) };
+        sink.plot_shaded(p, M_I * (1.0 - gupta_sproull_filter(distance)));
+    };
+
+    let inv_denom = 1.0 / (2.0 * (du * du + dv * dv).sqrt());
+    let two_du_inv_denom = 2.0 * du * inv_denom;
+    let mut d = 2.0 * dv - du;
+    let mut two_v_du = 0.0;
+    let (mut u, mut v) = (u1, v1);
+    for i in 0..=du as i32 {
+        plot(u, v, two_v_du * inv_denom);
+        plot(u, v + sv, two_du_inv_denom - two_v_du * inv_denom);
+        plot(u, v - sv, two_du_inv_denom + two_v_du * inv_denom);
+        if i == du as i32 {
+            break;
+        }
+        if d < 0.0 {
+            two_v_du = d + du;
+            d += 2.0 * dv;
+        } else {
+            two_v_du = d - du;
+            d += 2.0 * (dv - du);
+            v += sv;
+        }
+        u += su;
+    }
+}
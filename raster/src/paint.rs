@@ -0,0 +1,189 @@
+//! Чем заливать область: цвет берётся для каждого пикселя отдельно — один
+//! цвет, линейный или радиальный градиент, картинка, повторённая по всей
+//! плоскости. Градиент смешивает два цвета так же, как `abate_color`.
+//! Заливки пишут через `PaintBrush`, который спрашивает цвет у `Paint` для
+//! каждого пикселя отрезка.
+
+use crate::color::{abate_color, Color, Rgba};
+use crate::framebuffer::Framebuffer;
+use crate::point::{point, Point};
+use crate::sink::{Image, Sink};
+
+/// Вид заливки для выбора в интерфейсе; сама заливка строится по двум
+/// цветам и охватывающему прямоугольнику в `Paint::new`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    #[default]
+    Solid,
+    Linear,
+    Radial,
+    Pattern,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 4] = [Kind::Solid, Kind::Linear, Kind::Radial, Kind::Pattern];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Solid => "solid",
+            Kind::Linear => "linear",
+            Kind::Radial => "radial",
+            Kind::Pattern => "pattern",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(Rgba),
+    /// От `from` в точке `start` к `to` в точке `end`; за концами отрезка
+    /// цвет не меняется.
+    Linear {
+        start: Point,
+        end: Point,
+        from: Rgba,
+        to: Rgba,
+    },
+    /// От `from` в центре к `to` на окружности радиуса `radius` и дальше.
+    Radial {
+        center: Point,
+        radius: f32,
+        from: Rgba,
+        to: Rgba,
+    },
+    /// Картинка `tile`, повторённая по всей плоскости; её левый верхний
+    /// пиксель — в точке `origin`.
+    Pattern {
+        tile: Framebuffer,
+        origin: Point,
+    },
+}
+
+impl Paint {
+    /// Заливка вида `kind` из цветов `from` и `to` по прямоугольнику от `min`
+    /// до `max`: линейный градиент идёт по его диагонали, радиальный — от
+    /// центра до углов, узор — шахматка из клеток в `PATTERN_CELL` пикселей.
+    pub fn new(kind: Kind, from: Rgba, to: Rgba, min: Point, max: Point) -> Self {
+        match kind {
+            Kind::Solid => Paint::Solid(from),
+            Kind::Linear => Paint::Linear {
+                start: min,
+                end: max,
+                from,
+                to,
+            },
+            Kind::Radial => Paint::Radial {
+                center: (min + max) * 0.5,
+                radius: min.distance(max) / 2.0,
+                from,
+                to,
+            },
+            Kind::Pattern => Paint::Pattern {
+                tile: checker(from, to, PATTERN_CELL),
+                origin: min,
+            },
+        }
+    }
+
+    /// Цвет, если он один на всю плоскость.
+    pub fn solid(&self) -> Option<Rgba> {
+        match self {
+            Paint::Solid(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    /// Цвет пикселя с центром в `p`.
+    pub fn at(&self, p: Point) -> Rgba {
+        match self {
+            Paint::Solid(color) => *color,
+            Paint::Linear {
+                start,
+                end,
+                from,
+                to,
+            } => {
+                let (d, v) = (*end - *start, p - *start);
+                let length = d.x * d.x + d.y * d.y;
+                let t = if length > 0.0 {
+                    (v.x * d.x + v.y * d.y) / length
+                } else {
+                    1.0
+                };
+                abate_color(*from, *to, 255.0 * t.clamp(0.0, 1.0))
+            }
+            Paint::Radial {
+                center,
+                radius,
+                from,
+                to,
+            } => {
+                let t = if *radius > 0.0 {
+                    p.distance(*center) / radius
+                } else {
+                    1.0
+                };
+                abate_color(*from, *to, 255.0 * t.clamp(0.0, 1.0))
+            }
+            Paint::Pattern { tile, origin } => {
+                let (width, height) = (tile.width() as i64, tile.height() as i64);
+                if width == 0 || height == 0 {
+                    return Rgba::TRANSPARENT;
+                }
+                let x = (p.x - origin.x).round() as i64;
+                let y = (p.y - origin.y).round() as i64;
+                tile.get(x.rem_euclid(width), y.rem_euclid(height))
+                    .unwrap_or(Rgba::TRANSPARENT)
+            }
+        }
+    }
+}
+
+/// Сторона клетки шахматки у `Kind::Pattern`, в пикселях.
+pub const PATTERN_CELL: usize = 8;
+
+/// Картинка шахматки 2×2 из квадратных клеток со стороной `cell`: цвет `a`
+/// в левой верхней и правой нижней, `b` — в остальных.
+pub fn checker(a: Rgba, b: Rgba, cell: usize) -> Framebuffer {
+    let mut tile = Framebuffer::new(2 * cell, 2 * cell, a);
+    for y in 0..2 * cell {
+        for x in 0..2 * cell {
+            if (x / cell + y / cell) % 2 == 1 {
+                tile.set(x as i64, y as i64, b);
+            }
+        }
+    }
+    tile
+}
+
+/// Рисует пиксели в изображение цветом из `Paint`; ослабленные
+/// смешиваются с тем, что уже нарисовано, как у `Brush`.
+pub struct PaintBrush<'a, I: Image + ?Sized> {
+    image: &'a mut I,
+    paint: &'a Paint,
+}
+
+impl<'a, I: Image + ?Sized> PaintBrush<'a, I> {
+    pub fn new(image: &'a mut I, paint: &'a Paint) -> Self {
+        PaintBrush { image, paint }
+    }
+}
+
+impl<I: Image + ?Sized> Sink for PaintBrush<'_, I>
+where
+    I::Color: From<Rgba>,
+{
+    fn plot(&mut self, p: Point) {
+        let (x, y) = (p.x.round(), p.y.round());
+        let color = self.paint.at(point(x, y));
+        self.image.set(x as i64, y as i64, color.into());
+    }
+
+    fn plot_shaded(&mut self, p: Point, i: f32) {
+        let (x, y) = (p.x.round(), p.y.round());
+        if let Some(canvas) = self.image.get(x as i64, y as i64) {
+            let color: I::Color = self.paint.at(point(x, y)).into();
+            self.image.set(x as i64, y as i64, color.abate(canvas, i));
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Узоры линий: штрихи, пунктир, штрихпунктир. Узор задаётся битовой маской
+//! или списком длин штрихов и промежутков и накладывается на результат любого
+//! `Rasterizer`, так что пиксели остаются теми же, просто часть пропускается.
+//!
+//! Положение на узоре отсчитывается по главной оси отрезка: шаг растеризатора
+//! — единица длины, как у stipple в OpenGL. На ломаной отсчёт продолжается
+//! с того места, где закончился предыдущий отрезок.
+
+use crate::integer::Overflow;
+use crate::line::{LinePixels, Rasterizer};
+use crate::point::Point;
+use std::str::FromStr;
+
+pub trait Pattern {
+    /// Закрашивается ли пиксель в позиции `pos` от начала узора.
+    fn is_on(&self, pos: f32) -> bool;
+}
+
+impl<P: Pattern + ?Sized> Pattern for &P {
+    fn is_on(&self, pos: f32) -> bool {
+        (**self).is_on(pos)
+    }
+}
+
+impl<P: Pattern + ?Sized> Pattern for Box<P> {
+    fn is_on(&self, pos: f32) -> bool {
+        (**self).is_on(pos)
+    }
+}
+
+/// Сплошная линия.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solid;
+
+impl Pattern for Solid {
+    fn is_on(&self, _: f32) -> bool {
+        true
+    }
+}
+
+/// Повторяющиеся `len` младших битов маски, младший бит — первый пиксель.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitMask {
+    bits: u32,
+    len: u32,
+}
+
+impl BitMask {
+    /// `len` — от 1 до 32.
+    pub fn new(bits: u32, len: u32) -> Result<Self, String> {
+        if !(1..=32).contains(&len) {
+            return Err(format!("mask length {len} is not in 1..=32"));
+        }
+        Ok(BitMask { bits, len })
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn period(&self) -> u32 {
+        self.len
+    }
+}
+
+impl Pattern for BitMask {
+    fn is_on(&self, pos: f32) -> bool {
+        let i = (pos.floor() as i64).rem_euclid(self.len as i64);
+        self.bits >> i & 1 == 1
+    }
+}
+
+/// Длины штрихов и промежутков по очереди, начиная со штриха. Список нечётной
+/// длины повторяется дважды, как в SVG. Фаза сдвигает начало узора.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dashes {
+    dashes: Vec<f32>,
+    period: f32,
+    phase: f32,
+}
+
+impl Dashes {
+    pub fn new(dashes: &[f32]) -> Result<Self, String> {
+        if dashes.is_empty() {
+            return Err("dash array is empty".to_string());
+        }
+        if let Some(d) = dashes.iter().find(|d| !(d.is_finite() && **d >= 0.0)) {
+            return Err(format!("dash length {d} is not a non-negative number"));
+        }
+        let mut dashes = dashes.to_vec();
+        if dashes.len() % 2 == 1 {
+            dashes.extend_from_within(..);
+        }
+        let period = dashes.iter().sum::<f32>();
+        if period == 0.0 {
+            return Err("dash array has zero length".to_string());
+        }
+        Ok(Dashes {
+            dashes,
+            period,
+            phase: 0.0,
+        })
+    }
+
+    pub fn dashes(&self) -> &[f32] {
+        &self.dashes
+    }
+
+    pub fn period(&self) -> f32 {
+        self.period
+    }
+
+    pub fn phase(&self) -> f32 {
+        self.phase
+    }
+
+    pub fn set_phase(&mut self, phase: f32) {
+        self.phase = phase;
+    }
+}
+
+impl Pattern for Dashes {
+    fn is_on(&self, pos: f32) -> bool {
+        let t = (pos + self.phase).rem_euclid(self.period);
+        let mut end = 0.0;
+        for (i, d) in self.dashes.iter().enumerate() {
+            end += d;
+            if t < end {
+                return i % 2 == 0;
+            }
+        }
+        // t == period из-за округления
+        false
+    }
+}
+
+/// `"6,3"` или `"6 3 1 3"`.
+impl FromStr for Dashes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dashes = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<f32>().map_err(|e| format!("`{s}`: {e}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        Dashes::new(&dashes)
+    }
+}
+
+/// Узор вместе с пройденной длиной: отрезки, растеризованные подряд, образуют
+/// непрерывный узор.
+#[derive(Debug, Clone)]
+pub struct Patterned<P> {
+    pattern: P,
+    pos: f32,
+}
+
+impl<P: Pattern> Patterned<P> {
+    pub fn new(pattern: P) -> Self {
+        Patterned { pattern, pos: 0.0 }
+    }
+
+    pub fn pattern(&self) -> &P {
+        &self.pattern
+    }
+
+    pub fn position(&self) -> f32 {
+        self.pos
+    }
+
+    /// Начать узор заново со следующего отрезка.
+    pub fn reset(&mut self) {
+        self.pos = 0.0;
+    }
+
+    /// Пиксели отрезка, попавшие на штрихи. Длина отрезка добавляется
+    /// к пройденной; при `Overflow` позиция в узоре не меняется.
+    pub fn segment<R: Rasterizer + ?Sized>(
+        &mut self,
+        rasterizer: &R,
+        points: &[Point; 2],
+    ) -> Result<LinePixels, Overflow> {
+        let [a, b] = *points;
+        let d = b - a;
+        let (start, pattern) = (self.pos, &self.pattern);
+        let mut pixels = rasterizer.rasterize(points)?;
+        pixels.retain(|p| pattern.is_on(start + along(d, p - a)));
+        self.pos += d.x.abs().max(d.y.abs());
+        Ok(pixels)
+    }
+
+    /// Отрезки ломаной по порядку, узор не прерывается на вершинах.
+    pub fn polyline<R: Rasterizer + ?Sized>(
+        &mut self,
+        rasterizer: &R,
+        vertices: &[Point],
+    ) -> Result<Vec<LinePixels>, Overflow> {
+        vertices
+            .windows(2)
+            .map(|w| self.segment(rasterizer, &[w[0], w[1]]))
+            .collect()
+    }
+}
+
+/// Смещение `rel` вдоль главной оси направления `d`.
+fn along(d: Point, rel: Point) -> f32 {
+    if d.x.abs() >= d.y.abs() {
+        rel.x * d.x.signum()
+    } else {
+        rel.y * d.y.signum()
+    }
+}
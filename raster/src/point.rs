@@ -0,0 +1,164 @@
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+pub const fn point(x: f32, y: f32) -> Point {
+    Point { x, y }
+}
+
+impl Point {
+    pub const ZERO: Self = point(0.0, 0.0);
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        point(x, y)
+    }
+
+    pub fn round(self) -> Self {
+        point(self.x.round(), self.y.round())
+    }
+
+    pub fn distance(self, other: Self) -> f32 {
+        (other.x - self.x).hypot(other.y - self.y)
+    }
+
+    /// Поворот вокруг `center` на `angle` градусов.
+    pub fn rotate(&mut self, angle: f32, center: Point) {
+        let (tx, ty) = (self.x - center.x, self.y - center.y);
+        let (sin, cos) = angle.to_radians().sin_cos();
+        self.x = tx * cos - ty * sin + center.x;
+        self.y = tx * sin + ty * cos + center.y;
+    }
+}
+
+/// Тип точки, в котором растеризаторы отдают пиксели (см. `sink`).
+pub trait Coord: Copy {
+    fn from_xy(x: f32, y: f32) -> Self;
+
+    fn x(&self) -> f32;
+
+    fn y(&self) -> f32;
+}
+
+impl Coord for Point {
+    fn from_xy(x: f32, y: f32) -> Self {
+        point(x, y)
+    }
+
+    fn x(&self) -> f32 {
+        self.x
+    }
+
+    fn y(&self) -> f32 {
+        self.y
+    }
+}
+
+impl Coord for (f32, f32) {
+    fn from_xy(x: f32, y: f32) -> Self {
+        (x, y)
+    }
+
+    fn x(&self) -> f32 {
+        self.0
+    }
+
+    fn y(&self) -> f32 {
+        self.1
+    }
+}
+
+impl Coord for [f32; 2] {
+    fn from_xy(x: f32, y: f32) -> Self {
+        [x, y]
+    }
+
+    fn x(&self) -> f32 {
+        self[0]
+    }
+
+    fn y(&self) -> f32 {
+        self[1]
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        point(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        point(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        point(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl From<(f32, f32)> for Point {
+    fn from((x, y): (f32, f32)) -> Self {
+        point(x, y)
+    }
+}
+
+impl From<[f32; 2]> for Point {
+    fn from([x, y]: [f32; 2]) -> Self {
+        point(x, y)
+    }
+}
+
+impl From<Point> for (f32, f32) {
+    fn from(p: Point) -> Self {
+        (p.x, p.y)
+    }
+}
+
+#[cfg(feature = "emath")]
+impl From<emath::Pos2> for Point {
+    fn from(p: emath::Pos2) -> Self {
+        point(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "emath")]
+impl From<Point> for emath::Pos2 {
+    fn from(p: Point) -> Self {
+        emath::pos2(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "emath")]
+impl From<emath::Vec2> for Point {
+    fn from(v: emath::Vec2) -> Self {
+        point(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "emath")]
+impl Coord for emath::Pos2 {
+    fn from_xy(x: f32, y: f32) -> Self {
+        emath::pos2(x, y)
+    }
+
+    fn x(&self) -> f32 {
+        self.x
+    }
+
+    fn y(&self) -> f32 {
+        self.y
+    }
+}
@@ -0,0 +1,66 @@
+use crate::point::Point;
+
+/// Ломаная, которую можно замкнуть: при замыкании первая вершина
+/// дублируется в конец.
+#[derive(Debug, Clone)]
+pub struct Polygon<P = Point> {
+    vertices: Vec<P>,
+    closed: bool,
+}
+
+impl<P> Default for Polygon<P> {
+    fn default() -> Self {
+        Self {
+            vertices: vec![],
+            closed: false,
+        }
+    }
+}
+
+impl<P: Copy + PartialEq> Polygon<P> {
+    pub fn push(&mut self, pos: P) -> &mut Self {
+        self.vertices.push(pos);
+        self
+    }
+
+    /// Как `push`, но вершина, совпадающая с последней, пропускается: у
+    /// многоугольника для заливки или отсечения не бывает рёбер нулевой
+    /// длины.
+    pub fn push_distinct(&mut self, pos: P) -> &mut Self {
+        if self.vertices.last() != Some(&pos) {
+            self.vertices.push(pos);
+        }
+        self
+    }
+
+    pub fn vertices(&self) -> &[P] {
+        &self.vertices
+    }
+
+    pub fn last(&self) -> Option<&P> {
+        self.vertices.last()
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.open()
+    }
+
+    pub fn closed(&self) -> bool {
+        self.closed
+    }
+
+    pub fn close(&mut self) -> &mut Self {
+        if self.vertices.len() < 3 || self.closed {
+            return self;
+        }
+        self.vertices.push(self.vertices[0]);
+        self.closed = true;
+        self
+    }
+
+    pub fn open(&mut self) -> &mut Self {
+        self.vertices.clear();
+        self.closed = false;
+        self
+    }
+}
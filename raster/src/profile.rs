@@ -0,0 +1,261 @@
+//! Хранилище ручных замеров времени (кнопки «Измерить» лабораторных). В
+//! отличие от сводки бенчмарка, файл только дополняется: каждый замер
+//! помечен названием запуска, временем и машиной, так что запуски разных
+//! сессий можно сравнивать между собой. Запуск определяется названием
+//! вместе со временем начала: сессии с одинаковым названием не сливаются.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CSV_HEADER: &str = "run,timestamp,machine,shape,algorithm,radius,time_us";
+
+/// Один замер: среднее время `time_us` построения фигуры `shape`
+/// (`circle`, `ellipse`, `rotated`) алгоритмом `algorithm` при радиусе
+/// `radius`. `timestamp` — секунды от начала эпохи UNIX.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub run: String,
+    pub timestamp: u64,
+    pub machine: String,
+    pub shape: String,
+    pub algorithm: String,
+    pub radius: u32,
+    pub time_us: f64,
+}
+
+/// Общие поля всех замеров одного запуска.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub name: String,
+    pub timestamp: u64,
+    pub machine: String,
+}
+
+impl Run {
+    /// Запуск на текущей машине в текущий момент. Пустое имя заменяется
+    /// на `run-<timestamp>`.
+    pub fn new(name: &str) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let name = match name.trim() {
+            "" => format!("run-{timestamp}"),
+            name => name.to_owned(),
+        };
+        Self {
+            name,
+            timestamp,
+            machine: machine(),
+        }
+    }
+
+    /// Относится ли замер к этому запуску.
+    pub fn contains(&self, record: &Record) -> bool {
+        record.run == self.name && record.timestamp == self.timestamp
+    }
+
+    pub fn record(&self, shape: &str, algorithm: &str, radius: u32, time_us: f64) -> Record {
+        Record {
+            run: self.name.clone(),
+            timestamp: self.timestamp,
+            machine: self.machine.clone(),
+            shape: shape.to_owned(),
+            algorithm: algorithm.to_owned(),
+            radius,
+            time_us,
+        }
+    }
+}
+
+/// Описание машины: ОС, архитектура, число потоков и имя хоста, если оно
+/// есть в окружении.
+pub fn machine() -> String {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut res = format!(
+        "{} {} {threads} threads",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    if let Ok(host) = std::env::var("HOSTNAME").or_else(|_| std::env::var("COMPUTERNAME")) {
+        res.push_str(" @ ");
+        res.push_str(&host);
+    }
+    res
+}
+
+fn invalid(line: usize, msg: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("line {line}: {msg}"))
+}
+
+/// Запятые и переводы строк в текстовых полях заменяются пробелами.
+fn field(s: &str) -> String {
+    s.replace([',', '\n', '\r'], " ")
+}
+
+fn write_records(mut w: impl Write, records: &[Record]) -> io::Result<()> {
+    for r in records {
+        writeln!(
+            w,
+            "{},{},{},{},{},{},{}",
+            field(&r.run),
+            r.timestamp,
+            field(&r.machine),
+            field(&r.shape),
+            field(&r.algorithm),
+            r.radius,
+            r.time_us
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_csv(mut w: impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(w, "{CSV_HEADER}")?;
+    write_records(w, records)
+}
+
+pub fn read_csv(r: impl BufRead) -> io::Result<Vec<Record>> {
+    let mut records = vec![];
+    for (i, line) in r.lines().enumerate() {
+        let line = line?;
+        if i == 0 {
+            if line.trim() != CSV_HEADER {
+                return Err(invalid(1, format!("expected header `{CSV_HEADER}`")));
+            }
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+        let [run, timestamp, machine, shape, algorithm, radius, time] = fields[..] else {
+            return Err(invalid(i + 1, "expected 7 fields"));
+        };
+        records.push(Record {
+            run: run.to_owned(),
+            timestamp: timestamp.parse().map_err(|e| invalid(i + 1, e))?,
+            machine: machine.to_owned(),
+            shape: shape.to_owned(),
+            algorithm: algorithm.to_owned(),
+            radius: radius.parse().map_err(|e| invalid(i + 1, e))?,
+            time_us: time.parse().map_err(|e| invalid(i + 1, e))?,
+        });
+    }
+    Ok(records)
+}
+
+/// Дописывает замеры в файл; файл и каталог создаются, если их нет.
+pub fn append(path: impl AsRef<Path>, records: &[Record]) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file = OpenOptions::new().append(true).create(true).open(path)?;
+    let empty = file.metadata()?.len() == 0;
+    let mut w = BufWriter::new(file);
+    if empty {
+        writeln!(w, "{CSV_HEADER}")?;
+    }
+    write_records(&mut w, records)?;
+    w.flush()
+}
+
+/// Все замеры из файла; если файла ещё нет — пустой список.
+pub fn load(path: impl AsRef<Path>) -> io::Result<Vec<Record>> {
+    match File::open(path) {
+        Ok(file) => read_csv(BufReader::new(file)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Запуски в порядке первого появления.
+pub fn runs(records: &[Record]) -> Vec<Run> {
+    let mut res: Vec<Run> = vec![];
+    for r in records {
+        if !res.iter().any(|run| run.contains(r)) {
+            res.push(Run {
+                name: r.run.clone(),
+                timestamp: r.timestamp,
+                machine: r.machine.clone(),
+            });
+        }
+    }
+    res
+}
+
+/// Подписи запусков для интерфейса: название, а если оно повторяется —
+/// ещё и номер сессии с этим названием, начиная с 1.
+pub fn labels(runs: &[Run]) -> Vec<String> {
+    runs.iter()
+        .enumerate()
+        .map(|(i, run)| {
+            let same = |other: &&Run| other.name == run.name;
+            if runs.iter().filter(same).count() == 1 {
+                run.name.clone()
+            } else {
+                let n = runs[..i].iter().filter(same).count() + 1;
+                format!("{} #{n}", run.name)
+            }
+        })
+        .collect()
+}
+
+/// Среднее время по радиусам, отсортированное по радиусу.
+pub fn average<'a>(records: impl IntoIterator<Item = &'a Record>) -> Vec<(u32, f64)> {
+    let mut sums: Vec<(u32, f64, usize)> = vec![];
+    for r in records {
+        match sums.iter_mut().find(|(radius, ..)| *radius == r.radius) {
+            Some((_, sum, count)) => {
+                *sum += r.time_us;
+                *count += 1;
+            }
+            None => sums.push((r.radius, r.time_us, 1)),
+        }
+    }
+    sums.sort_by_key(|s| s.0);
+    sums.into_iter()
+        .map(|(radius, sum, count)| (radius, sum / count as f64))
+        .collect()
+}
+
+/// Серии средних по алгоритмам для фигуры `shape` — всех запусков или
+/// только `run`. Алгоритмы идут в порядке первого появления.
+pub fn series(records: &[Record], shape: &str, run: Option<&Run>) -> Vec<(String, Vec<(u32, f64)>)> {
+    let records = records
+        .iter()
+        .filter(|r| r.shape == shape && run.is_none_or(|run| run.contains(r)))
+        .collect::<Vec<_>>();
+    let mut algorithms: Vec<&str> = vec![];
+    for r in &records {
+        if !algorithms.contains(&r.algorithm.as_str()) {
+            algorithms.push(&r.algorithm);
+        }
+    }
+    algorithms
+        .into_iter()
+        .map(|a| {
+            let points = average(records.iter().copied().filter(|r| r.algorithm == a));
+            (a.to_owned(), points)
+        })
+        .collect()
+}
+
+/// Серии одного алгоритма по запускам — для сравнения сессий; серии
+/// подписаны как в `labels`.
+pub fn compare_runs(records: &[Record], shape: &str, algorithm: &str) -> Vec<(String, Vec<(u32, f64)>)> {
+    let runs = runs(records);
+    runs.iter()
+        .zip(labels(&runs))
+        .filter_map(|(run, label)| {
+            let points = average(
+                records
+                    .iter()
+                    .filter(|r| run.contains(r) && r.shape == shape && r.algorithm == algorithm),
+            );
+            (!points.is_empty()).then_some((label, points))
+        })
+        .collect()
+}
@@ -0,0 +1,138 @@
+//! Куда растеризаторы отдают пиксели. Каждый алгоритм пишет в `Sink`, так
+//! что одни и те же пиксели можно собрать в вектор точек любого типа
+//! (`Coord`), сразу нарисовать в изображении (`Brush`) или только
+//! посчитать (`Counter`) — так замеряется время без затрат на память.
+
+use crate::color::Color;
+use crate::point::{point, Coord, Point};
+
+pub trait Sink {
+    /// Пиксель полной интенсивности.
+    fn plot(&mut self, p: Point);
+
+    /// Пиксель, ослабленный на `i` — от 0 (полная интенсивность) до 255,
+    /// как в `abate_color`. По умолчанию ослабление не учитывается.
+    fn plot_shaded(&mut self, p: Point, i: f32) {
+        let _ = i;
+        self.plot(p);
+    }
+
+    /// Пиксели строки `y` от `x_from` до `x_to` включительно — так пишут
+    /// заливки.
+    fn span(&mut self, y: f32, x_from: f32, x_to: f32) {
+        let mut x = x_from;
+        while x <= x_to {
+            self.plot(point(x, y));
+            x += 1.0;
+        }
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for &mut S {
+    fn plot(&mut self, p: Point) {
+        (**self).plot(p);
+    }
+
+    fn plot_shaded(&mut self, p: Point, i: f32) {
+        (**self).plot_shaded(p, i);
+    }
+
+    fn span(&mut self, y: f32, x_from: f32, x_to: f32) {
+        (**self).span(y, x_from, x_to);
+    }
+}
+
+impl<P: Coord> Sink for Vec<P> {
+    fn plot(&mut self, p: Point) {
+        self.push(P::from_xy(p.x, p.y));
+    }
+}
+
+/// Пиксели вместе со степенью ослабления, у обычных она 0.
+impl<P: Coord> Sink for Vec<(P, f32)> {
+    fn plot(&mut self, p: Point) {
+        self.plot_shaded(p, 0.0);
+    }
+
+    fn plot_shaded(&mut self, p: Point, i: f32) {
+        self.push((P::from_xy(p.x, p.y), i));
+    }
+}
+
+/// Пиксели, собранные в вектор.
+pub fn collect<P>(draw: impl FnOnce(&mut Vec<P>)) -> Vec<P> {
+    let mut pixels = vec![];
+    draw(&mut pixels);
+    pixels
+}
+
+/// Только считает пиксели.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Counter(pub usize);
+
+impl Sink for Counter {
+    fn plot(&mut self, _: Point) {
+        self.0 += 1;
+    }
+
+    fn span(&mut self, _: f32, x_from: f32, x_to: f32) {
+        if x_to >= x_from {
+            self.0 += (x_to - x_from) as usize + 1;
+        }
+    }
+}
+
+/// Передаёт все пиксели дальше с одним и тем же ослаблением `i`.
+pub struct Shaded<'a, S: Sink + ?Sized> {
+    sink: &'a mut S,
+    i: f32,
+}
+
+impl<'a, S: Sink + ?Sized> Shaded<'a, S> {
+    pub fn new(sink: &'a mut S, i: f32) -> Self {
+        Shaded { sink, i }
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for Shaded<'_, S> {
+    fn plot(&mut self, p: Point) {
+        self.sink.plot_shaded(p, self.i);
+    }
+}
+
+/// Растровое изображение с цветом `Color`; координаты за его пределами
+/// молча отбрасываются.
+pub trait Image {
+    type Color: Color;
+
+    fn get(&self, x: i64, y: i64) -> Option<Self::Color>;
+
+    fn set(&mut self, x: i64, y: i64, color: Self::Color);
+}
+
+/// Рисует пиксели в изображение одним цветом; ослабленные смешиваются с
+/// тем, что уже нарисовано.
+pub struct Brush<'a, I: Image + ?Sized> {
+    image: &'a mut I,
+    color: I::Color,
+}
+
+impl<'a, I: Image + ?Sized> Brush<'a, I> {
+    pub fn new(image: &'a mut I, color: I::Color) -> Self {
+        Brush { image, color }
+    }
+}
+
+impl<I: Image + ?Sized> Sink for Brush<'_, I> {
+    fn plot(&mut self, p: Point) {
+        self.image
+            .set(p.x.round() as i64, p.y.round() as i64, self.color);
+    }
+
+    fn plot_shaded(&mut self, p: Point, i: f32) {
+        let (x, y) = (p.x.round() as i64, p.y.round() as i64);
+        if let Some(canvas) = self.image.get(x, y) {
+            self.image.set(x, y, self.color.abate(canvas, i));
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Равномерные кубические сплайны через заданные точки: B-сплайн (гладкий,
+//! но проходит только рядом с точками) и сплайн Катмулла — Рома (проходит
+//! через каждую точку). Каждый участок сплайна переводится в кубическую
+//! кривую Безье и растеризуется как она (`bezier`).
+//!
+//! Незамкнутый сплайн начинается в первой точке и кончается в последней:
+//! крайние точки повторяются — у сплайна Катмулла — Рома один раз, у
+//! B-сплайна два. Замкнутый берёт точки по кругу.
+
+use crate::bezier::{self, Bezier, Flattening};
+use crate::integer::Overflow;
+use crate::line::{LinePixels, Rasterizer};
+use crate::point::Point;
+use crate::polygon::Polygon;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    #[default]
+    BSpline,
+    CatmullRom,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 2] = [Kind::BSpline, Kind::CatmullRom];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::BSpline => "b-spline",
+            Kind::CatmullRom => "catmull-rom",
+        }
+    }
+
+    /// Участок сплайна между `p1` и `p2` в виде кривой Безье.
+    fn segment(self, [p0, p1, p2, p3]: [Point; 4]) -> Bezier {
+        match self {
+            Kind::BSpline => Bezier::Cubic([
+                (p0 + p1 * 4.0 + p2) * (1.0 / 6.0),
+                (p1 * 2.0 + p2) * (1.0 / 3.0),
+                (p1 + p2 * 2.0) * (1.0 / 3.0),
+                (p1 + p2 * 4.0 + p3) * (1.0 / 6.0),
+            ]),
+            Kind::CatmullRom => Bezier::Cubic([
+                p1,
+                p1 + (p2 - p0) * (1.0 / 6.0),
+                p2 - (p3 - p1) * (1.0 / 6.0),
+                p2,
+            ]),
+        }
+    }
+
+    /// Сколько раз повторяются крайние точки незамкнутого сплайна, чтобы
+    /// он начинался и кончался в них.
+    fn padding(self) -> usize {
+        match self {
+            Kind::BSpline => 2,
+            Kind::CatmullRom => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spline {
+    kind: Kind,
+    points: Vec<Point>,
+    closed: bool,
+}
+
+impl Spline {
+    /// Незамкнутому сплайну нужны хотя бы две точки, замкнутому — три.
+    pub fn new(kind: Kind, points: &[Point], closed: bool) -> Result<Self, String> {
+        let min = if closed { 3 } else { 2 };
+        if points.len() < min {
+            return Err(format!(
+                "{} points are not enough for a spline, need at least {min}",
+                points.len()
+            ));
+        }
+        Ok(Spline {
+            kind,
+            points: points.to_vec(),
+            closed,
+        })
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    pub fn closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Участки сплайна по порядку; у замкнутого конец последнего совпадает с
+    /// началом первого.
+    pub fn segments(&self) -> Vec<Bezier> {
+        let points = &self.points;
+        let n = points.len();
+        let window = |at: &dyn Fn(usize) -> Point, i: usize| {
+            self.kind.segment([at(i), at(i + 1), at(i + 2), at(i + 3)])
+        };
+        if self.closed {
+            let at = |i: usize| points[(i + n - 1) % n];
+            (0..n).map(|i| window(&at, i)).collect()
+        } else {
+            let pad = self.kind.padding();
+            let at = |i: usize| points[i.saturating_sub(pad).min(n - 1)];
+            (0..n + 2 * pad - 3).map(|i| window(&at, i)).collect()
+        }
+    }
+
+    /// Ломаная, отходящая от сплайна не дальше `tolerance`; у замкнутого
+    /// последняя вершина повторяет первую.
+    pub fn flatten(&self, flattening: Flattening, tolerance: f32) -> Vec<Point> {
+        let mut vertices = vec![];
+        for segment in self.segments() {
+            let points = segment.flatten(flattening, tolerance);
+            let skip = usize::from(!vertices.is_empty());
+            vertices.extend_from_slice(&points[skip..]);
+        }
+        vertices
+    }
+
+    /// Ломаная сплайна в пикселях (`bezier::pixel_polyline`).
+    pub fn polyline(&self, flattening: Flattening, tolerance: f32) -> Vec<Point> {
+        bezier::pixel_polyline(self.flatten(flattening, tolerance))
+    }
+
+    /// Замкнутый сплайн как многоугольник для заливки; `None`, если сплайн
+    /// не замкнут или стянулся меньше чем в три пикселя.
+    pub fn polygon(&self, flattening: Flattening, tolerance: f32) -> Option<Polygon> {
+        if !self.closed {
+            return None;
+        }
+        let vertices = self.polyline(flattening, tolerance);
+        let mut polygon = Polygon::default();
+        for &p in &vertices[..vertices.len() - 1] {
+            polygon.push_distinct(p);
+        }
+        polygon.close();
+        polygon.closed().then_some(polygon)
+    }
+
+    /// Пиксели звеньев ломаной (`polyline`) по порядку.
+    pub fn rasterize<R: Rasterizer + ?Sized>(
+        &self,
+        rasterizer: &R,
+        flattening: Flattening,
+        tolerance: f32,
+    ) -> Result<Vec<LinePixels>, Overflow> {
+        bezier::rasterize_polyline(&self.polyline(flattening, tolerance), rasterizer)
+    }
+}
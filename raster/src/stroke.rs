@@ -0,0 +1,346 @@
+//! Толстые линии: отрезки ломаной превращаются в прямоугольники, к ним
+//! добавляются концы и стыки, а закрашиваются пиксели, центры которых попали
+//! в объединение этих фигур. Каждый пиксель выдаётся ровно один раз.
+
+use crate::integer::Overflow;
+use crate::line::{LinePixels, Rasterizer};
+use crate::point::{point, Point};
+
+/// Форма свободного конца ломаной.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cap {
+    /// Обрезается точно по концу.
+    #[default]
+    Butt,
+    /// Полукруг диаметром в толщину линии.
+    Round,
+    /// Продлевается на половину толщины.
+    Square,
+}
+
+/// Форма стыка двух соседних отрезков.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Join {
+    /// Внешние края продлеваются до пересечения; слишком острые углы
+    /// (длиннее `miter_limit` толщин) срезаются как `Bevel`.
+    #[default]
+    Miter,
+    Round,
+    /// Внешние углы соединяются прямой.
+    Bevel,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    width: f32,
+    cap: Cap,
+    join: Join,
+    miter_limit: f32,
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Stroke::new(1.0)
+    }
+}
+
+/// Фигура, из которых складывается линия. Проверяется точка — центр пикселя.
+enum Piece {
+    /// Отрезок `a`–`b`, продлённый на `ext` с обеих сторон, полутолщины `hw`.
+    Band {
+        a: Point,
+        b: Point,
+        hw: f32,
+        ext: [f32; 2],
+    },
+    Disk {
+        c: Point,
+        r: f32,
+    },
+    Polygon(Vec<Point>),
+}
+
+fn cross(a: Point, b: Point) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Point, b: Point) -> f32 {
+    a.x * b.x + a.y * b.y
+}
+
+fn normalize(v: Point) -> Point {
+    v * (1.0 / dot(v, v).sqrt())
+}
+
+/// Левая нормаль.
+fn normal(v: Point) -> Point {
+    point(-v.y, v.x)
+}
+
+/// Пересечение строки `y` с выпуклым многоугольником. Все фигуры выпуклые,
+/// поэтому в строке от каждой остаётся один интервал.
+fn polygon_row(v: &[Point], y: f32) -> Option<(f32, f32)> {
+    let (mut lo, mut hi) = (f32::MAX, f32::MIN);
+    for i in 0..v.len() {
+        let (p, q) = (v[i], v[(i + 1) % v.len()]);
+        if y < p.y.min(q.y) || y > p.y.max(q.y) {
+            continue;
+        }
+        let xs = if p.y == q.y {
+            [p.x, q.x]
+        } else {
+            let x = p.x + (y - p.y) * (q.x - p.x) / (q.y - p.y);
+            [x, x]
+        };
+        for x in xs {
+            lo = lo.min(x);
+            hi = hi.max(x);
+        }
+    }
+    (lo <= hi).then_some((lo, hi))
+}
+
+/// Пересечение строки `y` с кругом.
+fn disk_row(c: Point, r: f32, y: f32) -> Option<(f32, f32)> {
+    let dy = y - c.y;
+    if dy.abs() > r {
+        return None;
+    }
+    let half = (r * r - dy * dy).sqrt();
+    Some((c.x - half, c.x + half))
+}
+
+impl Piece {
+    /// Вершины выпуклого многоугольника фигуры; у круга их нет.
+    fn corners(&self) -> Vec<Point> {
+        match self {
+            Piece::Band { a, b, hw, ext } => {
+                let dir = normalize(*b - *a);
+                let n = normal(dir) * *hw;
+                let (s, e) = (*a - dir * ext[0], *b + dir * ext[1]);
+                vec![s - n, e - n, e + n, s + n]
+            }
+            Piece::Disk { .. } => vec![],
+            Piece::Polygon(v) => v.clone(),
+        }
+    }
+
+    fn contains(&self, p: Point) -> bool {
+        match self {
+            Piece::Band { a, b, hw, ext } => {
+                let d = *b - *a;
+                let len = dot(d, d).sqrt();
+                let dir = d * (1.0 / len);
+                let rel = p - *a;
+                let t = dot(rel, dir);
+                -ext[0] <= t && t <= len + ext[1] && cross(dir, rel).abs() <= *hw
+            }
+            Piece::Disk { c, r } => {
+                let rel = p - *c;
+                dot(rel, rel) <= r * r
+            }
+            Piece::Polygon(v) => {
+                let (mut pos, mut neg) = (false, false);
+                for i in 0..v.len() {
+                    let c = cross(v[(i + 1) % v.len()] - v[i], p - v[i]);
+                    pos |= c > 0.0;
+                    neg |= c < 0.0;
+                }
+                !(pos && neg)
+            }
+        }
+    }
+}
+
+impl Stroke {
+    pub fn new(width: f32) -> Self {
+        Stroke {
+            width,
+            cap: Cap::default(),
+            join: Join::default(),
+            miter_limit: 4.0,
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn cap(&self) -> Cap {
+        self.cap
+    }
+
+    pub fn join(&self) -> Join {
+        self.join
+    }
+
+    pub fn set_width(&mut self, width: f32) {
+        self.width = width;
+    }
+
+    pub fn set_cap(&mut self, cap: Cap) {
+        self.cap = cap;
+    }
+
+    pub fn set_join(&mut self, join: Join) {
+        self.join = join;
+    }
+
+    /// Отношение длины острия к толщине, после которого `Miter` заменяется
+    /// на `Bevel`.
+    pub fn set_miter_limit(&mut self, limit: f32) {
+        self.miter_limit = limit;
+    }
+
+    pub fn segment(&self, points: &[Point; 2]) -> Vec<Point> {
+        self.polyline(points)
+    }
+
+    /// Пиксели толстой ломаной. Если первая и последняя вершины совпадают,
+    /// ломаная считается замкнутой: концов нет, в первой вершине — стык.
+    pub fn polyline(&self, points: &[Point]) -> Vec<Point> {
+        let mut vertices = points.to_vec();
+        vertices.dedup();
+        let closed = vertices.len() > 2 && vertices.first() == vertices.last();
+        if closed {
+            vertices.pop();
+        }
+        let pieces = self.pieces(&vertices, closed);
+        rasterize(&pieces)
+    }
+
+    fn pieces(&self, v: &[Point], closed: bool) -> Vec<Piece> {
+        let hw = self.width / 2.0;
+        let mut pieces = vec![];
+        match v {
+            [] => return pieces,
+            [p] => {
+                match self.cap {
+                    Cap::Butt => {}
+                    Cap::Round => pieces.push(Piece::Disk { c: *p, r: hw }),
+                    Cap::Square => pieces.push(Piece::Polygon(vec![
+                        *p + point(-hw, -hw),
+                        *p + point(hw, -hw),
+                        *p + point(hw, hw),
+                        *p + point(-hw, hw),
+                    ])),
+                }
+                return pieces;
+            }
+            _ => {}
+        }
+
+        let n = v.len();
+        let segments = if closed { n } else { n - 1 };
+        for i in 0..segments {
+            let (a, b) = (v[i], v[(i + 1) % n]);
+            let mut ext = [0.0, 0.0];
+            if !closed && self.cap == Cap::Square {
+                if i == 0 {
+                    ext[0] = hw;
+                }
+                if i == segments - 1 {
+                    ext[1] = hw;
+                }
+            }
+            pieces.push(Piece::Band { a, b, hw, ext });
+        }
+
+        if !closed && self.cap == Cap::Round {
+            pieces.push(Piece::Disk { c: v[0], r: hw });
+            pieces.push(Piece::Disk { c: v[n - 1], r: hw });
+        }
+
+        let joints = if closed { 0..n } else { 1..n - 1 };
+        for i in joints {
+            let (prev, cur, next) = (v[(i + n - 1) % n], v[i], v[(i + 1) % n]);
+            if let Some(piece) = self.join_piece(prev, cur, next, hw) {
+                pieces.push(piece);
+            }
+        }
+        pieces
+    }
+
+    fn join_piece(&self, prev: Point, cur: Point, next: Point, hw: f32) -> Option<Piece> {
+        if self.join == Join::Round {
+            return Some(Piece::Disk { c: cur, r: hw });
+        }
+        let d1 = normalize(cur - prev);
+        let d2 = normalize(next - cur);
+        let turn = cross(d1, d2);
+        if turn == 0.0 {
+            if dot(d1, d2) > 0.0 {
+                return None;
+            }
+            // разворот на 180°: стыка нет, конец закрывается как `Cap::Round`
+            // или, при остальных концах, как `Cap::Square`
+            if self.cap == Cap::Round {
+                return Some(Piece::Disk { c: cur, r: hw });
+            }
+            let n = normal(d1) * hw;
+            let tip = cur + d1 * hw;
+            return Some(Piece::Polygon(vec![cur - n, tip - n, tip + n, cur + n]));
+        }
+        // внешняя сторона поворота
+        let side = if turn > 0.0 { -1.0 } else { 1.0 };
+        let p1 = cur + normal(d1) * (hw * side);
+        let p2 = cur + normal(d2) * (hw * side);
+
+        let miter = normalize(normal(d1) + normal(d2));
+        let cos_half = dot(miter, normal(d1));
+        let miter_len = hw / cos_half;
+        if self.join == Join::Miter
+            && cos_half > 0.0
+            && 2.0 * miter_len <= self.miter_limit * self.width
+        {
+            let tip = cur + miter * (miter_len * side);
+            Some(Piece::Polygon(vec![cur, p1, tip, p2]))
+        } else {
+            Some(Piece::Polygon(vec![cur, p1, p2]))
+        }
+    }
+}
+
+impl Rasterizer for Stroke {
+    fn rasterize(&self, points: &[Point; 2]) -> Result<LinePixels, Overflow> {
+        Ok(LinePixels::Plain(self.segment(points)))
+    }
+}
+
+/// Каждая фигура заливается построчно: внутренность интервала строки
+/// принимается целиком, а крайние пиксели проверяются точно, чтобы
+/// погрешность пересечения не сдвигала границу.
+fn rasterize(pieces: &[Piece]) -> Vec<Point> {
+    let mut pixels = vec![];
+    for piece in pieces {
+        let corners = piece.corners();
+        let (lo, hi) = match piece {
+            Piece::Disk { c, r } => (c.y - r, c.y + r),
+            _ => corners.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+                (lo.min(p.y), hi.max(p.y))
+            }),
+        };
+        for y in lo.ceil() as i64..=hi.floor() as i64 {
+            let row = match piece {
+                Piece::Disk { c, r } => disk_row(*c, *r, y as f32),
+                _ => polygon_row(&corners, y as f32),
+            };
+            let Some((left, right)) = row else {
+                continue;
+            };
+            let (left, right) = (left.ceil() as i64, right.floor() as i64);
+            for x in left - 1..=right + 1 {
+                let edge = x <= left || x >= right;
+                if !edge || piece.contains(point(x as f32, y as f32)) {
+                    pixels.push((y, x));
+                }
+            }
+        }
+    }
+    pixels.sort_unstable();
+    pixels.dedup();
+    pixels
+        .into_iter()
+        .map(|(y, x)| point(x as f32, y as f32))
+        .collect()
+}
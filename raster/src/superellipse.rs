@@ -0,0 +1,147 @@
+//! Суперэллипс `|x/a|^n + |y/b|^n = 1`: при `n = 2` — эллипс, при больших
+//! `n` — прямоугольник со скруглёнными углами, при `n < 1` — звезда с
+//! острыми вершинами на осях.
+
+use crate::ellipse::plot_ellipse_pixels;
+use crate::implicit::{self, Bounds, Implicit};
+use crate::line::dda_to;
+use crate::point::{point, Point};
+use crate::sink::{self, Sink};
+
+#[derive(Debug, Clone)]
+pub struct Superellipse {
+    center: Point,
+    radius: Point,
+    exponent: f32,
+}
+
+/// Кривая с центром в начале координат.
+#[derive(Debug, Clone, Copy)]
+struct Curve {
+    a: f64,
+    b: f64,
+    n: f64,
+}
+
+impl Curve {
+    /// Производная `|v/r|^n` по `v`; в нуле при `n < 1` — бесконечность.
+    fn part(&self, v: f64, r: f64) -> f64 {
+        if v == 0.0 {
+            if self.n < 1.0 {
+                f64::INFINITY
+            } else {
+                0.0
+            }
+        } else {
+            self.n / r * (v.abs() / r).powf(self.n - 1.0) * v.signum()
+        }
+    }
+}
+
+impl Implicit for Curve {
+    fn value(&self, x: f64, y: f64) -> f64 {
+        (x.abs() / self.a).powf(self.n) + (y.abs() / self.b).powf(self.n) - 1.0
+    }
+
+    fn gradient(&self, x: f64, y: f64) -> (f64, f64) {
+        (self.part(x, self.a), self.part(y, self.b))
+    }
+}
+
+impl Superellipse {
+    pub fn new(center: Point, radius: Point, exponent: f32) -> Self {
+        Superellipse {
+            center,
+            radius,
+            exponent,
+        }
+    }
+
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    pub fn radius(&self) -> Point {
+        self.radius
+    }
+
+    pub fn exponent(&self) -> f32 {
+        self.exponent
+    }
+
+    pub fn set_radius(&mut self, radius: Point) {
+        self.radius = radius;
+    }
+
+    pub fn set_exponent(&mut self, exponent: f32) {
+        self.exponent = exponent;
+    }
+
+    fn curve(&self) -> Curve {
+        Curve {
+            a: self.radius.x as f64,
+            b: self.radius.y as f64,
+            n: self.exponent as f64,
+        }
+    }
+
+    /// Первая четверть вместе с пикселями на осях.
+    fn quadrant(&self) -> Bounds {
+        let (a, b) = (self.radius.x.ceil() as i64, self.radius.y.ceil() as i64);
+        Bounds::new((0, 0), (a + 1, b + 1))
+    }
+
+    /// Пиксели первой четверти отражаются в остальные три.
+    fn mirror<S: Sink + ?Sized>(&self, quadrant: Vec<(i64, i64)>, sink: &mut S) {
+        for (x, y) in quadrant {
+            plot_ellipse_pixels(x as f32, y as f32, self.center.x, self.center.y, sink);
+        }
+    }
+
+    /// Вырожденный суперэллипс — отрезок вдоль ненулевой полуоси; `false`,
+    /// если он не вырожден и ничего не нарисовано.
+    fn degenerate<S: Sink + ?Sized>(&self, sink: &mut S) -> bool {
+        if self.radius.x >= 1.0 && self.radius.y >= 1.0 && self.exponent > 0.0 {
+            return false;
+        }
+        let end = if self.radius.x >= self.radius.y {
+            point(self.radius.x, 0.0)
+        } else {
+            point(0.0, self.radius.y)
+        };
+        let ends = [self.center - end, self.center + end].map(Point::round);
+        dda_to(&ends, sink);
+        true
+    }
+
+    /// Обход первой четверти от вершины `(0, b)` с выбором пикселя по знаку
+    /// в средней точке. При `n < 1` в вершинах градиент бесконечен, и кривая
+    /// строится перебором клеток.
+    pub fn draw_midpoint(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_midpoint_to(pixels))
+    }
+
+    pub fn draw_midpoint_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        if self.degenerate(sink) {
+            return;
+        }
+        let curve = self.curve();
+        let bounds = self.quadrant();
+        let seed = (0, self.radius.y.round() as i64);
+        let quadrant = implicit::trace(&curve, &[seed], bounds)
+            .unwrap_or_else(|| implicit::march(&curve, bounds));
+        self.mirror(quadrant, sink);
+    }
+
+    /// Перебор клеток первой четверти.
+    pub fn draw_marching(&self) -> Vec<Point> {
+        sink::collect(|pixels| self.draw_marching_to(pixels))
+    }
+
+    pub fn draw_marching_to<S: Sink + ?Sized>(&self, sink: &mut S) {
+        if self.degenerate(sink) {
+            return;
+        }
+        self.mirror(implicit::march(&self.curve(), self.quadrant()), sink);
+    }
+}
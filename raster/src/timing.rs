@@ -0,0 +1,167 @@
+//! Результаты замеров времени в виде, пригодном для графиков лабораторных:
+//! бенчмарк `benches/rasterizers.rs` пишет их в CSV и JSON, лабораторные
+//! читают CSV.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const CSV_HEADER: &str = "group,algorithm,size,mean_ns,lower_ns,upper_ns";
+
+/// Один замер: алгоритм из группы (`line`, `circle`, `ellipse`) на входе
+/// размера `size` (длина отрезка или радиус). Границы — 95% доверительный
+/// интервал среднего.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub group: String,
+    pub algorithm: String,
+    pub size: u32,
+    pub mean_ns: f64,
+    pub lower_ns: f64,
+    pub upper_ns: f64,
+}
+
+/// Каталог отчётов criterion: `CRITERION_HOME`, иначе `criterion` в
+/// `CARGO_TARGET_DIR`, иначе `target/criterion` в каталоге `workspace`.
+pub fn criterion_dir_in(workspace: &Path) -> PathBuf {
+    if let Some(dir) = std::env::var_os("CRITERION_HOME") {
+        PathBuf::from(dir)
+    } else if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        PathBuf::from(dir).join("criterion")
+    } else {
+        workspace.join("target/criterion")
+    }
+}
+
+/// Каталог отчётов criterion относительно текущего каталога — лабораторные
+/// запускаются из корня workspace (`cargo run -p lab_3`).
+pub fn criterion_dir() -> PathBuf {
+    criterion_dir_in(Path::new(""))
+}
+
+/// Сводка бенчмарка в CSV в каталоге отчётов `dir`.
+pub fn summary_csv(dir: &Path) -> PathBuf {
+    dir.join("rasterizers.csv")
+}
+
+/// Та же сводка в JSON.
+pub fn summary_json(dir: &Path) -> PathBuf {
+    dir.join("rasterizers.json")
+}
+
+fn invalid(line: usize, msg: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("line {line}: {msg}"))
+}
+
+pub fn write_csv(mut w: impl Write, samples: &[Sample]) -> io::Result<()> {
+    writeln!(w, "{CSV_HEADER}")?;
+    for s in samples {
+        writeln!(
+            w,
+            "{},{},{},{},{},{}",
+            s.group, s.algorithm, s.size, s.mean_ns, s.lower_ns, s.upper_ns
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_json(mut w: impl Write, samples: &[Sample]) -> io::Result<()> {
+    writeln!(w, "[")?;
+    for (i, s) in samples.iter().enumerate() {
+        let sep = if i + 1 == samples.len() { "" } else { "," };
+        writeln!(
+            w,
+            r#"  {{"group": {}, "algorithm": {}, "size": {}, "mean_ns": {}, "lower_ns": {}, "upper_ns": {}}}{sep}"#,
+            json_string(&s.group),
+            json_string(&s.algorithm),
+            s.size,
+            s.mean_ns,
+            s.lower_ns,
+            s.upper_ns
+        )?;
+    }
+    writeln!(w, "]")
+}
+
+/// Строка JSON в кавычках: кавычки, обратная косая черта и управляющие
+/// символы экранируются по RFC 8259.
+fn json_string(s: &str) -> String {
+    let mut res = String::with_capacity(s.len() + 2);
+    res.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+pub fn read_csv(r: impl BufRead) -> io::Result<Vec<Sample>> {
+    let mut samples = vec![];
+    for (i, line) in r.lines().enumerate() {
+        let line = line?;
+        if i == 0 {
+            if line.trim() != CSV_HEADER {
+                return Err(invalid(1, format!("expected header `{CSV_HEADER}`")));
+            }
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+        let [group, algorithm, size, mean, lower, upper] = fields[..] else {
+            return Err(invalid(i + 1, "expected 6 fields"));
+        };
+        let num = |s: &str| s.parse::<f64>().map_err(|e| invalid(i + 1, e));
+        samples.push(Sample {
+            group: group.to_owned(),
+            algorithm: algorithm.to_owned(),
+            size: size.parse().map_err(|e| invalid(i + 1, e))?,
+            mean_ns: num(mean)?,
+            lower_ns: num(lower)?,
+            upper_ns: num(upper)?,
+        });
+    }
+    Ok(samples)
+}
+
+pub fn save_csv(path: impl AsRef<Path>, samples: &[Sample]) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_csv(&mut w, samples)?;
+    w.flush()
+}
+
+pub fn save_json(path: impl AsRef<Path>, samples: &[Sample]) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_json(&mut w, samples)?;
+    w.flush()
+}
+
+pub fn load_csv(path: impl AsRef<Path>) -> io::Result<Vec<Sample>> {
+    read_csv(BufReader::new(File::open(path)?))
+}
+
+/// Серии `(размер, среднее время в нс)` по алгоритмам группы, в порядке
+/// первого появления алгоритма.
+pub fn series(samples: &[Sample], group: &str) -> Vec<(String, Vec<(u32, f64)>)> {
+    let mut res: Vec<(String, Vec<(u32, f64)>)> = vec![];
+    for s in samples.iter().filter(|s| s.group == group) {
+        let point = (s.size, s.mean_ns);
+        match res.iter_mut().find(|(name, _)| *name == s.algorithm) {
+            Some((_, points)) => points.push(point),
+            None => res.push((s.algorithm.clone(), vec![point])),
+        }
+    }
+    for (_, points) in &mut res {
+        points.sort_by_key(|p| p.0);
+    }
+    res
+}
@@ -0,0 +1,121 @@
+use proptest::prelude::*;
+use raster::accuracy::{self, analyze, gaps, measure, radial_error, sweep, Algorithm, Shape};
+use raster::circle::Circle;
+use raster::{point, Point};
+
+#[test]
+fn radial_error_along_ray() {
+    let radius = point(10.0, 5.0);
+    assert_eq!(radial_error(point(10.0, 0.0), Point::ZERO, radius), 0.0);
+    assert_eq!(radial_error(point(0.0, -7.0), Point::ZERO, radius), 2.0);
+    assert!((radial_error(point(3.0, 4.0), point(0.0, 0.0), point(4.0, 4.0)) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn gaps_of_ring() {
+    let ring = [
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+        (0, -1),
+        (1, -1),
+    ]
+    .map(|(x, y)| point(x as f32, y as f32));
+    assert_eq!(gaps(&ring, Point::ZERO), 0);
+    let broken = ring
+        .iter()
+        .copied()
+        .filter(|&p| p != point(-1.0, 0.0))
+        .collect::<Vec<_>>();
+    assert_eq!(gaps(&broken, Point::ZERO), 1);
+    let broken = [ring[0], ring[1], ring[4], ring[5]];
+    assert_eq!(gaps(&broken, Point::ZERO), 2);
+}
+
+/// Точки на осях и диагонали рисуются дважды, и каждая из них —
+/// наложение октантов.
+#[test]
+fn overlap_of_symmetric_points() {
+    let circle = Circle::new(Point::ZERO, 1.0);
+    let pixels = circle.draw_midpoint();
+    let accuracy = analyze(
+        Shape::Circle,
+        &pixels,
+        &[point(0.0, 1.0)],
+        Point::ZERO,
+        point(1.0, 1.0),
+    );
+    assert_eq!(accuracy.pixels, 8);
+    assert_eq!(accuracy.duplicates, 4);
+    assert_eq!(accuracy.overlap, 1);
+    assert_eq!(accuracy.gaps, 0);
+}
+
+/// Каноническое уравнение заходит за диагональ, так что октанты
+/// накладываются сильнее, чем у Брезенхема.
+#[test]
+fn canonic_overlaps_more_than_bresenham() {
+    let radius = point(100.0, 100.0);
+    let canonic = measure(Shape::Circle, Algorithm::Canonic, radius);
+    let bresenham = measure(Shape::Circle, Algorithm::Bresenham, radius);
+    assert!(canonic.overlap > bresenham.overlap);
+    assert!(canonic.duplicates > bresenham.duplicates);
+}
+
+#[test]
+fn report() {
+    let records = sweep([point(10.0, 5.0), point(20.0, 10.0)]);
+    assert_eq!(records.len(), Shape::ALL.len() * Algorithm::ALL.len() * 2);
+    assert!(records
+        .iter()
+        .all(|r| r.shape != "circle" || r.radius.x == r.radius.y));
+
+    let mut csv = vec![];
+    accuracy::write_csv(&mut csv, &records).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("shape,algorithm,a,b,pixels,max_error,mean_error,duplicates,gaps,overlap")
+    );
+    assert!(lines.next().unwrap().starts_with("circle,canonic,10,10,"));
+    assert_eq!(lines.count(), records.len() - 1);
+
+    let mut md = vec![];
+    accuracy::write_markdown(&mut md, &records).unwrap();
+    assert_eq!(
+        String::from_utf8(md).unwrap().lines().count(),
+        records.len() + 2
+    );
+}
+
+proptest! {
+    /// Брезенхем и средняя точка дают сплошной контур не дальше полупикселя
+    /// от окружности.
+    #[test]
+    fn incremental_circles_are_exact(r in 1i32..300) {
+        for algorithm in [Algorithm::Bresenham, Algorithm::Midpoint] {
+            let accuracy = measure(Shape::Circle, algorithm, point(r as f32, r as f32));
+            prop_assert_eq!(accuracy.gaps, 0, "{}", algorithm.name());
+            prop_assert!(accuracy.max_error <= 0.5, "{}: {}", algorithm.name(), accuracy.max_error);
+            prop_assert!(accuracy.mean_error <= accuracy.max_error);
+        }
+    }
+
+    #[test]
+    fn duplicates_are_counted(a in 1i32..200, b in 1i32..200) {
+        for algorithm in Algorithm::ALL {
+            let radius = point(a as f32, b as f32);
+            let pixels = algorithm.draw(Shape::Ellipse, radius, true);
+            let accuracy = measure(Shape::Ellipse, algorithm, radius);
+            let mut unique = pixels.iter().map(|p| (p.x as i32, p.y as i32)).collect::<Vec<_>>();
+            unique.sort();
+            unique.dedup();
+            prop_assert_eq!(accuracy.pixels, pixels.len());
+            prop_assert_eq!(accuracy.duplicates, pixels.len() - unique.len());
+        }
+    }
+}
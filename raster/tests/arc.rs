@@ -0,0 +1,170 @@
+use proptest::prelude::*;
+use raster::arc::{ArcRange, Coverage, Sector, SectorKind};
+use raster::circle::Circle;
+use raster::ellipse::Ellipse;
+use raster::{point, Point};
+use std::collections::HashSet;
+
+fn sorted(mut pixels: Vec<Point>) -> Vec<(i32, i32)> {
+    let mut res = pixels
+        .drain(..)
+        .map(|p| (p.x as i32, p.y as i32))
+        .collect::<Vec<_>>();
+    res.sort();
+    res
+}
+
+/// Пиксели полной кривой, направление на которые попадает в промежуток.
+fn filtered(pixels: Vec<Point>, center: Point, arc: &ArcRange) -> Vec<(i32, i32)> {
+    sorted(
+        pixels
+            .into_iter()
+            .filter(|&p| arc.contains(p - center))
+            .collect(),
+    )
+}
+
+#[test]
+fn range() {
+    let arc = ArcRange::new(30.0, 120.0);
+    assert_eq!(arc.span(), 90.0);
+    assert!(arc.contains(point(0.0, 1.0)));
+    assert!(!arc.contains(point(1.0, 0.0)));
+    assert!(!arc.contains(point(0.0, -1.0)));
+    assert_eq!(arc.octant(0), Coverage::Partial);
+    assert_eq!(arc.octant(1), Coverage::Inside);
+    assert_eq!(arc.octant(2), Coverage::Partial);
+    assert_eq!(arc.octant(5), Coverage::Outside);
+
+    let reflex = ArcRange::new(300.0, 60.0);
+    assert_eq!(reflex.span(), 120.0);
+    assert!(reflex.contains(point(1.0, 0.0)));
+    assert_eq!(reflex.octant(7), Coverage::Inside);
+    assert_eq!(reflex.octant(0), Coverage::Inside);
+    assert_eq!(reflex.octant(3), Coverage::Outside);
+
+    let wide = ArcRange::new(0.0, 270.0);
+    assert!(wide.contains(point(-1.0, -0.5)));
+    assert!(!wide.contains(point(0.5, -1.0)));
+    assert!(ArcRange::new(-90.0, 270.0).is_full());
+
+    let empty = ArcRange::new(0.0, 0.0);
+    assert!(empty.contains(point(1.0, 0.0)));
+    assert!(!empty.contains(point(-1.0, 0.0)));
+}
+
+#[test]
+fn full_arc() {
+    let circle = Circle::new(point(10.0, 20.0), 30.0);
+    let mut arc = circle.clone();
+    arc.set_arc(ArcRange::new(0.0, 360.0));
+    assert_eq!(circle.draw_midpoint(), arc.draw_midpoint());
+}
+
+/// Граница замкнута: заливка по 4 соседям от затравки не выходит за
+/// описанный прямоугольник.
+fn check_closed(sector: &Sector, border: &[Point]) -> Result<(), TestCaseError> {
+    let set = border
+        .iter()
+        .map(|p| (p.x.round() as i32, p.y.round() as i32))
+        .collect::<HashSet<_>>();
+    let (min_x, max_x) = set.iter().fold((i32::MAX, i32::MIN), |(lo, hi), p| (lo.min(p.0), hi.max(p.0)));
+    let (min_y, max_y) = set.iter().fold((i32::MAX, i32::MIN), |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+    let seed = sector.seed(border);
+    let seed = (seed.x.round() as i32, seed.y.round() as i32);
+    prop_assert!(!set.contains(&seed), "seed {:?} is on the border", seed);
+    let (mut seen, mut stack) = (HashSet::from([seed]), vec![seed]);
+    while let Some((x, y)) = stack.pop() {
+        prop_assert!(
+            x > min_x && x < max_x && y > min_y && y < max_y,
+            "fill leaks through ({}, {})",
+            x,
+            y
+        );
+        for n in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if !set.contains(&n) && seen.insert(n) {
+                stack.push(n);
+            }
+        }
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn circle_arc(r in 1i32..200, start in 0.0f32..360.0, span in 0.0f32..360.0) {
+        let center = point(7.0, -3.0);
+        let range = ArcRange::new(start, start + span);
+        let full = Circle::new(center, r as f32);
+        let mut arc = full.clone();
+        arc.set_arc(range);
+        for (f, name) in [
+            (Circle::draw_canonic as fn(&Circle) -> Vec<Point>, "canonic"),
+            (Circle::draw_parametric, "parametric"),
+            (Circle::draw_bresenham, "bresenham"),
+            (Circle::draw_midpoint, "midpoint"),
+            (|c: &Circle| c.draw_wu().into_iter().map(|(p, _)| p).collect(), "wu"),
+        ] {
+            prop_assert_eq!(sorted(f(&arc)), filtered(f(&full), center, &range), "{}", name);
+        }
+    }
+
+    #[test]
+    fn ellipse_arc(a in 1i32..200, b in 1i32..200, start in 0.0f32..360.0, span in 0.0f32..360.0) {
+        let center = point(-5.0, 11.0);
+        let range = ArcRange::new(start, start + span);
+        let full = Ellipse::new(center, point(a as f32, b as f32));
+        let mut arc = full.clone();
+        arc.set_arc(range);
+        for (f, name) in [
+            (Ellipse::draw_canonic as fn(&Ellipse) -> Vec<Point>, "canonic"),
+            (Ellipse::draw_parametric, "parametric"),
+            (Ellipse::draw_bresenham, "bresenham"),
+            (Ellipse::draw_midpoint, "midpoint"),
+            (|e: &Ellipse| e.draw_wu().into_iter().map(|(p, _)| p).collect(), "wu"),
+        ] {
+            prop_assert_eq!(sorted(f(&arc)), filtered(f(&full), center, &range), "{}", name);
+        }
+    }
+
+    #[test]
+    fn pie_is_closed(a in 10i32..100, b in 10i32..100, start in 0.0f32..360.0, span in 20.0f32..360.0, cx in -50.0f32..50.0, cy in -50.0f32..50.0) {
+        let range = ArcRange::new(start, start + span);
+        let sector = Sector::new(point(cx, cy), point(a as f32, b as f32), range, SectorKind::Pie);
+        let border = sector.border(sector.ellipse().draw_bresenham());
+        check_closed(&sector, &border)?;
+    }
+
+    #[test]
+    fn chord_is_closed(a in 10i32..100, b in 10i32..100, start in 0.0f32..360.0, span in 60.0f32..360.0, cx in -50.0f32..50.0, cy in -50.0f32..50.0) {
+        let range = ArcRange::new(start, start + span);
+        let sector = Sector::new(point(cx, cy), point(a as f32, b as f32), range, SectorKind::Chord);
+        let arc = sector.ellipse().draw_bresenham();
+        // у слишком низкого сегмента внутренних пикселей нет
+        let (from, to) = range.directions();
+        let (p, q) = (sector.ray_point(from), sector.ray_point(to));
+        let height = arc
+            .iter()
+            .map(|&r| ((q - p).x * (r - p).y - (q - p).y * (r - p).x).abs() / p.distance(q))
+            .fold(0.0, f32::max);
+        prop_assume!(height >= 3.0);
+        let border = sector.border(arc);
+        check_closed(&sector, &border)?;
+    }
+}
+
+#[test]
+fn sector_polygon() {
+    let range = ArcRange::new(0.0, 90.0);
+    let pie = Sector::new(point(0.0, 0.0), point(10.0, 10.0), range, SectorKind::Pie);
+    let vertices = pie.polygon().vertices().to_vec();
+    assert_eq!(vertices[0], point(0.0, 0.0));
+    assert_eq!(vertices[1], point(10.0, 0.0));
+    assert_eq!(vertices[vertices.len() - 2], point(0.0, 10.0));
+    assert_eq!(vertices.last(), vertices.first());
+
+    let chord = Sector::new(point(0.0, 0.0), point(10.0, 10.0), range, SectorKind::Chord);
+    let vertices = chord.polygon().vertices().to_vec();
+    assert_eq!(vertices[0], point(10.0, 0.0));
+    assert!(!vertices.contains(&point(0.0, 0.0)));
+}
@@ -0,0 +1,143 @@
+use proptest::prelude::*;
+use raster::bezier::{Bezier, Flattening};
+use raster::line::{Algo, LinePixels};
+use raster::{point, Point};
+
+/// Расстояние от `p` до кривой по густой выборке точек.
+fn curve_distance(curve: &Bezier, p: Point) -> f32 {
+    (0..=2000)
+        .map(|i| curve.eval(i as f32 / 2000.0).distance(p))
+        .fold(f32::INFINITY, f32::min)
+}
+
+fn plain(pixels: Vec<LinePixels>) -> Vec<Point> {
+    pixels
+        .into_iter()
+        .flat_map(|p| match p {
+            LinePixels::Plain(v) => v,
+            LinePixels::Shaded(_) => panic!("expected plain pixels"),
+        })
+        .collect()
+}
+
+fn coord() -> impl Strategy<Value = f32> {
+    (-200i32..=200).prop_map(|v| v as f32 / 2.0)
+}
+
+fn control_point() -> impl Strategy<Value = Point> {
+    (coord(), coord()).prop_map(|(x, y)| point(x, y))
+}
+
+fn bezier() -> impl Strategy<Value = Bezier> {
+    prop_oneof![
+        [control_point(), control_point(), control_point()].prop_map(Bezier::Quadratic),
+        [
+            control_point(),
+            control_point(),
+            control_point(),
+            control_point()
+        ]
+        .prop_map(Bezier::Cubic),
+    ]
+}
+
+#[test]
+fn split_halves_meet_on_curve() {
+    let curve = Bezier::Cubic([
+        point(0.0, 0.0),
+        point(10.0, 40.0),
+        point(50.0, -20.0),
+        point(60.0, 10.0),
+    ]);
+    let (left, right) = curve.split(0.3);
+    let joint = curve.eval(0.3);
+    assert!(left.end().distance(joint) < 1e-4);
+    assert!(right.start().distance(joint) < 1e-4);
+    assert!(left.eval(0.5).distance(curve.eval(0.15)) < 1e-3);
+    assert!(right.eval(0.5).distance(curve.eval(0.65)) < 1e-3);
+}
+
+/// Прямые разности дают те же точки, что и вычисление по формуле.
+#[test]
+fn forward_differences_match_eval() {
+    let curves = [
+        Bezier::Quadratic([point(0.0, 0.0), point(30.0, 60.0), point(80.0, 0.0)]),
+        Bezier::Cubic([
+            point(0.0, 0.0),
+            point(0.0, 80.0),
+            point(80.0, -80.0),
+            point(80.0, 0.0),
+        ]),
+    ];
+    for curve in curves {
+        let points = curve.forward_differences(50);
+        assert_eq!(points.len(), 51);
+        for (i, p) in points.iter().enumerate() {
+            let expected = curve.eval(i as f32 / 50.0);
+            assert!(p.distance(expected) < 1e-2, "{i}: {p:?} != {expected:?}");
+        }
+    }
+}
+
+/// У кривой на одной прямой ломаная — одно звено.
+#[test]
+fn straight_curve_is_one_segment() {
+    let curve = Bezier::Cubic([
+        point(0.0, 0.0),
+        point(10.0, 10.0),
+        point(20.0, 20.0),
+        point(30.0, 30.0),
+    ]);
+    assert_eq!(
+        curve.subdivide(0.5),
+        vec![point(0.0, 0.0), point(30.0, 30.0)]
+    );
+    assert_eq!(curve.segments(0.5), 1);
+}
+
+#[test]
+fn degenerate_curve_is_one_pixel() {
+    let p = point(3.0, 4.0);
+    let curve = Bezier::Quadratic([p, p, p]);
+    for flattening in Flattening::ALL {
+        assert_eq!(
+            plain(curve.rasterize(&Algo::DDA, flattening, 0.5).unwrap()),
+            vec![p]
+        );
+    }
+}
+
+proptest! {
+    /// Вершины ломаной лежат на кривой, а звенья — не дальше допуска от неё.
+    #[test]
+    fn flattening_is_within_tolerance(curve in bezier(), tolerance in 0.25f32..2.0) {
+        for flattening in Flattening::ALL {
+            let vertices = curve.flatten(flattening, tolerance);
+            prop_assert_eq!(vertices[0], curve.start());
+            prop_assert_eq!(*vertices.last().unwrap(), curve.end());
+            for w in vertices.windows(2) {
+                let mid = (w[0] + w[1]) * 0.5;
+                let d = curve_distance(&curve, mid);
+                prop_assert!(d <= tolerance + 0.05, "{:?}: {} > {}", flattening, d, tolerance);
+            }
+        }
+    }
+
+    /// Пиксели кривой идут от начала к концу без разрывов и не дальше
+    /// пикселя с допуском от неё.
+    #[test]
+    fn rasterized_curve_is_connected(curve in bezier(), algo in prop::sample::select(vec![Algo::DDA, Algo::BresenhamReal])) {
+        for flattening in Flattening::ALL {
+            let pixels = plain(curve.rasterize(&algo, flattening, 0.5).unwrap());
+            prop_assert_eq!(pixels[0], curve.start().round());
+            prop_assert_eq!(*pixels.last().unwrap(), curve.end().round());
+            for w in pixels.windows(2) {
+                let d = w[1] - w[0];
+                prop_assert!(d.x.abs() <= 1.0 && d.y.abs() <= 1.0, "{:?} -> {:?}", w[0], w[1]);
+            }
+            for &p in &pixels {
+                prop_assert!(curve_distance(&curve, p) <= 0.5 + 1.5, "{:?}", p);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use raster::ellipse::{Ellipse, RotatedEllipse};
+use raster::{point, Point};
+
+#[test]
+fn degenerate_wu_ellipse_is_a_segment() {
+    let center = point(5.0, -2.0);
+    for radius in [point(0.0, 3.0), point(3.0, 0.0), point(0.0, 0.0)] {
+        let pixels = Ellipse::new(center, radius).draw_wu();
+        assert!(!pixels.is_empty());
+        for (p, i) in pixels {
+            let d = p - center;
+            assert_eq!(i, 0.0, "{radius:?}");
+            assert!(
+                d.x.abs() <= radius.x && d.y.abs() <= radius.y,
+                "{radius:?}: {p:?}"
+            );
+            assert!(d.x == 0.0 || d.y == 0.0, "{radius:?}: {p:?}");
+        }
+    }
+}
+
+/// У эллипса 15×20 точка с наклоном 1 — (9, 16), целая: её рисует только
+/// первый цикл, но рисует.
+#[test]
+fn wu_seam_is_drawn_once() {
+    let mut ellipse = Ellipse::new(point(0.0, 0.0), point(15.0, 20.0));
+    ellipse.set_symmetric(false);
+    let seam = ellipse
+        .draw_wu()
+        .into_iter()
+        .filter(|&(p, _)| p == point(9.0, 16.0))
+        .count();
+    assert_eq!(seam, 1);
+}
+
+/// Расстояние от пикселя до повёрнутого эллипса, посчитанное в `f64`:
+/// ближайшая точка кривой ищется методом Ньютона по параметру.
+fn curve_distance(p: Point, radius: Point, angle: f32) -> f64 {
+    let (sin, cos) = (-angle as f64).to_radians().sin_cos();
+    let (x, y) = (p.x as f64, p.y as f64);
+    let (x, y) = (x * cos - y * sin, x * sin + y * cos);
+    let (a, b) = (radius.x as f64, radius.y as f64);
+    let mut t = (a * y).atan2(b * x);
+    for _ in 0..20 {
+        let (st, ct) = t.sin_cos();
+        let (dx, dy) = (a * ct - x, b * st - y);
+        let grad = -dx * a * st + dy * b * ct;
+        let hess = a * a * st * st + b * b * ct * ct - dx * a * ct - dy * b * st;
+        t -= grad / hess;
+    }
+    let (st, ct) = t.sin_cos();
+    (a * ct - x).hypot(b * st - y)
+}
+
+/// У эллипса 1:50 меньший коэффициент уравнения в 2500 раз меньше большего;
+/// если он теряет точность при переводе в целые, контур уходит с кривой.
+/// Средняя точка гарантирует не больше полупикселя.
+#[test]
+fn thin_rotated_ellipse_stays_on_the_curve() {
+    let radius = point(5000.0, 100.0);
+    for angle in [10.0, 77.0, 89.0] {
+        let ellipse = RotatedEllipse::new(point(0.0, 0.0), radius, angle);
+        let worst = ellipse
+            .draw_midpoint()
+            .into_iter()
+            .map(|p| curve_distance(p, radius, angle))
+            .fold(0.0, f64::max);
+        assert!(worst <= 0.5 + 1e-3, "{angle}: {worst}");
+    }
+}
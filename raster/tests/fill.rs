@@ -0,0 +1,336 @@
+use proptest::prelude::*;
+use raster::fill::{antialiased, antialiased_to, spans, spans_to, Rule, Span, SAMPLES};
+use raster::sink::Counter;
+use raster::{abate_color, point, Framebuffer, Point, Rgba};
+use std::collections::HashMap;
+
+fn polygon(vertices: &[(i32, i32)]) -> Vec<Point> {
+    vertices
+        .iter()
+        .map(|&(x, y)| point(x as f32, y as f32))
+        .collect()
+}
+
+fn span(y: i32, x_from: i32, x_to: i32) -> Span {
+    Span {
+        y: y as f32,
+        x_from: x_from as f32,
+        x_to: x_to as f32,
+    }
+}
+
+fn pixels(spans: &[Span]) -> Vec<(i32, i32)> {
+    let mut res = spans
+        .iter()
+        .flat_map(|s| (s.x_from as i32..=s.x_to as i32).map(move |x| (x, s.y as i32)))
+        .collect::<Vec<_>>();
+    res.sort();
+    res
+}
+
+/// Строки идут от большего `y` к меньшему; центры на сторонах с меньшими `x`
+/// и `y` заливаются, на противоположных — нет, так что пикселей ровно
+/// столько, какова площадь.
+#[test]
+fn rectangle() {
+    let rect = polygon(&[(1, 0), (4, 0), (4, 3), (1, 3)]);
+    let expected = (0..=2).rev().map(|y| span(y, 1, 3)).collect::<Vec<_>>();
+    assert_eq!(spans(std::slice::from_ref(&rect), Rule::EvenOdd), expected);
+
+    // повтор первой вершины в конце ничего не меняет
+    let mut closed = rect.clone();
+    closed.push(rect[0]);
+    assert_eq!(spans(&[closed], Rule::NonZero), expected);
+}
+
+#[test]
+fn triangle() {
+    let triangle = polygon(&[(0, 0), (8, 0), (0, 4)]);
+    assert_eq!(
+        spans(&[triangle], Rule::EvenOdd),
+        [span(3, 0, 1), span(2, 0, 3), span(1, 0, 5), span(0, 0, 7)]
+    );
+}
+
+#[test]
+fn hole_is_not_filled() {
+    let outer = polygon(&[(0, 0), (10, 0), (10, 10), (0, 10)]);
+    let hole = polygon(&[(3, 3), (7, 3), (7, 7), (3, 7)]);
+    let filled = pixels(&spans(&[outer, hole], Rule::EvenOdd));
+    assert!(filled.contains(&(2, 5)) && filled.contains(&(8, 5)));
+    assert!(!filled.contains(&(5, 5)));
+    assert!(!filled.contains(&(5, 3)) && filled.contains(&(5, 7)));
+    assert!(filled.contains(&(5, 2)) && !filled.contains(&(5, 10)));
+}
+
+#[test]
+fn empty_and_flat_contours() {
+    for rule in Rule::ALL {
+        assert!(spans::<Vec<Point>>(&[], rule).is_empty());
+        assert!(spans(&[polygon(&[(0, 0), (5, 0), (9, 0)])], rule).is_empty());
+        assert!(spans(&[polygon(&[(3, 3)])], rule).is_empty());
+    }
+}
+
+/// Контуры, разнесённые по высоте, с пустыми строками между ними.
+#[test]
+fn separate_contours() {
+    let upper = polygon(&[(0, 10), (2, 10), (2, 12), (0, 12)]);
+    let lower = polygon(&[(5, 0), (6, 0), (6, 2), (5, 2)]);
+    assert_eq!(
+        spans(&[lower, upper], Rule::EvenOdd),
+        [span(11, 0, 1), span(10, 0, 1), span(1, 5, 5), span(0, 5, 5)]
+    );
+}
+
+/// Пятиконечная звезда одним контуром: по правилу чётности пятиугольник в
+/// середине — дыра, по ненулевому он залит.
+#[test]
+fn star() {
+    let star = (0..5)
+        .map(|i| {
+            let angle = (90.0 + 144.0 * i as f32).to_radians();
+            point(40.0 * angle.cos(), 40.0 * angle.sin()).round()
+        })
+        .collect::<Vec<_>>();
+    let even_odd = pixels(&spans(std::slice::from_ref(&star), Rule::EvenOdd));
+    let non_zero = pixels(&spans(&[star], Rule::NonZero));
+    // лучи залиты при любом правиле
+    for tip in [(0, 30), (-28, 9), (28, 9)] {
+        assert!(even_odd.contains(&tip) && non_zero.contains(&tip));
+    }
+    assert!(!even_odd.contains(&(0, 0)));
+    assert!(non_zero.contains(&(0, 0)));
+}
+
+/// Восьмёрка: половины обходятся в разные стороны, но обе залиты при любом
+/// правиле, а между ними пусто.
+#[test]
+fn figure_eight() {
+    let bowtie = polygon(&[(0, 0), (10, 10), (10, 0), (0, 10)]);
+    for rule in Rule::ALL {
+        let filled = pixels(&spans(std::slice::from_ref(&bowtie), rule));
+        assert!(filled.contains(&(1, 5)) && filled.contains(&(9, 5)));
+        assert!(!filled.contains(&(5, 1)) && !filled.contains(&(5, 9)));
+    }
+}
+
+/// Дыра, обходимая в ту же сторону, что и внешний контур, остаётся дырой
+/// только по правилу чётности; обходимая в обратную — при обоих правилах.
+#[test]
+fn hole_orientation() {
+    let outer = polygon(&[(0, 0), (10, 0), (10, 10), (0, 10)]);
+    let same = polygon(&[(3, 3), (7, 3), (7, 7), (3, 7)]);
+    let reversed = polygon(&[(3, 3), (3, 7), (7, 7), (7, 3)]);
+    let filled =
+        |hole: &[Point], rule| pixels(&spans(&[outer.as_slice(), hole], rule)).contains(&(5, 5));
+    assert!(!filled(&same, Rule::EvenOdd));
+    assert!(filled(&same, Rule::NonZero));
+    assert!(!filled(&reversed, Rule::EvenOdd));
+    assert!(!filled(&reversed, Rule::NonZero));
+}
+
+/// Три вложенных квадрата, обходимых в одну сторону.
+#[test]
+fn nested_contours() {
+    let square = |r: i32| polygon(&[(-r, -r), (r, -r), (r, r), (-r, r)]);
+    let contours = [square(30), square(20), square(10)];
+    let even_odd = pixels(&spans(&contours, Rule::EvenOdd));
+    let non_zero = pixels(&spans(&contours, Rule::NonZero));
+    for x in [25, 15, 0] {
+        assert!(non_zero.contains(&(x, 0)));
+    }
+    assert!(even_odd.contains(&(25, 0)));
+    assert!(!even_odd.contains(&(15, 0)));
+    assert!(even_odd.contains(&(0, 0)));
+}
+
+/// Треугольники вокруг общей вершины с дробными соседями: пиксель в
+/// вершине достаётся ровно одному, хотя по длинным рёбрам до него много
+/// строк.
+#[test]
+fn shared_vertex_is_painted_once() {
+    let (p, q) = (point(10.937538, 51.82765), point(20.712275, 50.0));
+    let fan = [
+        vec![point(10.0, 40.0), point(20.0, 40.0), p],
+        vec![point(20.0, 40.0), q, p],
+        vec![point(20.0, 40.0), point(30.0, 40.0), q],
+    ];
+    for rule in Rule::ALL {
+        let count = fan
+            .iter()
+            .filter(|triangle| {
+                pixels(&spans(std::slice::from_ref(*triangle), rule)).contains(&(20, 40))
+            })
+            .count();
+        assert_eq!(count, 1);
+    }
+}
+
+/// Число выборок, попавших в пиксель, по ослаблению из сглаженной заливки.
+fn samples(i: f32) -> u32 {
+    (SAMPLES as f32 * (255.0 - i) / 255.0).round() as u32
+}
+
+/// У квадрата с целыми вершинами внутренние пиксели покрыты целиком, на
+/// сторонах — наполовину, в углах — на четверть; в сумме выходит площадь.
+#[test]
+fn antialiased_square() {
+    let square = polygon(&[(0, 0), (10, 0), (10, 10), (0, 10)]);
+    for rule in Rule::ALL {
+        let pixels = antialiased(std::slice::from_ref(&square), rule)
+            .into_iter()
+            .map(|(p, i)| ((p.x as i32, p.y as i32), samples(i)))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(pixels.len(), 11 * 11);
+        assert_eq!(pixels.values().sum::<u32>(), 100 * SAMPLES as u32);
+        assert_eq!(pixels[&(5, 5)], 8);
+        assert_eq!(pixels[&(0, 5)], 4);
+        assert_eq!(pixels[&(5, 10)], 4);
+        assert_eq!(pixels[&(0, 0)], 2);
+        assert_eq!(pixels[&(10, 10)], 2);
+    }
+}
+
+/// Частично покрытые пиксели смешиваются с фоном так же, как в `abate_color`.
+#[test]
+fn antialiased_brush_blends_with_background() {
+    let triangle = polygon(&[(2, 2), (30, 2), (2, 20)]);
+    let (background, color) = (Rgba::WHITE, Rgba::from_rgb(200, 30, 60));
+    let mut fb = Framebuffer::new(32, 32, background);
+    antialiased_to(
+        std::slice::from_ref(&triangle),
+        Rule::NonZero,
+        &mut fb.brush(color),
+    );
+    for (p, i) in antialiased(&[triangle], Rule::NonZero) {
+        let expected = abate_color(color, background, i);
+        assert_eq!(fb.get(p.x as i64, p.y as i64), Some(expected));
+    }
+}
+
+proptest! {
+    /// В треугольнике с вершинами в четвертях пикселя при любом правиле
+    /// заливаются все пиксели, чей центр внутри, и ни один, чей центр
+    /// снаружи; сумма по счётчику равна числу пикселей в отрезках.
+    #[test]
+    fn triangle_matches_half_planes(
+        x1 in -200i32..200, y1 in -200i32..200,
+        x2 in -200i32..200, y2 in -200i32..200,
+        x3 in -200i32..200, y3 in -200i32..200,
+        rule in prop::sample::select(Rule::ALL.to_vec()),
+    ) {
+        let mut vertices = [(x1, y1), (x2, y2), (x3, y3)];
+        let area = (x2 - x1) * (y3 - y1) - (x3 - x1) * (y2 - y1);
+        prop_assume!(area != 0);
+        if area < 0 {
+            vertices.swap(1, 2);
+        }
+        let triangle = vertices
+            .map(|(x, y)| point(x as f32 / 4.0, y as f32 / 4.0))
+            .to_vec();
+        // расстояние со знаком до каждой стороны, внутри — положительное
+        let distance = |x: i32, y: i32| {
+            (0..3)
+                .map(|i| {
+                    let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                    let (ex, ey) = (b.x - a.x, b.y - a.y);
+                    (ex * (y as f32 - a.y) - ey * (x as f32 - a.x)) / ex.hypot(ey)
+                })
+                .fold(f32::INFINITY, f32::min)
+        };
+        let result = spans(std::slice::from_ref(&triangle), rule);
+        let filled = pixels(&result);
+        for &(x, y) in &filled {
+            prop_assert!(distance(x, y) >= -1e-3, "({}, {}) is outside", x, y);
+        }
+        for x in -50..50 {
+            for y in -50..50 {
+                if distance(x, y) > 1e-3 {
+                    prop_assert!(filled.binary_search(&(x, y)).is_ok(), "({}, {}) is missing", x, y);
+                }
+            }
+        }
+        let mut counter = Counter::default();
+        spans_to(&[triangle], rule, &mut counter);
+        prop_assert_eq!(counter.0, result.iter().map(Span::len).sum::<usize>());
+        prop_assert_eq!(counter.0, filled.len());
+    }
+
+    /// Всё, что залито по правилу чётности, залито и по ненулевому — в том
+    /// числе у самопересекающихся многоугольников.
+    #[test]
+    fn even_odd_within_non_zero(
+        vertices in prop::collection::vec((-40i32..40, -40i32..40), 3..9),
+    ) {
+        let contour = polygon(&vertices);
+        let even_odd = pixels(&spans(std::slice::from_ref(&contour), Rule::EvenOdd));
+        let non_zero = pixels(&spans(&[contour], Rule::NonZero));
+        for pixel in &even_odd {
+            prop_assert!(non_zero.binary_search(pixel).is_ok(), "{:?} is missing", pixel);
+        }
+    }
+
+    /// Сетка из треугольников с общими сторонами и дробными вершинами: каждый
+    /// пиксель внутри сетки залит ровно одним треугольником. Вершины сдвинуты
+    /// не больше чем на 2 пикселя, так что клетки остаются выпуклыми.
+    #[test]
+    fn triangulated_grid_tiles_exactly(
+        jitter in prop::collection::vec((-2.0f32..2.0, -2.0f32..2.0), 36),
+        diagonals in prop::collection::vec(any::<bool>(), 25),
+        rule in prop::sample::select(Rule::ALL.to_vec()),
+    ) {
+        const CELLS: usize = 5;
+        const SIZE: f32 = 10.0;
+        let vertex = |i: usize, j: usize| {
+            let (dx, dy) = jitter[j * (CELLS + 1) + i];
+            point(i as f32 * SIZE + dx, j as f32 * SIZE + dy)
+        };
+        let mut triangles = vec![];
+        for j in 0..CELLS {
+            for i in 0..CELLS {
+                let [a, b, c, d] = [vertex(i, j), vertex(i + 1, j), vertex(i + 1, j + 1), vertex(i, j + 1)];
+                // обход треугольников в разные стороны тоже не должен мешать
+                if diagonals[j * CELLS + i] {
+                    triangles.extend([vec![a, b, c], vec![a, d, c]]);
+                } else {
+                    triangles.extend([vec![a, b, d], vec![b, c, d]]);
+                }
+            }
+        }
+        let mut coverage = HashMap::<(i32, i32), u32>::new();
+        for triangle in &triangles {
+            for pixel in pixels(&spans(std::slice::from_ref(triangle), rule)) {
+                *coverage.entry(pixel).or_default() += 1;
+            }
+        }
+        for (pixel, count) in &coverage {
+            prop_assert_eq!(*count, 1, "{:?} is painted {} times", pixel, count);
+        }
+        let inner = 2..(CELLS as f32 * SIZE - 2.0) as i32;
+        for x in inner.clone() {
+            for y in inner.clone() {
+                prop_assert!(coverage.contains_key(&(x, y)), "({}, {}) is not painted", x, y);
+            }
+        }
+
+        // при сглаживании доли соседних треугольников в пикселе дают целый
+        let mut samples_in = HashMap::<(i32, i32), u32>::new();
+        for triangle in &triangles {
+            for (p, i) in antialiased(std::slice::from_ref(triangle), rule) {
+                *samples_in.entry((p.x as i32, p.y as i32)).or_default() += samples(i);
+            }
+        }
+        for (pixel, count) in &samples_in {
+            prop_assert!(*count <= SAMPLES as u32, "{:?} has {} samples", pixel, count);
+        }
+        // выборки отстоят от центра меньше чем на полпикселя
+        let inner = 3..(CELLS as f32 * SIZE - 3.0) as i32;
+        for x in inner.clone() {
+            for y in inner.clone() {
+                let count = samples_in.get(&(x, y)).copied();
+                prop_assert_eq!(count, Some(SAMPLES as u32), "({}, {})", x, y);
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use raster::{Framebuffer, Rgba};
+use std::io::ErrorKind;
+
+/// Буфер 3×2 с разными цветами во всех пикселях.
+fn sample() -> Framebuffer {
+    let mut image = Framebuffer::new(3, 2, Rgba::WHITE);
+    image.set(0, 0, Rgba::RED);
+    image.set(1, 0, Rgba::GREEN);
+    image.set(2, 0, Rgba::BLUE);
+    image.set(0, 1, Rgba::BLACK);
+    image.set(1, 1, Rgba::from_rgba(10, 20, 30, 128));
+    image
+}
+
+#[test]
+fn ppm_header_and_bytes() {
+    let mut buf = vec![];
+    sample().write_ppm(&mut buf).unwrap();
+    let mut expected = b"P6\n3 2\n255\n".to_vec();
+    expected.extend([255, 0, 0, 0, 255, 0, 0, 0, 255]);
+    // альфа-канал отбрасывается
+    expected.extend([0, 0, 0, 10, 20, 30, 255, 255, 255]);
+    assert_eq!(buf, expected);
+}
+
+#[test]
+fn png_round_trip() {
+    let image = sample();
+    let mut buf = vec![];
+    image.write_png(&mut buf).unwrap();
+
+    let mut reader = png::Decoder::new(buf.as_slice()).read_info().unwrap();
+    let mut bytes = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut bytes).unwrap();
+    assert_eq!((info.width, info.height), (3, 2));
+    assert_eq!(info.color_type, png::ColorType::Rgba);
+    assert_eq!(info.bit_depth, png::BitDepth::Eight);
+    let decoded = bytes[..info.buffer_size()]
+        .chunks(4)
+        .map(|c| Rgba::from_rgba(c[0], c[1], c[2], c[3]))
+        .collect::<Vec<_>>();
+    let original = (0..2)
+        .flat_map(|y| (0..3).map(move |x| (x, y)))
+        .map(|(x, y)| image.get(x, y).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(decoded, original);
+}
+
+#[test]
+fn save_by_extension() {
+    let dir = std::env::temp_dir().join(format!("raster-framebuffer-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let image = sample();
+
+    let ppm = dir.join("image.ppm");
+    image.save(&ppm).unwrap();
+    let mut expected = vec![];
+    image.write_ppm(&mut expected).unwrap();
+    assert_eq!(std::fs::read(&ppm).unwrap(), expected);
+
+    let png = dir.join("image.png");
+    image.save(&png).unwrap();
+    let mut expected = vec![];
+    image.write_png(&mut expected).unwrap();
+    assert_eq!(std::fs::read(&png).unwrap(), expected);
+
+    for name in ["image.bmp", "image"] {
+        let path = dir.join(name);
+        let err = image.save(&path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(!path.exists(), "{} was created", path.display());
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
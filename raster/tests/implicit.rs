@@ -0,0 +1,151 @@
+use proptest::prelude::*;
+use raster::accuracy::gaps;
+use raster::conic::Conic;
+use raster::superellipse::Superellipse;
+use raster::{point, Point};
+
+/// Проходит ли кривая `f = 0` не дальше пикселя от `p`: `f` меняет знак
+/// на сетке точек квадрата с центром в `p` и полустороной 1.
+fn near(f: impl Fn(f64, f64) -> f64, p: Point) -> bool {
+    let samples = (-4..=4).flat_map(|i| (-4..=4).map(move |j| (i as f64 / 4.0, j as f64 / 4.0)));
+    let values = samples
+        .map(|(dx, dy)| f(p.x as f64 + dx, p.y as f64 + dy))
+        .collect::<Vec<_>>();
+    values.iter().any(|&v| v <= 0.0) && values.iter().any(|&v| v >= 0.0)
+}
+
+fn superellipse(radius: Point, n: f64) -> impl Fn(f64, f64) -> f64 {
+    move |x, y| (x.abs() / radius.x as f64).powf(n) + (y.abs() / radius.y as f64).powf(n) - 1.0
+}
+
+fn conic([a, b, c, d, e, f]: [f64; 6]) -> impl Fn(f64, f64) -> f64 {
+    move |x, y| a * x * x + b * x * y + c * y * y + d * x + e * y + f
+}
+
+fn check_closed(pixels: &[Point], f: impl Fn(f64, f64) -> f64) -> Result<(), TestCaseError> {
+    prop_assert!(!pixels.is_empty());
+    for &p in pixels {
+        prop_assert!(
+            near(&f, p),
+            "{:?} is farther than a pixel from the curve",
+            p
+        );
+    }
+    prop_assert_eq!(gaps(pixels, Point::ZERO), 0);
+    Ok(())
+}
+
+/// При `n = 2` обход совпадает с эллипсом по средней точке с точностью до
+/// выбора на стыке дуг.
+#[test]
+fn superellipse_of_degree_two_is_ellipse() {
+    let radius = point(30.0, 20.0);
+    let pixels = Superellipse::new(Point::ZERO, radius, 2.0).draw_midpoint();
+    let ellipse = raster::ellipse::Ellipse::new(Point::ZERO, radius).draw_midpoint();
+    let common = pixels.iter().filter(|p| ellipse.contains(p)).count();
+    assert!(
+        common * 10 >= pixels.len() * 9,
+        "{common} of {}",
+        pixels.len()
+    );
+}
+
+/// Звезду с острыми вершинами обход не проходит, её рисует перебор клеток.
+#[test]
+fn astroid_falls_back_to_marching() {
+    let s = Superellipse::new(Point::ZERO, point(40.0, 40.0), 2.0 / 3.0);
+    assert_eq!(s.draw_midpoint(), s.draw_marching());
+    for &p in &s.draw_midpoint() {
+        assert!(near(superellipse(point(40.0, 40.0), 2.0 / 3.0), p), "{p:?}");
+    }
+}
+
+#[test]
+fn hyperbola_has_two_branches() {
+    let extent = point(50.0, 20.0);
+    let h = Conic::hyperbola(Point::ZERO, point(10.0, 5.0), extent);
+    let pixels = h.draw_midpoint();
+    let f = conic(h.coeffs());
+    assert!(pixels.iter().all(|&p| near(&f, p)));
+    assert!(pixels
+        .iter()
+        .all(|p| p.x.abs() <= 50.0 && p.y.abs() <= 20.0));
+    assert!(pixels.contains(&point(10.0, 0.0)));
+    assert!(pixels.contains(&point(-10.0, 0.0)));
+    // каждая ветвь доходит до верхней и нижней границы
+    for side in [1.0, -1.0] {
+        for y in [-20.0, 20.0] {
+            assert!(
+                pixels.iter().any(|p| p.x * side > 0.0 && p.y == y),
+                "{side} {y}"
+            );
+        }
+    }
+}
+
+#[test]
+fn parabola_segment() {
+    let p = Conic::parabola(point(100.0, 100.0), 5.0, point(40.0, 40.0));
+    let pixels = p.draw_midpoint();
+    assert!(pixels.contains(&point(100.0, 100.0)));
+    assert!(pixels.contains(&point(120.0, 120.0)));
+    assert!(pixels.contains(&point(80.0, 120.0)));
+    let f = conic(p.coeffs());
+    assert!(pixels.iter().all(|&q| near(&f, q - point(100.0, 100.0))));
+}
+
+/// Пара прямых `x² = y²` пересекается в нуле, где градиент нулевой.
+#[test]
+fn crossing_lines_fall_back_to_marching() {
+    let c = Conic::new(
+        Point::ZERO,
+        [1.0, 0.0, -1.0, 0.0, 0.0, 0.0],
+        point(10.0, 10.0),
+    );
+    let pixels = c.draw_midpoint();
+    assert_eq!(pixels, c.draw_marching());
+    for i in -10..=10 {
+        assert!(pixels.contains(&point(i as f32, i as f32)));
+        assert!(pixels.contains(&point(i as f32, -i as f32)));
+    }
+}
+
+/// Замкнутая ветвь внутри области находится по точкам с вертикальной
+/// касательной, даже если границу области не пересекает.
+#[test]
+fn ellipse_as_conic() {
+    // u² + u v + 2 v² = 400 при u = x - 5, v = y + 3
+    let coeffs = [1.0, 1.0, 2.0, -7.0, 7.0, -372.0];
+    let c = Conic::new(Point::ZERO, coeffs, point(100.0, 100.0));
+    let pixels = c.draw_midpoint();
+    assert_ne!(pixels, c.draw_marching());
+    assert_eq!(gaps(&pixels, point(5.0, -3.0)), 0);
+    assert!(pixels.iter().all(|&p| near(conic(coeffs), p)));
+}
+
+proptest! {
+    #[test]
+    fn superellipse_midpoint(a in 2i32..200, b in 2i32..200, n in 1.0f64..8.0) {
+        let radius = point(a as f32, b as f32);
+        let pixels = Superellipse::new(Point::ZERO, radius, n as f32).draw_midpoint();
+        check_closed(&pixels, superellipse(radius, n as f32 as f64))?;
+    }
+
+    #[test]
+    fn superellipse_marching(a in 2i32..100, b in 2i32..100, n in 0.3f64..8.0) {
+        let radius = point(a as f32, b as f32);
+        let pixels = Superellipse::new(Point::ZERO, radius, n as f32).draw_marching();
+        for &p in &pixels {
+            prop_assert!(near(superellipse(radius, n as f32 as f64), p), "{:?}", p);
+        }
+    }
+
+    #[test]
+    fn hyperbola_midpoint(a in 1i32..100, b in 1i32..100, w in 1i32..200, h in 1i32..200) {
+        let h = Conic::hyperbola(Point::ZERO, point(a as f32, b as f32), point(w as f32, h as f32));
+        let f = conic(h.coeffs());
+        for &p in &h.draw_midpoint() {
+            prop_assert!(near(&f, p), "{:?}", p);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use proptest::prelude::*;
+use raster::bezier::{Bezier, Flattening};
+use raster::circle::Circle;
+use raster::ellipse::Ellipse;
+use raster::integer::{self, Int, Overflow};
+use raster::jaggies;
+use raster::line::{bresenham_integer, bresenham_real, Algo};
+use raster::pattern::{Patterned, Solid};
+use raster::{point, Point};
+
+fn sorted(pixels: Vec<Point>) -> Vec<(i32, i32)> {
+    let mut res = pixels
+        .into_iter()
+        .map(|p| (p.x as i32, p.y as i32))
+        .collect::<Vec<_>>();
+    res.sort();
+    res
+}
+
+#[test]
+fn rounding_to_integer_type() {
+    assert_eq!(i16::from_f32(32767.4), Some(32767));
+    assert_eq!(i16::from_f32(32767.6), None);
+    assert_eq!(i16::from_f32(-32768.0), Some(-32768));
+    assert_eq!(i16::from_f32(-32768.6), None);
+    assert_eq!(i32::from_f32(f32::NAN), None);
+    assert_eq!(i64::from_f32(f32::INFINITY), None);
+    assert_eq!(i128::from_f32(-2.5), Some(-3));
+}
+
+#[test]
+fn overflow_is_reported() {
+    assert_eq!(integer::line::<i16>((-30000, 0), (30000, 0)), Err(Overflow));
+    assert_eq!(integer::circle::<i16>((0, 0), 20000), Err(Overflow));
+    assert_eq!(integer::circle::<i16>((32000, 0), 1000), Err(Overflow));
+    assert_eq!(
+        integer::ellipse::<i32>((0, 0), (50000, 50000)),
+        Err(Overflow)
+    );
+    assert!(integer::ellipse::<i64>((0, 0), (50000, 10)).is_ok());
+    assert_eq!(
+        bresenham_integer(&[point(0.0, 0.0), point(1e10, 0.0)]),
+        Err(Overflow)
+    );
+    assert_eq!(
+        bresenham_integer(&[point(0.0, 0.0), point(3e9, 0.0)]),
+        Err(Overflow)
+    );
+}
+
+/// Переполнение доходит до всех, кто рисует через `Algo` и `Rasterizer`.
+#[test]
+fn overflow_is_passed_up() {
+    let algo = Algo::BresenhamInteger;
+    let points = [point(0.0, 0.0), point(3e9, 0.0)];
+    assert_eq!(algo.draw(&points), Err(Overflow));
+    let mut pixels: Vec<Point> = vec![];
+    assert_eq!(algo.draw_to(&points, &mut pixels), Err(Overflow));
+    assert!(pixels.is_empty());
+    assert_eq!(Patterned::new(Solid).segment(&algo, &points), Err(Overflow));
+    assert_eq!(jaggies::sweep(&algo, 3e9, [0]), Err(Overflow));
+    let curve = Bezier::Quadratic([points[0], point(1.5e9, 1.0), points[1]]);
+    assert_eq!(
+        curve.rasterize(&algo, Flattening::Subdivision, 0.5),
+        Err(Overflow)
+    );
+}
+
+/// Радиус, не помещающийся в тип, — тоже переполнение, а не насыщение;
+/// дробные радиусы и полуоси округляются одинаково.
+#[test]
+fn shape_radius_is_checked_and_rounded() {
+    let huge = Circle::new(point(0.0, 0.0), 3e9);
+    assert_eq!(huge.draw_bresenham_integer(), Err(Overflow));
+    let huge = Ellipse::new(point(0.0, 0.0), point(1e19, 1.0));
+    assert_eq!(huge.draw_bresenham_integer(), Err(Overflow));
+
+    let rounded = |r: f32| Circle::new(point(0.0, 0.0), r).draw_bresenham_integer();
+    assert_eq!(rounded(4.6), rounded(5.0));
+    let ellipse =
+        |a: f32, b: f32| Ellipse::new(point(0.0, 0.0), point(a, b)).draw_bresenham_integer();
+    assert_eq!(ellipse(4.6, 2.5), ellipse(5.0, 3.0));
+}
+
+#[test]
+fn single_pixel_line() {
+    assert_eq!(integer::line((3i16, -4), (3, -4)), Ok(vec![(3, -4)]));
+}
+
+proptest! {
+    /// На целых концах результат тот же, что у варианта с `f32`.
+    #[test]
+    fn line_matches_float_bresenham(x1 in -500i32..500, y1 in -500i32..500, x2 in -500i32..500, y2 in -500i32..500) {
+        let points = [point(x1 as f32, y1 as f32), point(x2 as f32, y2 as f32)];
+        prop_assert_eq!(bresenham_integer(&points), Ok(bresenham_real(&points)));
+        let narrow = integer::line((x1 as i16, y1 as i16), (x2 as i16, y2 as i16)).unwrap();
+        let wide = integer::line((x1 as i128, y1 as i128), (x2 as i128, y2 as i128)).unwrap();
+        let narrow = narrow.iter().map(|&(x, y)| (x as i128, y as i128)).collect::<Vec<_>>();
+        prop_assert_eq!(narrow, wide);
+    }
+
+    #[test]
+    fn circle_matches_float_bresenham(r in 0i32..300, cx in -100i32..100, cy in -100i32..100) {
+        let circle = Circle::new(point(cx as f32, cy as f32), r as f32);
+        prop_assert_eq!(circle.draw_bresenham_integer(), Ok(circle.draw_bresenham()));
+        let pixels = integer::circle((cx, cy), r).unwrap();
+        prop_assert_eq!(sorted(integer::points(&pixels)), sorted(circle.draw_bresenham()));
+    }
+
+    #[test]
+    fn ellipse_matches_float_bresenham(a in 0i32..300, b in 0i32..300, cx in -100i32..100, cy in -100i32..100) {
+        let ellipse = Ellipse::new(point(cx as f32, cy as f32), point(a as f32, b as f32));
+        prop_assert_eq!(ellipse.draw_bresenham_integer(), Ok(ellipse.draw_bresenham()));
+        let pixels = integer::ellipse((cx as i64, cy as i64), (a as i64, b as i64)).unwrap();
+        prop_assert_eq!(sorted(integer::points(&pixels)), sorted(ellipse.draw_bresenham()));
+    }
+}
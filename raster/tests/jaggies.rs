@@ -0,0 +1,99 @@
+use raster::jaggies::{self, analyze, sweep, Record, Steps};
+use raster::line::{Algo, LinePixels};
+use raster::point;
+
+#[test]
+fn straight_lines_have_no_steps() {
+    let points = [point(0.0, 0.0), point(10.0, 0.0)];
+    let steps = analyze(&Algo::DDA.draw(&points).unwrap(), &points);
+    assert_eq!(
+        steps,
+        Steps {
+            steps: 0,
+            longest_run: 11,
+            mean_deviation: 0.0
+        }
+    );
+
+    let points = [point(0.0, 0.0), point(-6.0, 6.0)];
+    let steps = analyze(&Algo::BresenhamReal.draw(&points).unwrap(), &points);
+    assert_eq!((steps.steps, steps.longest_run), (6, 1));
+    assert_eq!(steps.mean_deviation, 0.0);
+}
+
+#[test]
+fn staircase() {
+    let points = [point(0.0, 0.0), point(5.0, 2.0)];
+    let pixels = LinePixels::Plain(
+        [(0, 0), (1, 0), (2, 1), (3, 1), (4, 1), (5, 2)]
+            .map(|(x, y)| point(x as f32, y as f32))
+            .into(),
+    );
+    let steps = analyze(&pixels, &points);
+    assert_eq!((steps.steps, steps.longest_run), (2, 3));
+    let expected = [0.0, 2.0, 1.0, 1.0, 3.0, 0.0]
+        .iter()
+        .map(|d| d / 29f32.sqrt())
+        .sum::<f32>()
+        / 6.0;
+    assert!((steps.mean_deviation - expected).abs() < 1e-6);
+}
+
+/// В столбце учитывается самый яркий пиксель, а не первый.
+#[test]
+fn brightest_pixel_of_column() {
+    let points = [point(0.0, 0.0), point(3.0, 1.0)];
+    let pixels = LinePixels::Shaded(vec![
+        (point(0.0, 0.0), 0.0),
+        (point(1.0, 0.0), 100.0),
+        (point(1.0, 1.0), 150.0),
+        (point(2.0, 0.0), 160.0),
+        (point(2.0, 1.0), 90.0),
+        (point(3.0, 1.0), 0.0),
+    ]);
+    let steps = analyze(&pixels, &points);
+    assert_eq!((steps.steps, steps.longest_run), (1, 2));
+}
+
+#[test]
+fn sweep_all_algorithms() {
+    for algo in Algo::ALL {
+        let rows = sweep(&algo, 100.0, 0..=90).unwrap();
+        assert_eq!(rows.len(), 91);
+        assert_eq!(rows[0].1.steps, 0, "{algo}");
+        assert_eq!(rows[90].1.steps, 0, "{algo}");
+        assert_eq!(rows[45].1.longest_run, 1, "{algo}");
+        for (angle, steps) in rows {
+            assert!(steps.steps <= 100, "{algo} at {angle}°: {steps:?}");
+            assert!(steps.mean_deviation < 1.0, "{algo} at {angle}°: {steps:?}");
+        }
+    }
+}
+
+#[test]
+fn export() {
+    let records = sweep(&Algo::DDA, 50.0, [0, 30])
+        .unwrap()
+        .into_iter()
+        .map(|(angle, steps)| Record {
+            algorithm: "dda".to_owned(),
+            angle,
+            steps,
+        })
+        .collect::<Vec<_>>();
+
+    let mut csv = vec![];
+    jaggies::write_csv(&mut csv, &records).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "algorithm,angle,steps,longest_run,mean_deviation");
+    assert_eq!(lines[1], "dda,0,0,51,0.0000");
+    assert!(lines[2].starts_with("dda,30,"));
+    assert_eq!(lines.len(), 3);
+
+    let mut md = vec![];
+    jaggies::write_markdown(&mut md, &records).unwrap();
+    let md = String::from_utf8(md).unwrap();
+    assert_eq!(md.lines().count(), 4);
+    assert_eq!(md.lines().nth(2), Some("| dda | 0 | 0 | 51 | 0.0000 |"));
+}
@@ -0,0 +1,168 @@
+//! Сравнение растеризации отрезков с эталонами из `tests/golden/`.
+//!
+//! Эталоны пересоздаются командой
+//! `GOLDEN_UPDATE=1 cargo test -p raster --test line_golden`.
+
+use raster::line::{
+    bresenham_float, bresenham_jaggiesless, bresenham_real, dda, gupta_sproull, wu, xiaolin_wu,
+};
+use raster::{point, Point};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+const CASES: [(&str, [Point; 2]); 14] = [
+    ("octant-1", [point(0.0, 0.0), point(7.0, 3.0)]),
+    ("octant-2", [point(0.0, 0.0), point(3.0, 7.0)]),
+    ("octant-3", [point(0.0, 0.0), point(-3.0, 7.0)]),
+    ("octant-4", [point(0.0, 0.0), point(-7.0, 3.0)]),
+    ("octant-5", [point(0.0, 0.0), point(-7.0, -3.0)]),
+    ("octant-6", [point(0.0, 0.0), point(-3.0, -7.0)]),
+    ("octant-7", [point(0.0, 0.0), point(3.0, -7.0)]),
+    ("octant-8", [point(0.0, 0.0), point(7.0, -3.0)]),
+    ("zero-length", [point(5.0, 5.0), point(5.0, 5.0)]),
+    ("horizontal", [point(-4.0, 2.0), point(4.0, 2.0)]),
+    ("horizontal-reversed", [point(4.0, 2.0), point(-4.0, 2.0)]),
+    ("vertical", [point(2.0, -4.0), point(2.0, 4.0)]),
+    ("diagonal", [point(0.0, 0.0), point(5.0, 5.0)]),
+    ("anti-diagonal", [point(0.0, 0.0), point(-5.0, 5.0)]),
+];
+
+/// Для алгоритмов, которые учитывают дробные концы.
+const FRACTIONAL: [(&str, [Point; 2]); 3] = [
+    ("fractional-flat", [point(0.3, 0.6), point(6.8, 2.2)]),
+    ("fractional-steep", [point(1.7, 0.2), point(-0.4, 5.9)]),
+    ("fractional-short", [point(2.2, 3.1), point(2.6, 3.3)]),
+];
+
+type Pixels = Vec<(Point, Option<f32>)>;
+type Rasterizer = Box<dyn Fn(&[Point; 2]) -> Pixels>;
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.txt"))
+}
+
+/// Пиксели каждого случая сортируются, так что сравнивается набор, а не порядок.
+fn render(cases: &[(&str, [Point; 2])], f: impl Fn(&[Point; 2]) -> Pixels) -> String {
+    let mut out = String::new();
+    for &(name, points) in cases {
+        let [a, b] = points;
+        writeln!(out, "== {name}: {},{} -> {},{}", a.x, a.y, b.x, b.y).unwrap();
+        let mut pixels = f(&points)
+            .into_iter()
+            .map(|(p, i)| (p.x as i32, p.y as i32, i.map(|i| format!(" {i:.2}"))))
+            .collect::<Vec<_>>();
+        pixels.sort();
+        for (x, y, i) in pixels {
+            writeln!(out, "{x} {y}{}", i.unwrap_or_default()).unwrap();
+        }
+    }
+    out
+}
+
+fn check(name: &str, actual: String) {
+    let path = golden_path(name);
+    if std::env::var_os("GOLDEN_UPDATE").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+        .replace("\r\n", "\n");
+    let mismatch = expected
+        .lines()
+        .zip(actual.lines())
+        .position(|(e, a)| e != a)
+        .unwrap_or(expected.lines().count().min(actual.lines().count()));
+    assert!(
+        expected == actual,
+        "{name} differs from {} at line {}:\nexpected: {:?}\nactual:   {:?}",
+        path.display(),
+        mismatch + 1,
+        expected.lines().nth(mismatch),
+        actual.lines().nth(mismatch),
+    );
+}
+
+fn plain(f: fn(&[Point; 2]) -> Vec<Point>) -> impl Fn(&[Point; 2]) -> Pixels {
+    move |points| f(points).into_iter().map(|p| (p, None)).collect()
+}
+
+fn shaded(f: fn(&[Point; 2]) -> Vec<(Point, f32)>) -> impl Fn(&[Point; 2]) -> Pixels {
+    move |points| f(points).into_iter().map(|(p, i)| (p, Some(i))).collect()
+}
+
+#[test]
+fn dda_golden() {
+    check("dda", render(&CASES, plain(dda)));
+}
+
+#[test]
+fn bresenham_float_golden() {
+    check("bresenham_float", render(&CASES, plain(bresenham_float)));
+}
+
+#[test]
+fn bresenham_real_golden() {
+    check("bresenham_real", render(&CASES, plain(bresenham_real)));
+}
+
+#[test]
+fn bresenham_jaggiesless_golden() {
+    check(
+        "bresenham_jaggiesless",
+        render(&CASES, shaded(bresenham_jaggiesless)),
+    );
+}
+
+#[test]
+fn wu_golden() {
+    check("wu", render(&CASES, shaded(wu)));
+}
+
+#[test]
+fn xiaolin_wu_golden() {
+    let cases = [&CASES[..], &FRACTIONAL].concat();
+    check("xiaolin_wu", render(&cases, shaded(xiaolin_wu)));
+}
+
+#[test]
+fn gupta_sproull_golden() {
+    let cases = [&CASES[..], &FRACTIONAL].concat();
+    check("gupta_sproull", render(&cases, shaded(gupta_sproull)));
+}
+
+/// Все алгоритмы ставят оба конца отрезка, и цвет там не ослаблен.
+#[test]
+fn endpoints_are_drawn() {
+    let algos: [(&str, Rasterizer); 5] = [
+        ("dda", Box::new(plain(dda))),
+        ("bresenham_float", Box::new(plain(bresenham_float))),
+        ("bresenham_real", Box::new(plain(bresenham_real))),
+        (
+            "bresenham_jaggiesless",
+            Box::new(shaded(bresenham_jaggiesless)),
+        ),
+        ("wu", Box::new(shaded(wu))),
+    ];
+    for (algo, f) in &algos {
+        for (name, points) in CASES {
+            let pixels = f(&points);
+            for end in points {
+                assert!(
+                    pixels.iter().any(|&(p, _)| p == end),
+                    "{algo}/{name}: endpoint {end:?} missing"
+                );
+            }
+            if *algo == "wu" {
+                for end in points {
+                    let (_, i) = pixels.iter().find(|&&(p, _)| p == end).unwrap();
+                    assert_eq!(*i, Some(0.0), "{algo}/{name}: endpoint {end:?} is abated");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use proptest::prelude::*;
+use raster::fill::{self, Rule};
+use raster::paint::{checker, Kind, Paint, PATTERN_CELL};
+use raster::{abate_color, point, Framebuffer, Rgba};
+
+const FROM: Rgba = Rgba::from_rgb(200, 30, 60);
+const TO: Rgba = Rgba::from_rgb(10, 120, 240);
+
+#[test]
+fn linear_goes_from_start_to_end() {
+    let paint = Paint::new(Kind::Linear, FROM, TO, point(0.0, 0.0), point(10.0, 0.0));
+    assert_eq!(paint.at(point(0.0, 5.0)), FROM);
+    assert_eq!(paint.at(point(10.0, -5.0)), TO);
+    assert_eq!(paint.at(point(5.0, 3.0)), abate_color(FROM, TO, 127.5));
+    // за концами цвет не меняется
+    assert_eq!(paint.at(point(-20.0, 0.0)), FROM);
+    assert_eq!(paint.at(point(30.0, 0.0)), TO);
+}
+
+#[test]
+fn radial_goes_from_center_to_circle() {
+    let paint = Paint::new(Kind::Radial, FROM, TO, point(0.0, 0.0), point(6.0, 8.0));
+    assert_eq!(paint.at(point(3.0, 4.0)), FROM);
+    assert_eq!(paint.at(point(8.0, 4.0)), TO);
+    assert_eq!(paint.at(point(50.0, 50.0)), TO);
+    assert_eq!(paint.at(point(3.0, 6.5)), abate_color(FROM, TO, 127.5));
+}
+
+#[test]
+fn degenerate_bounds_take_second_color() {
+    let p = point(4.0, 4.0);
+    assert_eq!(Paint::new(Kind::Linear, FROM, TO, p, p).at(p), TO);
+    assert_eq!(Paint::new(Kind::Radial, FROM, TO, p, p).at(p), TO);
+    assert_eq!(Paint::new(Kind::Solid, FROM, TO, p, p).solid(), Some(FROM));
+    assert_eq!(Paint::new(Kind::Linear, FROM, TO, p, p).solid(), None);
+}
+
+#[test]
+fn checker_cells() {
+    let tile = checker(FROM, TO, 2);
+    assert_eq!((tile.width(), tile.height()), (4, 4));
+    assert_eq!(tile.get(1, 1), Some(FROM));
+    assert_eq!(tile.get(2, 1), Some(TO));
+    assert_eq!(tile.get(1, 3), Some(TO));
+    assert_eq!(tile.get(3, 2), Some(FROM));
+}
+
+/// Заливка кистью с `Paint` красит каждый пиксель цветом из `Paint::at`.
+#[test]
+fn paint_brush_colors_each_pixel() {
+    let square = [
+        point(2.0, 2.0),
+        point(30.0, 2.0),
+        point(30.0, 30.0),
+        point(2.0, 30.0),
+    ];
+    for kind in Kind::ALL {
+        let paint = Paint::new(kind, FROM, TO, square[0], square[2]);
+        let mut image = Framebuffer::new(32, 32, Rgba::WHITE);
+        fill::spans_to(&[square], Rule::EvenOdd, &mut image.paint_brush(&paint));
+        for y in 0..32 {
+            for x in 0..32 {
+                let inside = (2..30).contains(&x) && (2..30).contains(&y);
+                let expected = if inside {
+                    paint.at(point(x as f32, y as f32))
+                } else {
+                    Rgba::WHITE
+                };
+                assert_eq!(
+                    image.get(x, y),
+                    Some(expected),
+                    "{} at {x}, {y}",
+                    kind.name()
+                );
+            }
+        }
+    }
+
+    // сплошная заливка ничем не отличается от обычной кисти
+    let mut solid = Framebuffer::new(32, 32, Rgba::WHITE);
+    let mut brush = Framebuffer::new(32, 32, Rgba::WHITE);
+    fill::antialiased_to(
+        &[square],
+        Rule::EvenOdd,
+        &mut solid.paint_brush(&Paint::Solid(FROM)),
+    );
+    fill::antialiased_to(&[square], Rule::EvenOdd, &mut brush.brush(FROM));
+    assert_eq!(solid, brush);
+}
+
+proptest! {
+    /// Узор повторяется с периодом в две клетки в обе стороны, в том числе
+    /// левее и выше `origin`.
+    #[test]
+    fn pattern_tiles_the_plane(
+        x in -100i32..100, y in -100i32..100, ox in -20i32..20, oy in -20i32..20,
+    ) {
+        let origin = point(ox as f32, oy as f32);
+        let paint = Paint::new(Kind::Pattern, FROM, TO, origin, origin);
+        let p = point(x as f32, y as f32);
+        let period = 2.0 * PATTERN_CELL as f32;
+        prop_assert_eq!(paint.at(p), paint.at(point(p.x + period, p.y)));
+        prop_assert_eq!(paint.at(p), paint.at(point(p.x, p.y - period)));
+        let cell = PATTERN_CELL as i32;
+        let odd = ((x - ox).div_euclid(cell) + (y - oy).div_euclid(cell)) % 2 != 0;
+        prop_assert_eq!(paint.at(p), if odd { TO } else { FROM });
+    }
+}
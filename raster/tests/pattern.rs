@@ -0,0 +1,95 @@
+use raster::line::{Algo, LinePixels, Rasterizer};
+use raster::pattern::{BitMask, Dashes, Patterned, Solid};
+use raster::stroke::Stroke;
+use raster::{point, Point};
+
+fn pixels(line: &LinePixels) -> Vec<Point> {
+    match line {
+        LinePixels::Plain(v) => v.clone(),
+        LinePixels::Shaded(v) => v.iter().map(|&(p, _)| p).collect(),
+    }
+}
+
+fn xs(line: &LinePixels) -> Vec<f32> {
+    pixels(line).iter().map(|p| p.x).collect()
+}
+
+#[test]
+fn bit_mask() {
+    let mut dotted = Patterned::new(BitMask::new(0b0011, 4).unwrap());
+    let line = dotted
+        .segment(&Algo::BresenhamReal, &[point(0.0, 0.0), point(9.0, 3.0)])
+        .unwrap();
+    assert_eq!(xs(&line), [0.0, 1.0, 4.0, 5.0, 8.0, 9.0]);
+}
+
+#[test]
+fn dashes_with_phase() {
+    let mut dashes = Dashes::new(&[3.0, 2.0]).unwrap();
+    dashes.set_phase(1.0);
+    let line = Patterned::new(&dashes)
+        .segment(&Algo::DDA, &[point(10.0, 0.0), point(0.0, 0.0)])
+        .unwrap();
+    // позиция пикселя — 10 - x, со сдвигом на 1 штрихи на позициях 0, 1, 4..=6, 9, 10
+    assert_eq!(xs(&line), [10.0, 9.0, 6.0, 5.0, 4.0, 1.0, 0.0]);
+}
+
+#[test]
+fn odd_dash_array_is_repeated() {
+    let dashes = "2 1 1".parse::<Dashes>().unwrap();
+    assert_eq!(dashes.dashes(), [2.0, 1.0, 1.0, 2.0, 1.0, 1.0]);
+    assert_eq!(dashes.period(), 8.0);
+}
+
+#[test]
+fn invalid_dash_arrays() {
+    assert!(Dashes::new(&[]).is_err());
+    assert!(Dashes::new(&[0.0, 0.0]).is_err());
+    assert!(Dashes::new(&[3.0, -1.0]).is_err());
+    assert!("3,x".parse::<Dashes>().is_err());
+}
+
+#[test]
+fn invalid_bit_masks() {
+    assert!(BitMask::new(0b1, 0).is_err());
+    assert!(BitMask::new(0b1, 33).is_err());
+    assert_eq!(BitMask::new(u32::MAX, 32).map(|m| m.period()), Ok(32));
+}
+
+/// Узор продолжается через вершину: второй отрезок начинается с позиции 5.
+#[test]
+fn phase_is_continuous_along_polyline() {
+    let mut dashed = Patterned::new(Dashes::new(&[3.0, 3.0]).unwrap());
+    let vertices = [point(0.0, 0.0), point(5.0, 0.0), point(5.0, 7.0)];
+    let lines = dashed.polyline(&Algo::BresenhamReal, &vertices).unwrap();
+    assert_eq!(pixels(&lines[0]), [0.0, 1.0, 2.0].map(|x| point(x, 0.0)));
+    assert_eq!(
+        pixels(&lines[1]),
+        [1.0, 2.0, 3.0, 7.0].map(|y| point(5.0, y))
+    );
+    assert_eq!(dashed.position(), 12.0);
+}
+
+/// Узор только убирает пиксели, а сплошной не убирает ничего.
+#[test]
+fn pattern_keeps_rasterizer_pixels() {
+    let thick = Stroke::new(3.0);
+    let rasterizers: Vec<Box<dyn Rasterizer>> = Algo::ALL
+        .into_iter()
+        .map(|algo| Box::new(algo) as Box<dyn Rasterizer>)
+        .chain([Box::new(thick) as Box<dyn Rasterizer>])
+        .collect();
+    let points = [point(-3.0, 2.0), point(17.0, 11.0)];
+    for r in &rasterizers {
+        let full = r.rasterize(&points).unwrap();
+        assert_eq!(
+            Patterned::new(Solid).segment(r.as_ref(), &points),
+            Ok(full.clone())
+        );
+        let dashed = Patterned::new(BitMask::new(0b101, 3).unwrap())
+            .segment(r.as_ref(), &points)
+            .unwrap();
+        assert!(dashed.len() < full.len());
+        assert!(pixels(&dashed).iter().all(|p| pixels(&full).contains(p)));
+    }
+}
@@ -0,0 +1,15 @@
+use raster::point;
+use raster::polygon::Polygon;
+
+/// В редакторе повторный щелчок в ту же точку — тоже вершина.
+#[test]
+fn push_keeps_repeated_vertices() {
+    let (a, b) = (point(0.0, 0.0), point(5.0, 0.0));
+    let mut polygon = Polygon::default();
+    polygon.push(a).push(b).push(b);
+    assert_eq!(polygon.vertices(), [a, b, b]);
+
+    let mut polygon = Polygon::default();
+    polygon.push_distinct(a).push_distinct(b).push_distinct(b);
+    assert_eq!(polygon.vertices(), [a, b]);
+}
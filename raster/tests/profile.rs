@@ -0,0 +1,120 @@
+use raster::profile::{self, read_csv, series, write_csv, Record, Run};
+
+fn run(name: &str) -> Run {
+    Run {
+        name: name.to_owned(),
+        timestamp: 1_700_000_000,
+        machine: "linux x86_64 8 threads".to_owned(),
+    }
+}
+
+fn record(name: &str, algorithm: &str, radius: u32, time_us: f64) -> Record {
+    run(name).record("circle", algorithm, radius, time_us)
+}
+
+#[test]
+fn csv_round_trip() {
+    let records = vec![
+        record("first", "midpoint", 1000, 12.5),
+        record("second", "bresenham", 2000, 3.0),
+    ];
+    let mut buf = vec![];
+    write_csv(&mut buf, &records).unwrap();
+    assert_eq!(read_csv(buf.as_slice()).unwrap(), records);
+}
+
+#[test]
+fn csv_errors() {
+    assert!(read_csv("1000 12\n".as_bytes()).is_err());
+    let header = "run,timestamp,machine,shape,algorithm,radius,time_us\n";
+    assert!(read_csv(format!("{header}a,1,m,circle,dda,10\n").as_bytes()).is_err());
+    let err = read_csv(format!("{header}a,1,m,circle,dda,ten,1\n").as_bytes()).unwrap_err();
+    assert!(err.to_string().starts_with("line 2:"), "{}", err);
+}
+
+#[test]
+fn commas_in_names() {
+    let mut buf = vec![];
+    write_csv(&mut buf, &[record("a, b", "midpoint", 10, 1.0)]).unwrap();
+    assert_eq!(read_csv(buf.as_slice()).unwrap()[0].run, "a  b");
+}
+
+#[test]
+fn append_and_load() {
+    let dir = std::env::temp_dir().join(format!("raster-profile-{}", std::process::id()));
+    let path = dir.join("runs.csv");
+    assert!(profile::load(&path).unwrap().is_empty());
+    profile::append(&path, &[record("first", "midpoint", 10, 1.0)]).unwrap();
+    profile::append(&path, &[record("second", "midpoint", 10, 3.0)]).unwrap();
+    let records = profile::load(&path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(records.len(), 2);
+    let runs = profile::runs(&records);
+    assert_eq!(runs.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), ["first", "second"]);
+    assert_eq!(
+        profile::compare_runs(&records, "circle", "midpoint"),
+        vec![
+            ("first".to_owned(), vec![(10, 1.0)]),
+            ("second".to_owned(), vec![(10, 3.0)]),
+        ]
+    );
+}
+
+#[test]
+fn series_average() {
+    let records = vec![
+        record("first", "bresenham", 2000, 4.0),
+        record("first", "bresenham", 1000, 1.0),
+        record("second", "bresenham", 1000, 3.0),
+        record("first", "midpoint", 1000, 5.0),
+    ];
+    assert_eq!(
+        series(&records, "circle", None),
+        vec![
+            ("bresenham".to_owned(), vec![(1000, 2.0), (2000, 4.0)]),
+            ("midpoint".to_owned(), vec![(1000, 5.0)]),
+        ]
+    );
+    assert_eq!(
+        series(&records, "circle", Some(&run("second"))),
+        vec![("bresenham".to_owned(), vec![(1000, 3.0)])]
+    );
+    assert!(series(&records, "ellipse", None).is_empty());
+}
+
+#[test]
+fn unnamed_run() {
+    let run = Run::new("  ");
+    assert_eq!(run.name, format!("run-{}", run.timestamp));
+    assert_eq!(Run::new("baseline").name, "baseline");
+}
+
+/// Сессии с одинаковым названием остаются разными запусками.
+#[test]
+fn same_name_sessions() {
+    let (first, mut second) = (run("baseline"), run("baseline"));
+    second.timestamp += 60;
+    let other = run("other");
+    let records = vec![
+        first.record("circle", "midpoint", 10, 1.0),
+        other.record("circle", "midpoint", 10, 2.0),
+        second.record("circle", "midpoint", 10, 3.0),
+        first.record("ellipse", "midpoint", 10, 4.0),
+    ];
+    let runs = profile::runs(&records);
+    assert_eq!(runs, vec![first.clone(), other, second.clone()]);
+    assert_eq!(profile::labels(&runs), ["baseline #1", "other", "baseline #2"]);
+    assert_eq!(
+        series(&records, "circle", Some(&second)),
+        vec![("midpoint".to_owned(), vec![(10, 3.0)])]
+    );
+    assert_eq!(
+        profile::compare_runs(&records, "circle", "midpoint"),
+        vec![
+            ("baseline #1".to_owned(), vec![(10, 1.0)]),
+            ("other".to_owned(), vec![(10, 2.0)]),
+            ("baseline #2".to_owned(), vec![(10, 3.0)]),
+        ]
+    );
+}
@@ -0,0 +1,310 @@
+//! Инварианты растеризации на случайных входных данных.
+
+use proptest::prelude::*;
+use raster::circle::Circle;
+use raster::ellipse::{Ellipse, RotatedEllipse};
+use raster::line::{
+    bresenham_float, bresenham_jaggiesless, bresenham_real, dda, gupta_sproull, wu, xiaolin_wu,
+};
+use raster::{point, Point};
+use std::collections::HashSet;
+
+fn endpoints() -> impl Strategy<Value = [Point; 2]> {
+    (-500i32..500, -500i32..500, -500i32..500, -500i32..500)
+        .prop_map(|(x1, y1, x2, y2)| [point(x1 as f32, y1 as f32), point(x2 as f32, y2 as f32)])
+}
+
+fn fractional_endpoints() -> impl Strategy<Value = [Point; 2]> {
+    let coord = || -500.0f32..500.0;
+    (coord(), coord(), coord(), coord()).prop_map(|(x1, y1, x2, y2)| [point(x1, y1), point(x2, y2)])
+}
+
+fn center() -> impl Strategy<Value = Point> {
+    (-500i32..500, -500i32..500).prop_map(|(x, y)| point(x as f32, y as f32))
+}
+
+/// Радиус окружности: целый, дробный или нулевой. Алгоритмы с целым
+/// состоянием рисуют окружность радиуса, округлённого до целого.
+fn radius() -> impl Strategy<Value = f32> {
+    prop_oneof![
+        Just(0.0f32),
+        (1i32..300).prop_map(|r| r as f32),
+        0.0f32..300.0,
+    ]
+}
+
+fn check_line(pixels: &[Point], [start, end]: [Point; 2]) -> Result<(), TestCaseError> {
+    let d = end - start;
+    prop_assert_eq!(pixels.len(), d.x.abs().max(d.y.abs()) as usize + 1);
+    prop_assert_eq!(pixels[0], start);
+    prop_assert_eq!(pixels[pixels.len() - 1], end);
+    for w in pixels.windows(2) {
+        let step = w[1] - w[0];
+        prop_assert!(
+            step.x.abs() <= 1.0 && step.y.abs() <= 1.0 && step != Point::ZERO,
+            "{:?} -> {:?} is not an 8-connected step",
+            w[0],
+            w[1]
+        );
+    }
+    Ok(())
+}
+
+/// Пересекает ли кривая `|p - center| / radius = 1` (покоординатно) квадрат
+/// с центром в пикселе `p` и полустороной `half`; при `half = 0.5` — лежит
+/// ли пиксель не дальше полупикселя от кривой.
+fn touches_ellipse(p: Point, center: Point, radius: Point, half: f32) -> bool {
+    const EPS: f32 = 1e-3;
+    let d = p - center;
+    let range = |d: f32, r: f32| {
+        let (lo, hi) = ((d.abs() - half) / r, (d.abs() + half) / r);
+        (lo.max(0.0), hi)
+    };
+    let (x_lo, x_hi) = range(d.x, radius.x);
+    let (y_lo, y_hi) = range(d.y, radius.y);
+    let near = x_lo * x_lo + y_lo * y_lo;
+    let far = x_hi * x_hi + y_hi * y_hi;
+    near <= 1.0 + EPS && far >= 1.0 - EPS
+}
+
+fn check_curve(pixels: &[Point], center: Point, radius: Point) -> Result<(), TestCaseError> {
+    prop_assert!(!pixels.is_empty());
+    for &p in pixels {
+        prop_assert!(
+            touches_ellipse(p, center, radius, 0.5),
+            "{:?} is farther than half a pixel from the curve",
+            p
+        );
+    }
+    Ok(())
+}
+
+/// У Ву второй пиксель пары лежит по другую сторону кривой, так что оба
+/// не дальше пикселя от неё.
+fn check_shaded(
+    pixels: &[(Point, f32)],
+    center: Point,
+    radius: Point,
+) -> Result<(), TestCaseError> {
+    prop_assert!(!pixels.is_empty());
+    for &(p, i) in pixels {
+        prop_assert!((0.0..=255.0).contains(&i), "{:?} has intensity {}", p, i);
+        prop_assert!(
+            touches_ellipse(p, center, radius, 1.0),
+            "{:?} is farther than a pixel from the curve",
+            p
+        );
+    }
+    Ok(())
+}
+
+/// Расстояние от центра пикселя до повёрнутого эллипса. Сначала грубая
+/// оценка `|f| / |grad f|`; у острых вершин она сильно завышена, тогда
+/// расстояние ищется по частой выборке точек кривой.
+fn rotated_distance(p: Point, center: Point, radius: Point, angle: f32) -> f32 {
+    let mut rel = p - center;
+    rel.rotate(-angle, Point::ZERO);
+    let (u, v) = (rel.x / radius.x, rel.y / radius.y);
+    let f = u * u + v * v - 1.0;
+    let grad = (2.0 * u / radius.x).hypot(2.0 * v / radius.y);
+    if grad > 0.0 && f.abs() / grad < 0.5 {
+        return f.abs() / grad;
+    }
+    let n = 64 * radius.x.max(radius.y) as usize;
+    (0..n)
+        .map(|i| {
+            let t = std::f32::consts::TAU * i as f32 / n as f32;
+            rel.distance(point(radius.x * t.cos(), radius.y * t.sin()))
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Пиксели близко к кривой, покрывают её без разрывов и связаны между
+/// собой. У тонкого эллипса обе стороны острия идут по одним и тем же
+/// пикселям, так что соседей у пикселя на самом острие может быть один.
+fn check_rotated(pixels: &[Point], ellipse: &RotatedEllipse) -> Result<(), TestCaseError> {
+    let (center, radius, angle) = (ellipse.center(), ellipse.radius(), ellipse.angle());
+    let set = pixels
+        .iter()
+        .map(|p| (p.x as i32, p.y as i32))
+        .collect::<HashSet<_>>();
+    let neighbours = |(x, y): (i32, i32)| {
+        (-1..=1)
+            .flat_map(move |dx| (-1..=1).map(move |dy| (x + dx, y + dy)))
+            .filter(move |&n| n != (x, y))
+    };
+    for &p in pixels {
+        let d = rotated_distance(p, center, radius, angle);
+        prop_assert!(d < 0.75, "{:?} is {} away from the curve", p, d);
+    }
+    let n = (4.0 * radius.x.max(radius.y)) as usize * 4;
+    for i in 0..n {
+        let t = std::f32::consts::TAU * i as f32 / n as f32;
+        let mut q = point(radius.x * t.cos(), radius.y * t.sin());
+        q.rotate(angle, Point::ZERO);
+        let q = (q + center).round();
+        let covered = set.contains(&(q.x as i32, q.y as i32))
+            || neighbours((q.x as i32, q.y as i32)).any(|n| set.contains(&n));
+        prop_assert!(covered, "curve point {:?} is not covered", q);
+    }
+    let first = *set.iter().next().unwrap();
+    let (mut seen, mut stack) = (HashSet::from([first]), vec![first]);
+    while let Some(p) = stack.pop() {
+        for n in neighbours(p) {
+            if set.contains(&n) && seen.insert(n) {
+                stack.push(n);
+            }
+        }
+    }
+    prop_assert_eq!(seen.len(), set.len(), "contour is not connected");
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn dda_line(points in endpoints()) {
+        check_line(&dda(&points), points)?;
+    }
+
+    #[test]
+    fn bresenham_float_line(points in endpoints()) {
+        check_line(&bresenham_float(&points), points)?;
+    }
+
+    #[test]
+    fn bresenham_real_line(points in endpoints()) {
+        check_line(&bresenham_real(&points), points)?;
+    }
+
+    #[test]
+    fn bresenham_jaggiesless_line(points in endpoints()) {
+        let pixels = bresenham_jaggiesless(&points).into_iter().map(|(p, _)| p).collect::<Vec<_>>();
+        check_line(&pixels, points)?;
+    }
+
+    /// У Ву на столбец приходится пара пикселей, проверяем первые из пар —
+    /// с неглавной координатой, округлённой вниз.
+    #[test]
+    fn wu_line(points in endpoints()) {
+        let pixels = wu(&points);
+        let main = if pixels.len() == 1 {
+            vec![pixels[0].0]
+        } else {
+            pixels.chunks(2).map(|pair| pair[0].0).collect()
+        };
+        check_line(&main, points)?;
+    }
+
+
+    /// Внутренние столбцы закрыты полностью, крайние — не больше чем на пиксель.
+    #[test]
+    fn xiaolin_wu_coverage(points in fractional_endpoints()) {
+        let pixels = xiaolin_wu(&points);
+        let columns = pixels
+            .chunks(2)
+            .map(|pair| pair.iter().map(|(_, i)| 1.0 - i / 255.0).sum::<f32>())
+            .collect::<Vec<_>>();
+        for (i, &coverage) in columns.iter().enumerate() {
+            if i == 0 || i + 1 == columns.len() {
+                prop_assert!((-1e-3..=1.0 + 1e-3).contains(&coverage), "end column {}", coverage);
+            } else {
+                prop_assert!((coverage - 1.0).abs() < 1e-3, "column {} covers {}", i, coverage);
+            }
+        }
+    }
+
+    /// Центральные пиксели троек — это отрезок Брезенхема, и он закрашен не
+    /// слабее соседей.
+    #[test]
+    fn gupta_sproull_line(points in endpoints()) {
+        let pixels = gupta_sproull(&points);
+        let main = if pixels.len() == 1 {
+            vec![pixels[0].0]
+        } else {
+            pixels.chunks(3).map(|t| t[0].0).collect()
+        };
+        check_line(&main, points)?;
+        for t in pixels.chunks(3) {
+            prop_assert!(t.iter().all(|&(_, i)| t[0].1 <= i));
+        }
+    }
+
+    #[test]
+    fn circle_canonic(center in center(), r in radius()) {
+        let circle = Circle::new(center, r);
+        check_curve(&circle.draw_canonic(), center, point(r, r))?;
+    }
+
+    #[test]
+    fn circle_parametric(center in center(), r in radius()) {
+        let circle = Circle::new(center, r);
+        check_curve(&circle.draw_parametric(), center, point(r, r))?;
+    }
+
+    #[test]
+    fn circle_bresenham(center in center(), r in radius()) {
+        let circle = Circle::new(center, r);
+        let r = r.round();
+        check_curve(&circle.draw_bresenham(), center, point(r, r))?;
+    }
+
+    #[test]
+    fn circle_midpoint(center in center(), r in radius()) {
+        let circle = Circle::new(center, r);
+        let r = r.round();
+        check_curve(&circle.draw_midpoint(), center, point(r, r))?;
+    }
+
+    #[test]
+    fn circle_wu(center in center(), r in radius()) {
+        let circle = Circle::new(center, r);
+        check_shaded(&circle.draw_wu(), center, point(r, r))?;
+    }
+
+    #[test]
+    fn ellipse_canonic(center in center(), a in 1i32..300, b in 1i32..300) {
+        let radius = point(a as f32, b as f32);
+        let ellipse = Ellipse::new(center, radius);
+        check_curve(&ellipse.draw_canonic(), center, radius)?;
+    }
+
+    #[test]
+    fn ellipse_parametric(center in center(), a in 1i32..300, b in 1i32..300) {
+        let radius = point(a as f32, b as f32);
+        let ellipse = Ellipse::new(center, radius);
+        check_curve(&ellipse.draw_parametric(), center, radius)?;
+    }
+
+    #[test]
+    fn ellipse_bresenham(center in center(), a in 1i32..300, b in 1i32..300) {
+        let radius = point(a as f32, b as f32);
+        let ellipse = Ellipse::new(center, radius);
+        check_curve(&ellipse.draw_bresenham(), center, radius)?;
+    }
+
+    #[test]
+    fn ellipse_midpoint(center in center(), a in 1i32..300, b in 1i32..300) {
+        let radius = point(a as f32, b as f32);
+        let ellipse = Ellipse::new(center, radius);
+        check_curve(&ellipse.draw_midpoint(), center, radius)?;
+    }
+
+    #[test]
+    fn ellipse_wu(center in center(), a in 1i32..300, b in 1i32..300) {
+        let radius = point(a as f32, b as f32);
+        let ellipse = Ellipse::new(center, radius);
+        check_shaded(&ellipse.draw_wu(), center, radius)?;
+    }
+
+    #[test]
+    fn rotated_ellipse_midpoint(center in center(), a in 1i32..300, b in 1i32..300, angle in 0.0f32..360.0) {
+        let ellipse = RotatedEllipse::new(center, point(a as f32, b as f32), angle);
+        check_rotated(&ellipse.draw_midpoint(), &ellipse)?;
+    }
+
+    #[test]
+    fn rotated_ellipse_bresenham(center in center(), a in 1i32..300, b in 1i32..300, angle in 0.0f32..360.0) {
+        let ellipse = RotatedEllipse::new(center, point(a as f32, b as f32), angle);
+        check_rotated(&ellipse.draw_bresenham(), &ellipse)?;
+    }
+}
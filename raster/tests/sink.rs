@@ -0,0 +1,112 @@
+use proptest::prelude::*;
+use raster::circle::Circle;
+use raster::conic::Conic;
+use raster::ellipse::{Ellipse, RotatedEllipse};
+use raster::line::{Algo, LinePixels, Rasterizer};
+use raster::sink::{Counter, Sink};
+use raster::superellipse::Superellipse;
+use raster::{point, Framebuffer, Point, Rgba};
+
+#[test]
+fn span_covers_both_ends() {
+    let mut pixels: Vec<Point> = vec![];
+    pixels.span(3.0, 2.0, 5.0);
+    assert_eq!(pixels, [2.0, 3.0, 4.0, 5.0].map(|x| point(x, 3.0)).to_vec());
+
+    let mut counter = Counter::default();
+    counter.span(0.0, 2.0, 5.0);
+    counter.span(1.0, 5.0, 2.0);
+    counter.plot(point(0.0, 0.0));
+    assert_eq!(counter, Counter(5));
+}
+
+#[test]
+fn any_coordinate_type() {
+    let circle = Circle::new(point(10.0, -5.0), 17.0);
+    let points = circle.draw_midpoint();
+    let mut tuples: Vec<(f32, f32)> = vec![];
+    let mut arrays: Vec<[f32; 2]> = vec![];
+    circle.draw_midpoint_to(&mut tuples);
+    circle.draw_midpoint_to(&mut arrays);
+    assert_eq!(tuples, points.iter().map(|&p| p.into()).collect::<Vec<_>>());
+    assert_eq!(
+        arrays,
+        points.iter().map(|p| [p.x, p.y]).collect::<Vec<_>>()
+    );
+
+    let mut shaded: Vec<((f32, f32), f32)> = vec![];
+    circle.draw_wu_to(&mut shaded);
+    let expected = circle
+        .draw_wu()
+        .into_iter()
+        .map(|(p, i)| (p.into(), i))
+        .collect::<Vec<_>>();
+    assert_eq!(shaded, expected);
+}
+
+/// Счётчик видит столько же пикселей, сколько попадает в вектор.
+#[test]
+fn counter_matches_vectors() {
+    fn count(draw: impl FnOnce(&mut Counter)) -> usize {
+        let mut counter = Counter::default();
+        draw(&mut counter);
+        counter.0
+    }
+
+    let center = point(3.0, 4.0);
+    let circle = Circle::new(center, 40.0);
+    assert_eq!(
+        count(|c| circle.draw_canonic_to(c)),
+        circle.draw_canonic().len()
+    );
+    assert_eq!(count(|c| circle.draw_wu_to(c)), circle.draw_wu().len());
+    let ellipse = Ellipse::new(center, point(50.0, 20.0));
+    assert_eq!(
+        count(|c| ellipse.draw_bresenham_to(c)),
+        ellipse.draw_bresenham().len()
+    );
+    let rotated = RotatedEllipse::new(center, point(50.0, 20.0), 30.0);
+    assert_eq!(
+        count(|c| rotated.draw_midpoint_to(c)),
+        rotated.draw_midpoint().len()
+    );
+    let flat = RotatedEllipse::new(center, point(50.0, 0.0), 30.0);
+    assert_eq!(
+        count(|c| flat.draw_canonic_to(c)),
+        flat.draw_canonic().len()
+    );
+    let superellipse = Superellipse::new(center, point(30.0, 20.0), 4.0);
+    assert_eq!(
+        count(|c| superellipse.draw_midpoint_to(c)),
+        superellipse.draw_midpoint().len()
+    );
+    let conic = Conic::hyperbola(center, point(5.0, 5.0), point(30.0, 30.0));
+    assert_eq!(
+        count(|c| conic.draw_marching_to(c)),
+        conic.draw_marching().len()
+    );
+}
+
+proptest! {
+    /// Кисть рисует в буфер то же, что `plot_all` и `plot_all_intensity` по
+    /// готовым пикселям.
+    #[test]
+    fn brush_matches_collected_pixels(
+        x1 in -5i32..70, y1 in -5i32..70, x2 in -5i32..70, y2 in -5i32..70,
+        algo in prop::sample::select(Algo::ALL.to_vec()),
+    ) {
+        let points = [point(x1 as f32, y1 as f32), point(x2 as f32, y2 as f32)];
+        let (background, color) = (Rgba::WHITE, Rgba::from_rgb(200, 30, 60));
+        let mut expected = Framebuffer::new(64, 64, background);
+        match algo.draw(&points).unwrap() {
+            LinePixels::Plain(v) => expected.plot_all(&v, color),
+            LinePixels::Shaded(v) => expected.plot_all_intensity(&v, color),
+        }
+        let mut direct = Framebuffer::new(64, 64, background);
+        algo.draw_to(&points, &mut direct.brush(color)).unwrap();
+        prop_assert_eq!(&direct, &expected);
+        let mut via_trait = Framebuffer::new(64, 64, background);
+        algo.rasterize_to(&points, &mut via_trait.brush(color)).unwrap();
+        prop_assert_eq!(&via_trait, &expected);
+    }
+}
@@ -0,0 +1,113 @@
+use proptest::prelude::*;
+use raster::bezier::Flattening;
+use raster::line::{Algo, LinePixels};
+use raster::spline::{Kind, Spline};
+use raster::{point, Point};
+
+fn points() -> Vec<Point> {
+    vec![
+        point(10.0, 10.0),
+        point(60.0, 80.0),
+        point(120.0, 20.0),
+        point(180.0, 90.0),
+    ]
+}
+
+#[test]
+fn catmull_rom_passes_through_points() {
+    for closed in [false, true] {
+        let spline = Spline::new(Kind::CatmullRom, &points(), closed).unwrap();
+        let segments = spline.segments();
+        assert_eq!(segments.len(), if closed { 4 } else { 3 });
+        for (segment, p) in segments.iter().zip(points()) {
+            assert_eq!(segment.start(), p);
+        }
+    }
+}
+
+#[test]
+fn open_splines_start_and_end_at_points() {
+    for kind in Kind::ALL {
+        let spline = Spline::new(kind, &points(), false).unwrap();
+        let vertices = spline.flatten(Flattening::Subdivision, 0.5);
+        assert!(vertices[0].distance(point(10.0, 10.0)) < 1e-4, "{kind:?}");
+        assert!(
+            vertices.last().unwrap().distance(point(180.0, 90.0)) < 1e-4,
+            "{kind:?}"
+        );
+    }
+}
+
+/// B-сплайн по точкам на прямой с равным шагом — та же прямая.
+#[test]
+fn b_spline_of_collinear_points_is_straight() {
+    let points = (0..5)
+        .map(|i| point(i as f32 * 10.0, i as f32 * 5.0))
+        .collect::<Vec<_>>();
+    let spline = Spline::new(Kind::BSpline, &points, false).unwrap();
+    for segment in spline.segments() {
+        for &p in segment.points() {
+            assert!((p.y - p.x / 2.0).abs() < 1e-4, "{p:?}");
+        }
+    }
+}
+
+#[test]
+fn closed_spline_is_polygon() {
+    for kind in Kind::ALL {
+        let spline = Spline::new(kind, &points(), true).unwrap();
+        let polygon = spline.polygon(Flattening::Subdivision, 0.5).unwrap();
+        let vertices = polygon.vertices();
+        assert!(polygon.closed());
+        assert!(vertices.len() > points().len());
+        assert_eq!(vertices[0], vertices[vertices.len() - 1]);
+        assert!(vertices.iter().all(|p| *p == p.round()));
+    }
+    let open = Spline::new(Kind::BSpline, &points(), false).unwrap();
+    assert!(open.polygon(Flattening::Subdivision, 0.5).is_none());
+}
+
+#[test]
+fn too_few_points() {
+    assert!(Spline::new(Kind::CatmullRom, &points()[..1], false).is_err());
+    assert!(Spline::new(Kind::CatmullRom, &points()[..2], false).is_ok());
+    assert!(Spline::new(Kind::BSpline, &points()[..2], true).is_err());
+}
+
+fn control_points() -> impl Strategy<Value = Vec<Point>> {
+    prop::collection::vec(
+        (-100i32..=100, -100i32..=100).prop_map(|(x, y)| point(x as f32, y as f32)),
+        3..8,
+    )
+}
+
+proptest! {
+    /// Пиксели сплайна идут без разрывов; у замкнутого — по кругу.
+    #[test]
+    fn rasterized_spline_is_connected(
+        points in control_points(),
+        kind in prop::sample::select(Kind::ALL.to_vec()),
+        closed in any::<bool>(),
+    ) {
+        let spline = Spline::new(kind, &points, closed).unwrap();
+        let pixels = spline
+            .rasterize(&Algo::BresenhamReal, Flattening::ForwardDifferencing, 0.5)
+            .unwrap()
+            .into_iter()
+            .flat_map(|p| match p {
+                LinePixels::Plain(v) => v,
+                LinePixels::Shaded(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        for w in pixels.windows(2) {
+            let d = w[1] - w[0];
+            prop_assert!(d.x.abs() <= 1.0 && d.y.abs() <= 1.0, "{:?} -> {:?}", w[0], w[1]);
+        }
+        if closed {
+            prop_assert_eq!(pixels[0], *pixels.last().unwrap());
+        } else {
+            prop_assert_eq!(pixels[0], points[0]);
+            prop_assert_eq!(*pixels.last().unwrap(), points[points.len() - 1]);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use raster::stroke::{Cap, Join, Stroke};
+use raster::{point, Point};
+
+fn stroke(width: f32, cap: Cap, join: Join) -> Stroke {
+    let mut s = Stroke::new(width);
+    s.set_cap(cap);
+    s.set_join(join);
+    s
+}
+
+fn has(pixels: &[Point], x: f32, y: f32) -> bool {
+    pixels.contains(&point(x, y))
+}
+
+#[test]
+fn caps() {
+    let segment = [point(0.0, 0.0), point(10.0, 0.0)];
+    let butt = stroke(3.0, Cap::Butt, Join::Miter).segment(&segment);
+    let square = stroke(3.0, Cap::Square, Join::Miter).segment(&segment);
+    let round = stroke(3.0, Cap::Round, Join::Miter).segment(&segment);
+    assert_eq!(butt.len(), 11 * 3);
+    assert_eq!(square.len(), 13 * 3);
+    assert!(has(&square, -1.0, -1.0) && has(&square, 11.0, 1.0));
+    assert!(has(&round, -1.0, 0.0) && !has(&round, -2.0, 0.0));
+    assert!(butt
+        .iter()
+        .all(|p| (0.0..=10.0).contains(&p.x) && p.y.abs() <= 1.0));
+}
+
+#[test]
+fn joins() {
+    let corner = [point(0.0, 0.0), point(10.0, 0.0), point(10.0, 10.0)];
+    let miter = stroke(3.0, Cap::Butt, Join::Miter).polyline(&corner);
+    let bevel = stroke(3.0, Cap::Butt, Join::Bevel).polyline(&corner);
+    let round = stroke(3.0, Cap::Butt, Join::Round).polyline(&corner);
+    assert!(has(&miter, 11.0, -1.0));
+    assert!(!has(&bevel, 11.0, -1.0));
+    assert!(has(&round, 11.0, -1.0));
+    assert!(bevel.iter().all(|p| miter.contains(p)));
+}
+
+#[test]
+fn sharp_miter_falls_back_to_bevel() {
+    let spike = [point(0.0, 0.0), point(20.0, 0.0), point(0.0, 2.0)];
+    let miter = stroke(4.0, Cap::Butt, Join::Miter).polyline(&spike);
+    let bevel = stroke(4.0, Cap::Butt, Join::Bevel).polyline(&spike);
+    assert_eq!(miter, bevel);
+}
+
+#[test]
+fn reversal_gets_a_cap() {
+    let back = [point(0.0, 0.0), point(10.0, 0.0), point(0.0, 0.0)];
+    let miter = stroke(4.0, Cap::Butt, Join::Miter).polyline(&back);
+    let round = stroke(4.0, Cap::Round, Join::Bevel).polyline(&back);
+    assert!(has(&miter, 12.0, 2.0) && !has(&miter, 13.0, 0.0));
+    assert!(has(&round, 12.0, 0.0) && !has(&round, 12.0, 2.0));
+}
+
+#[test]
+fn diagonal_square_cap_keeps_corners() {
+    let s = stroke(6.0, Cap::Square, Join::Miter).segment(&[point(0.0, 0.0), point(10.0, 10.0)]);
+    // углы продления — (0, -3√2) и (-3√2, 0), дальше полутолщины от начала
+    assert!(has(&s, 0.0, -4.0) && has(&s, -4.0, 0.0));
+    assert!(!has(&s, 0.0, -5.0) && !has(&s, -5.0, 0.0));
+}
+
+/// Пиксели квадрата 5×5 вокруг вершины `c` как смещения от неё.
+fn around(pixels: &[Point], c: Point) -> Vec<(i32, i32)> {
+    let mut res = pixels
+        .iter()
+        .map(|&p| p - c)
+        .filter(|d| d.x.abs() <= 2.0 && d.y.abs() <= 2.0)
+        .map(|d| (d.x as i32, d.y as i32))
+        .collect::<Vec<_>>();
+    res.sort_unstable();
+    res
+}
+
+/// У замкнутой ломаной стык есть и в первой вершине, так что углы квадрата
+/// закрыты одинаково. Стыки различаются только снаружи угла — у правого
+/// нижнего это смещения, положительные по обеим осям.
+#[test]
+fn closed_polyline_joints() {
+    let square = [
+        point(0.0, 0.0),
+        point(20.0, 0.0),
+        point(20.0, 20.0),
+        point(0.0, 20.0),
+        point(0.0, 0.0),
+    ];
+    let cases: [(Join, &[(i32, i32)]); 3] = [
+        (Join::Miter, &[]),
+        (Join::Round, &[(2, 2)]),
+        (Join::Bevel, &[(1, 2), (2, 1), (2, 2)]),
+    ];
+    for (join, missing) in cases {
+        let pixels = stroke(5.0, Cap::Butt, join).polyline(&square);
+        let mut expected = (-2..=2)
+            .flat_map(|x| (-2..=2).map(move |y| (x, y)))
+            .filter(|d| !missing.contains(d))
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+        assert_eq!(around(&pixels, point(20.0, 20.0)), expected, "{join:?}");
+        let mut mirrored = expected.iter().map(|&(x, y)| (-x, -y)).collect::<Vec<_>>();
+        mirrored.sort_unstable();
+        assert_eq!(around(&pixels, point(0.0, 0.0)), mirrored, "{join:?}");
+    }
+}
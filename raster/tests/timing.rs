@@ -0,0 +1,67 @@
+use raster::timing::{read_csv, series, write_csv, write_json, Sample};
+
+fn sample(group: &str, algorithm: &str, size: u32, mean_ns: f64) -> Sample {
+    Sample {
+        group: group.to_owned(),
+        algorithm: algorithm.to_owned(),
+        size,
+        mean_ns,
+        lower_ns: mean_ns * 0.9,
+        upper_ns: mean_ns * 1.1,
+    }
+}
+
+#[test]
+fn csv_round_trip() {
+    let samples = vec![
+        sample("line", "dda", 100, 1234.5),
+        sample("circle", "midpoint", 10, 0.25),
+    ];
+    let mut buf = vec![];
+    write_csv(&mut buf, &samples).unwrap();
+    assert_eq!(read_csv(buf.as_slice()).unwrap(), samples);
+}
+
+#[test]
+fn csv_errors() {
+    assert!(read_csv("size,mean\n".as_bytes()).is_err());
+    let header = "group,algorithm,size,mean_ns,lower_ns,upper_ns\n";
+    assert!(read_csv(format!("{header}line,dda,10,1\n").as_bytes()).is_err());
+    assert!(read_csv(format!("{header}line,dda,ten,1,1,1\n").as_bytes()).is_err());
+}
+
+#[test]
+fn series_by_algorithm() {
+    let samples = vec![
+        sample("circle", "bresenham", 100, 3.0),
+        sample("circle", "bresenham", 10, 1.0),
+        sample("line", "dda", 10, 7.0),
+        sample("circle", "midpoint", 10, 2.0),
+    ];
+    assert_eq!(
+        series(&samples, "circle"),
+        vec![
+            ("bresenham".to_owned(), vec![(10, 1.0), (100, 3.0)]),
+            ("midpoint".to_owned(), vec![(10, 2.0)]),
+        ]
+    );
+}
+
+#[test]
+fn json_escapes_strings() {
+    let samples = vec![
+        sample("line", "say \"hi\"\u{1b}[0m\\", 10, 1.5),
+        sample("circle", "midpoint\n", 20, 2.0),
+    ];
+    let mut buf = vec![];
+    write_json(&mut buf, &samples).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+    let rows = json.as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    for (row, s) in rows.iter().zip(&samples) {
+        assert_eq!(row["group"], s.group.as_str());
+        assert_eq!(row["algorithm"], s.algorithm.as_str());
+        assert_eq!(row["size"], s.size);
+        assert_eq!(row["mean_ns"], s.mean_ns);
+    }
+}